@@ -2,6 +2,7 @@ mod binary_finder;
 mod error;
 pub mod logger;
 mod response;
+pub mod usage;
 
 pub use error::ClaudeCodeClientError;
 pub use response::CliResponse;
@@ -12,6 +13,8 @@ use std::process::{Command, Stdio};
 
 use serde::de::DeserializeOwned;
 
+use usage::UsageTotals;
+
 const TOOLS_LIST: &str = "AskUserQuestion,Bash,TaskOutput,Edit,ExitPlanMode,Glob,Grep,\
     KillShell,MCPSearch,Read,Skill,Task,TaskCreate,TaskGet,TaskList,TaskUpdate,\
     WebFetch,WebSearch,Write,LSP";
@@ -46,6 +49,7 @@ struct ParsedOutput<T> {
 
 fn parse_cli_output<T: DeserializeOwned>(
     stdout: &[u8],
+    usage: &mut UsageTotals,
 ) -> Result<ParsedOutput<T>, ClaudeCodeClientError> {
     // CLI 출력에서 메시지 배열을 추출한다. 표준 출력 형식은 JSON 배열이지만,
     // 단일 객체가 올 수도 있으므로 둘 다 처리한다.
@@ -64,6 +68,7 @@ fn parse_cli_output<T: DeserializeOwned>(
         .ok_or(ClaudeCodeClientError::NoResultMessage)?;
 
     let response: CliResponse = serde_json::from_value(result_value)?;
+    usage.record(&response);
     if response.is_error {
         return Err(ClaudeCodeClientError::CliReturnedError {
             message: response.result.unwrap_or_default(),
@@ -90,6 +95,9 @@ pub struct ClaudeCodeClient {
     working_directory: PathBuf,
     system_prompt: Option<String>,
     pending_system_prompt: Option<String>,
+    environment: Vec<(String, String)>,
+    /// 아직 엔진이 가져가지 않은 이 클라이언트의 사용량.
+    usage: UsageTotals,
 }
 
 impl ClaudeCodeClient {
@@ -113,12 +121,35 @@ impl ClaudeCodeClient {
         self.pending_system_prompt = Some(prompt);
     }
 
+    /// 지금까지 쌓인 사용량을 넘기고 0으로 되돌린다. 같은 쿼리를 두 번 세지 않도록 엔진은 결과를 받을 때마다 가져간다.
+    pub fn take_usage(&mut self) -> UsageTotals {
+        std::mem::take(&mut self.usage)
+    }
+
+    /// CLI 프로세스에 추가로 넘길 환경 변수. 에이전트가 실행하는 git 명령에도 적용된다.
+    pub fn set_environment(&mut self, environment: Vec<(String, String)>) {
+        self.environment = environment;
+    }
+
     pub fn new(
         api_key: String,
         working_directory: PathBuf,
         system_prompt: Option<String>,
     ) -> Result<Self, ClaudeCodeClientError> {
         let binary_path = binary_finder::find_claude_binary()?;
+        Self::with_binary(binary_path, api_key, working_directory, system_prompt)
+    }
+
+    /// 찾는 대신 지정한 실행 파일을 쓰는 클라이언트. 파일이 없으면 `new`와 같은 오류를 돌려준다.
+    pub fn with_binary(
+        binary_path: PathBuf,
+        api_key: String,
+        working_directory: PathBuf,
+        system_prompt: Option<String>,
+    ) -> Result<Self, ClaudeCodeClientError> {
+        if !binary_path.is_file() {
+            return Err(ClaudeCodeClientError::BinaryNotFound);
+        }
 
         Ok(Self {
             binary_path,
@@ -127,9 +158,26 @@ impl ClaudeCodeClient {
             working_directory,
             system_prompt,
             pending_system_prompt: None,
+            environment: Vec::new(),
+            usage: UsageTotals::default(),
         })
     }
 
+    /// 테스트가 에이전트 결과를 직접 넣을 때 함께 돌려줄 클라이언트. 쿼리하면 실행 파일이 없어 실패한다.
+    #[cfg(test)]
+    pub fn for_tests(working_directory: PathBuf) -> Self {
+        Self {
+            binary_path: PathBuf::from("bear-test-missing-agent"),
+            api_key: String::new(),
+            session_id: None,
+            working_directory,
+            system_prompt: None,
+            pending_system_prompt: None,
+            environment: Vec::new(),
+            usage: UsageTotals::default(),
+        }
+    }
+
     fn build_base_command(&mut self, request: &ClaudeCodeRequest) -> Result<BaseCommandOutput, ClaudeCodeClientError> {
         let model_effort_level = "high";
         let disable_auto_memory = "0";  // 0 = force enable.
@@ -143,6 +191,7 @@ impl ClaudeCodeClient {
             .env("CLAUDE_CODE_EFFORT_LEVEL", model_effort_level)
             .env("CLAUDE_CODE_DISABLE_AUTO_MEMORY", disable_auto_memory)
             .env("CLAUDE_CODE_DISABLE_FEEDBACK_SURVEY", disable_feedback_survey)
+            .envs(self.environment.iter().map(|(key, value)| (key, value)))
             .arg("-p")
             .arg("--allow-dangerously-skip-permissions")
             .arg("--permission-mode").arg("bypassPermissions")
@@ -343,7 +392,7 @@ impl ClaudeCodeClient {
             .unwrap_or("unknown");
         write_debug_log(&sent_system_prompt, &request.user_prompt, command_session_id, &output.stdout);
 
-        let parsed: ParsedOutput<T> = parse_cli_output(&output.stdout)?;
+        let parsed: ParsedOutput<T> = parse_cli_output(&output.stdout, &mut self.usage)?;
 
         if new_session_id.is_some() {
             self.session_id = Some(parsed.session_id);
@@ -509,6 +558,7 @@ impl ClaudeCodeClient {
 
         let result_json = result_value.ok_or(ClaudeCodeClientError::NoResultMessage)?;
         let response: CliResponse = serde_json::from_value(result_json)?;
+        self.usage.record(&response);
 
         if response.is_error {
             let error_message = response.result.unwrap_or_default();
@@ -689,12 +739,35 @@ mod tests {
         ];
         let stdout = make_json_array_output(&messages);
 
-        let parsed: ParsedOutput<TestOutput> = parse_cli_output(&stdout).unwrap();
+        let parsed: ParsedOutput<TestOutput> = parse_cli_output(&stdout, &mut UsageTotals::default()).unwrap();
 
         assert_eq!(parsed.result, TestOutput { answer: "hello".to_string() });
         assert_eq!(parsed.session_id, "sess-1");
     }
 
+    #[test]
+    fn parse_records_query_cost_and_duration() {
+        let mut message = make_result_message(
+            "sess-5",
+            false,
+            Some("cost"),
+            Some(serde_json::json!({"answer": "cost"})),
+        );
+        message["total_cost_usd"] = serde_json::json!(0.5);
+        message["duration_ms"] = serde_json::json!(1200);
+        let mut usage = UsageTotals::default();
+
+        let _: ParsedOutput<TestOutput> = parse_cli_output(&serde_json::to_vec(&message).unwrap(), &mut usage).unwrap();
+        message["is_error"] = serde_json::json!(true);
+        let _ = parse_cli_output::<TestOutput>(&serde_json::to_vec(&message).unwrap(), &mut usage);
+
+        // 오류 응답도 비용이 발생하므로 함께 센다.
+        assert_eq!(
+            usage,
+            UsageTotals { query_count: 2, total_cost_usd: 1.0, agent_duration_ms: 2400 }
+        );
+    }
+
     #[test]
     fn parse_single_result_object() {
         let message = make_result_message(
@@ -705,7 +778,7 @@ mod tests {
         );
         let stdout = serde_json::to_vec(&message).unwrap();
 
-        let parsed: ParsedOutput<TestOutput> = parse_cli_output(&stdout).unwrap();
+        let parsed: ParsedOutput<TestOutput> = parse_cli_output(&stdout, &mut UsageTotals::default()).unwrap();
 
         assert_eq!(parsed.result, TestOutput { answer: "world".to_string() });
         assert_eq!(parsed.session_id, "sess-2");
@@ -719,7 +792,7 @@ mod tests {
         ];
         let stdout = make_json_array_output(&messages);
 
-        let err = parse_cli_output::<TestOutput>(&stdout).unwrap_err();
+        let err = parse_cli_output::<TestOutput>(&stdout, &mut UsageTotals::default()).unwrap_err();
 
         assert!(
             matches!(err, ClaudeCodeClientError::NoResultMessage),
@@ -734,7 +807,7 @@ mod tests {
         ];
         let stdout = make_json_array_output(&messages);
 
-        let err = parse_cli_output::<TestOutput>(&stdout).unwrap_err();
+        let err = parse_cli_output::<TestOutput>(&stdout, &mut UsageTotals::default()).unwrap_err();
 
         assert!(
             matches!(err, ClaudeCodeClientError::MissingStructuredOutput),
@@ -749,7 +822,7 @@ mod tests {
         ];
         let stdout = make_json_array_output(&messages);
 
-        let err = parse_cli_output::<TestOutput>(&stdout).unwrap_err();
+        let err = parse_cli_output::<TestOutput>(&stdout, &mut UsageTotals::default()).unwrap_err();
 
         assert!(
             matches!(err, ClaudeCodeClientError::MissingStructuredOutput),
@@ -764,7 +837,7 @@ mod tests {
         ];
         let stdout = make_json_array_output(&messages);
 
-        let err = parse_cli_output::<TestOutput>(&stdout).unwrap_err();
+        let err = parse_cli_output::<TestOutput>(&stdout, &mut UsageTotals::default()).unwrap_err();
 
         match err {
             ClaudeCodeClientError::CliReturnedError { message } => {
@@ -778,7 +851,7 @@ mod tests {
     fn error_when_invalid_json() {
         let stdout = b"this is not json";
 
-        let err = parse_cli_output::<TestOutput>(stdout).unwrap_err();
+        let err = parse_cli_output::<TestOutput>(stdout, &mut UsageTotals::default()).unwrap_err();
 
         assert!(
             matches!(err, ClaudeCodeClientError::JsonParsingFailed { .. }),
@@ -10,4 +10,8 @@ pub struct CliResponse {
     pub result: Option<String>,
     #[serde(default)]
     pub structured_output: Option<serde_json::Value>,
+    #[serde(default)]
+    pub total_cost_usd: Option<f64>,
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
 }
\ No newline at end of file
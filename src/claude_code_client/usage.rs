@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use super::CliResponse;
+
+/// CLI 쿼리의 누적 사용량. 클라이언트는 자신이 실행한 쿼리를, 엔진은 세션 전체를 센다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageTotals {
+    pub query_count: usize,
+    pub total_cost_usd: f64,
+    pub agent_duration_ms: u64,
+}
+
+impl UsageTotals {
+    /// CLI result 메시지의 비용과 소요 시간을 누적한다. 오류 응답도 비용이 발생하므로 함께 센다.
+    pub fn record(&mut self, response: &CliResponse) {
+        self.query_count += 1;
+        self.total_cost_usd += response.total_cost_usd.unwrap_or_default();
+        self.agent_duration_ms += response.duration_ms.unwrap_or_default();
+    }
+
+    pub fn add(&mut self, other: UsageTotals) {
+        self.query_count += other.query_count;
+        self.total_cost_usd += other.total_cost_usd;
+        self.agent_duration_ms += other.agent_duration_ms;
+    }
+}
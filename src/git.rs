@@ -0,0 +1,206 @@
+mod error;
+
+pub use error::GitError;
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// 저장소에서 진행 중인 git 작업. 끝나거나 중단되기 전에는 브랜치를 옮길 수 없다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+    Rebase,
+    Merge,
+    CherryPick,
+    Revert,
+    Bisect,
+}
+
+impl Operation {
+    pub fn label(self) -> &'static str {
+        match self {
+            Operation::Rebase => "rebase",
+            Operation::Merge => "merge",
+            Operation::CherryPick => "cherry-pick",
+            Operation::Revert => "revert",
+            Operation::Bisect => "bisect",
+        }
+    }
+}
+
+/// git 명령을 실행하고 표준 출력을 끝의 공백을 제거해 반환한다. 실패하면 stderr를 담은 오류를 반환한다.
+pub fn run<I, S>(dir: &Path, args: I) -> Result<String, GitError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let args: Vec<S> = args.into_iter().collect();
+    let command = subcommand_name(&args);
+    let output = execute(dir, &args).map_err(|source| GitError::ExecutionFailed {
+        command: command.clone(),
+        source,
+    })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        crate::cli_log!("[git {} 실패] {}", command, stderr);
+        return Err(GitError::CommandFailed { command, stderr });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+}
+
+/// 종료 코드만 중요한 확인용 명령(`merge-base --is-ancestor` 등)이 성공했는지 반환한다.
+pub fn succeeds<I, S>(dir: &Path, args: I) -> bool
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let args: Vec<S> = args.into_iter().collect();
+    execute(dir, &args).is_ok_and(|output| output.status.success())
+}
+
+/// 출력 언어, 페이저, 자격 증명 프롬프트가 사용자 환경에 따라 달라지지 않도록 고정해서 실행한다.
+fn execute<S: AsRef<OsStr>>(dir: &Path, args: &[S]) -> std::io::Result<Output> {
+    crate::cli_log!(
+        "[git] {} (cwd: {})",
+        args.iter().map(|arg| arg.as_ref().to_string_lossy()).collect::<Vec<_>>().join(" "),
+        dir.display()
+    );
+    Command::new("git")
+        .current_dir(dir)
+        .env("LC_ALL", "C")
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .arg("--no-pager")
+        .args(args)
+        .output()
+}
+
+/// `-c <key>=<value>` 설정 인자를 건너뛴 첫 인자를 오류 메시지에 쓸 서브커맨드로 본다.
+fn subcommand_name<S: AsRef<OsStr>>(args: &[S]) -> String {
+    let mut args = args.iter().map(|arg| arg.as_ref().to_string_lossy());
+    while let Some(arg) = args.next() {
+        if arg == "-c" {
+            args.next();
+            continue;
+        }
+        return arg.to_string();
+    }
+    String::new()
+}
+
+/// 워크트리별 git 디렉토리의 절대 경로. 진행 중인 작업의 표식 파일이 이곳에 있다.
+pub fn git_dir(dir: &Path) -> Result<PathBuf, GitError> {
+    run(dir, ["rev-parse", "--absolute-git-dir"]).map(PathBuf::from)
+}
+
+/// git 디렉토리의 표식 파일로 진행 중인 작업을 찾는다. 메시지 문구와 달리 로캘에 영향을 받지 않는다.
+pub fn operation_in_progress(dir: &Path) -> Result<Option<Operation>, GitError> {
+    let git_dir = git_dir(dir)?;
+    let markers = [
+        ("rebase-merge", Operation::Rebase),
+        ("rebase-apply", Operation::Rebase),
+        ("MERGE_HEAD", Operation::Merge),
+        ("CHERRY_PICK_HEAD", Operation::CherryPick),
+        ("REVERT_HEAD", Operation::Revert),
+        ("BISECT_LOG", Operation::Bisect),
+    ];
+    Ok(markers
+        .into_iter()
+        .find(|(marker, _)| git_dir.join(marker).exists())
+        .map(|(_, operation)| operation))
+}
+
+/// 충돌이 해결되지 않은 파일. `git status --porcelain -z`의 unmerged 상태 코드로 판단한다.
+pub fn unmerged_files(dir: &Path) -> Result<Vec<String>, GitError> {
+    let status = run(dir, ["status", "--porcelain", "-z", "--untracked-files=no"])?;
+    Ok(parse_unmerged_files(&status))
+}
+
+fn parse_unmerged_files(porcelain: &str) -> Vec<String> {
+    const UNMERGED_CODES: [&str; 7] = ["DD", "AU", "UD", "UA", "DU", "AA", "UU"];
+
+    let mut files = Vec::new();
+    let mut entries = porcelain.split('\0').filter(|entry| !entry.is_empty());
+    while let Some(entry) = entries.next() {
+        let (code, path) = entry.split_at(entry.len().min(3));
+        let code = code.trim_end();
+        if UNMERGED_CODES.contains(&code) {
+            files.push(path.to_string());
+        }
+        // 이름 변경과 복사 항목 뒤에는 원래 경로를 담은 항목이 하나 더 온다.
+        if code.starts_with('R') || code.starts_with('C') {
+            entries.next();
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn init_git_repo(dir: &Path) {
+        run(dir, ["init", "-b", "main"]).unwrap();
+        run(dir, ["config", "user.email", "test@test.com"]).unwrap();
+        run(dir, ["config", "user.name", "Test"]).unwrap();
+        run(dir, ["config", "commit.gpgsign", "false"]).unwrap();
+        fs::write(dir.join("shared.txt"), "base\n").unwrap();
+        run(dir, ["add", "shared.txt"]).unwrap();
+        run(dir, ["commit", "-m", "initial commit"]).unwrap();
+    }
+
+    fn commit_change(dir: &Path, content: &str, message: &str) {
+        fs::write(dir.join("shared.txt"), content).unwrap();
+        run(dir, ["commit", "-am", message]).unwrap();
+    }
+
+    #[test]
+    fn failed_command_returns_typed_error_with_stderr() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path());
+
+        let error = run(temp_dir.path(), ["checkout", "missing-branch"]).unwrap_err();
+
+        match error {
+            GitError::CommandFailed { command, stderr } => {
+                assert_eq!(command, "checkout");
+                assert!(stderr.contains("missing-branch"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn conflicting_rebase_is_detected_from_repository_state() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        init_git_repo(dir);
+        run(dir, ["checkout", "-b", "feature"]).unwrap();
+        commit_change(dir, "feature\n", "feature change");
+        run(dir, ["checkout", "main"]).unwrap();
+        commit_change(dir, "main\n", "main change");
+        run(dir, ["checkout", "feature"]).unwrap();
+
+        assert_eq!(operation_in_progress(dir).unwrap(), None);
+        assert!(run(dir, ["rebase", "main"]).is_err());
+
+        assert_eq!(operation_in_progress(dir).unwrap(), Some(Operation::Rebase));
+        assert_eq!(unmerged_files(dir).unwrap(), vec!["shared.txt"]);
+    }
+
+    #[test]
+    fn subcommand_name_skips_config_overrides() {
+        assert_eq!(subcommand_name(&["-c", "user.name=Bear", "commit", "-m", "msg"]), "commit");
+        assert_eq!(subcommand_name::<&str>(&[]), "");
+    }
+
+    #[test]
+    fn parse_unmerged_files_skips_rename_sources() {
+        let porcelain = "R  new.txt\0old.txt\0UU both.txt\0M  staged.txt\0AA added.txt\0";
+
+        assert_eq!(parse_unmerged_files(porcelain), vec!["both.txt", "added.txt"]);
+    }
+}
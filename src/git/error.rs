@@ -0,0 +1,17 @@
+#[derive(Debug, thiserror::Error)]
+pub enum GitError {
+    #[error("failed to execute git {command}: {source}")]
+    ExecutionFailed {
+        command: String,
+        source: std::io::Error,
+    },
+
+    #[error("git {command} failed: {stderr}")]
+    CommandFailed { command: String, stderr: String },
+
+    #[error("working tree has uncommitted changes: {path}")]
+    UncommittedChanges { path: String },
+
+    #[error("git {operation} is in progress: {path}")]
+    OperationInProgress { path: String, operation: &'static str },
+}
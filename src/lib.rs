@@ -1,3 +1,5 @@
 pub mod claude_code_client;
 pub mod config;
+pub mod git;
 pub mod ui;
+pub mod workflow;
@@ -1,13 +1,7 @@
 pub mod app;
-mod clarification;
-pub mod coding;
 mod error;
 mod event;
-mod file_validation;
 mod renderer;
-mod planning;
-mod session_naming;
-mod spec_writing;
 
 pub use error::UiError;
 
@@ -1,24 +1,16 @@
 use std::path::{Path, PathBuf};
-use std::sync::mpsc;
 use std::time::Instant;
 use std::io::Write;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::claude_code_client::{ClaudeCodeClient, ClaudeCodeRequest};
 use crate::config::Config;
-use super::clarification::{self, ClarificationQuestions, QaRound};
-use super::coding::{
-    self, BuildTestCommands, BuildTestOutcome, BuildTestRepairResult,
-    BuildTestRepairStatus, CodingPhaseState, CodingTask, CodingTaskResult,
-    CodingTaskStatus, ConflictResolutionResult, ConflictResolutionStatus,
-    RebaseOutcome, ReviewResult, ReviewStatus, TaskExtractionResponse,
-    TaskReport, TaskWorktreeInfo,
-};
-use super::file_validation::{self, FileKind, FileValidationResponse};
-use super::planning::{self, PlanResponseType, PlanWritingResponse};
-use super::session_naming;
-use super::spec_writing::{self, SpecResponseType, SpecWritingResponse};
+use crate::workflow::cleanup::{self, BearResource};
+use crate::workflow::preflight::{self, PreflightRemedy};
+use crate::workflow::project_config;
+use crate::workflow::session_catalog::{self, SessionSummary};
+use crate::workflow::task_review;
+use crate::workflow::{Engine, WorkflowCommand, WorkflowError, WorkflowEvent, WorkflowPhase};
 use super::error::UiError;
 use super::renderer::{USER_PREFIX, wrap_text_by_char_width};
 
@@ -32,107 +24,40 @@ pub struct ChatMessage {
     pub content: String,
 }
 
-enum InputMode {
+/// 워크플로 엔진이 시작되기 전의 TUI 전용 화면과, 엔진에 입력을 넘기는 화면.
+enum Screen {
     WorkspaceConfirm,
+    PreflightConfirm,
     ModeSelection,
     SessionDirInput,
+    SessionBrowser,
     RequirementsInput,
-    AgentThinking,
-    ClarificationAnswer,
-    SpecClarificationAnswer,
-    SpecFeedback,
-    PlanClarificationAnswer,
-    PlanFeedback,
-    Coding,
-    BuildTestCommandInput,
-    Done,
-}
-
-enum AgentOutcome {
-    Clarification(ClarificationQuestions),
-    SpecWriting(SpecWritingResponse),
-    Planning(PlanWritingResponse),
-    TaskExtraction(TaskExtractionResponse),
-    CodingTaskCompleted(CodingTaskResult),
-    ReviewCompleted(ReviewResult),
-    ConflictResolutionCompleted(ConflictResolutionResult),
-    BuildTestCompleted(BuildTestOutcome),
-    BuildTestRepairCompleted(BuildTestRepairResult),
-    FileValidation(FileValidationResponse),
-}
-
-struct AgentThreadResult {
-    client: ClaudeCodeClient,
-    outcome: Result<AgentOutcome, String>,
-}
-
-enum AgentStreamMessage {
-    SessionName { name: String, date_dir: String },
-    StreamLine(String),
-    Completed(AgentThreadResult),
+    CleanupConfirm,
+    Workflow,
 }
 
 pub struct App {
     pub messages: Vec<ChatMessage>,
-    input_mode: InputMode,
+    screen: Screen,
     pub input_buffer: String,
     pub cursor_position: usize,
     pub terminal_width: u16,
-    pub confirmed_workspace: Option<PathBuf>,
-    pub confirmed_requirements: Option<String>,
     pub should_quit: bool,
     current_directory: PathBuf,
     keyboard_enhancement_enabled: bool,
     config: Config,
-    claude_client: Option<ClaudeCodeClient>,
-    agent_result_receiver: Option<mpsc::Receiver<AgentStreamMessage>>,
-    qa_log: Vec<QaRound>,
-    current_round_questions: Vec<String>,
+    engine: Option<Engine>,
     thinking_started_at: Instant,
-    last_spec_draft: Option<String>,
-    spec_clarification_questions: Vec<String>,
-    last_plan_draft: Option<String>,
-    plan_clarification_questions: Vec<String>,
-    approved_spec: Option<String>,
-    spec_revision_instructions_sent: bool,
-    session_name: Option<String>,
-    session_date_dir: Option<String>,
-    base_journal_dir: Option<PathBuf>,
-    integration_branch: Option<String>,
-    coding_state: Option<CodingPhaseState>,
-    pending_coding_report: Option<String>,
-    review_state: Option<ReviewState>,
-    pending_build_test: Option<PendingBuildTest>,
-    build_test_command_phase: BuildTestCommandPhase,
-    fatal_error: Option<String>,
     selected_mode_index: usize,
-    resumed_session_dir: Option<PathBuf>,
-    resumed_has_plan: bool,
-    pending_validation_kind: Option<FileKind>,
+    cleanup_candidates: Vec<BearResource>,
+    pending_workspace: Option<PathBuf>,
+    pending_remedies: Vec<PreflightRemedy>,
+    sessions: Vec<SessionSummary>,
+    session_filter: String,
+    selected_session_index: usize,
     pub pending_external_editor: bool,
 }
 
-struct PendingBuildTest {
-    task_id: String,
-    report: String,
-    is_retry: bool,
-}
-
-struct ReviewState {
-    task_id: String,
-    report: String,
-    iteration_count: usize,
-    reviewer_client: Option<ClaudeCodeClient>,
-    coding_client: Option<ClaudeCodeClient>,
-}
-
-const MAX_REVIEW_ITERATIONS: usize = 3;
-
-enum BuildTestCommandPhase {
-    BuildCommand,
-    TestCommand,
-}
-
 impl App {
     pub fn new(config: Config) -> Result<Self, UiError> {
         let current_directory = std::env::current_dir()?;
@@ -149,238 +74,138 @@ impl App {
 
         Ok(Self {
             messages,
-            input_mode: InputMode::WorkspaceConfirm,
+            screen: Screen::WorkspaceConfirm,
             input_buffer: String::new(),
             cursor_position: 0,
             terminal_width: 80,
-            confirmed_workspace: None,
-            confirmed_requirements: None,
             should_quit: false,
             current_directory,
             keyboard_enhancement_enabled: false,
             config,
-            claude_client: None,
-            agent_result_receiver: None,
-            qa_log: Vec::new(),
-            current_round_questions: Vec::new(),
+            engine: None,
             thinking_started_at: Instant::now(),
-            last_spec_draft: None,
-            spec_clarification_questions: Vec::new(),
-            last_plan_draft: None,
-            plan_clarification_questions: Vec::new(),
-            approved_spec: None,
-            spec_revision_instructions_sent: false,
-            session_name: None,
-            session_date_dir: None,
-            base_journal_dir: None,
-            integration_branch: None,
-            coding_state: None,
-            pending_coding_report: None,
-            review_state: None,
-            pending_build_test: None,
-            build_test_command_phase: BuildTestCommandPhase::BuildCommand,
-            fatal_error: None,
             selected_mode_index: 0,
-            resumed_session_dir: None,
-            resumed_has_plan: false,
-            pending_validation_kind: None,
+            cleanup_candidates: Vec::new(),
+            pending_workspace: None,
+            pending_remedies: Vec::new(),
+            sessions: Vec::new(),
+            session_filter: String::new(),
+            selected_session_index: 0,
             pending_external_editor: false,
         })
     }
 
     pub fn fatal_error(&self) -> Option<&str> {
-        self.fatal_error.as_deref()
+        self.engine.as_ref().and_then(|engine| engine.fatal_error())
+    }
+
+    fn workflow_phase(&self) -> Option<WorkflowPhase> {
+        match self.screen {
+            Screen::Workflow => self.engine.as_ref().map(|engine| engine.phase()),
+            _ => None,
+        }
     }
 
     pub fn handle_key_event(&mut self, key_event: KeyEvent) {
-        match self.input_mode {
-            InputMode::WorkspaceConfirm => self.handle_workspace_confirm(key_event),
-            InputMode::ModeSelection => self.handle_mode_selection(key_event),
-            InputMode::SessionDirInput => {
+        match self.screen {
+            Screen::WorkspaceConfirm => self.handle_workspace_confirm(key_event),
+            Screen::PreflightConfirm => self.handle_preflight_confirm(key_event),
+            Screen::ModeSelection => self.handle_mode_selection(key_event),
+            Screen::CleanupConfirm => self.handle_cleanup_confirm(key_event),
+            Screen::SessionBrowser => self.handle_session_browser(key_event),
+            Screen::SessionDirInput => {
                 self.handle_single_line_input(key_event, Self::submit_session_dir_path);
             }
-            InputMode::RequirementsInput => {
+            Screen::RequirementsInput => {
                 self.handle_multiline_input(key_event, Self::submit_requirements);
             }
-            InputMode::ClarificationAnswer => {
-                self.handle_multiline_input(key_event, Self::submit_clarification_answer);
-            }
-            InputMode::SpecClarificationAnswer => {
-                self.handle_multiline_input(key_event, Self::submit_spec_clarification_answer);
-            }
-            InputMode::SpecFeedback => {
-                if key_event.code == KeyCode::Char('a')
-                    && key_event.modifiers.contains(KeyModifiers::CONTROL)
-                {
-                    self.approve_spec();
-                } else {
-                    self.handle_multiline_input(key_event, Self::submit_spec_feedback);
-                }
-            }
-            InputMode::PlanClarificationAnswer => {
-                self.handle_multiline_input(key_event, Self::submit_plan_clarification_answer);
-            }
-            InputMode::PlanFeedback => {
-                if key_event.code == KeyCode::Char('a')
-                    && key_event.modifiers.contains(KeyModifiers::CONTROL)
-                {
-                    self.approve_plan();
-                } else {
-                    self.handle_multiline_input(key_event, Self::submit_plan_feedback);
-                }
-            }
-            InputMode::BuildTestCommandInput => {
-                self.handle_multiline_input(key_event, Self::submit_build_test_command);
-            }
-            InputMode::AgentThinking | InputMode::Coding | InputMode::Done => {
-                if key_event.code == KeyCode::Esc {
-                    self.should_quit = true;
-                }
+            Screen::Workflow => self.handle_workflow_key(key_event),
+        }
+    }
+
+    fn handle_workflow_key(&mut self, key_event: KeyEvent) {
+        let phase = match self.workflow_phase() {
+            Some(phase) => phase,
+            None => return,
+        };
+
+        if phase.accepts_approval()
+            && key_event.code == KeyCode::Char('a')
+            && key_event.modifiers.contains(KeyModifiers::CONTROL)
+        {
+            self.approve(phase);
+        } else if phase.is_waiting_for_input() {
+            self.handle_multiline_input(key_event, Self::submit_workflow_input);
+        } else if matches!(phase, WorkflowPhase::Coding | WorkflowPhase::Paused) {
+            self.handle_coding_control_key(key_event, phase);
+        } else if phase == WorkflowPhase::Done && key_event.code == KeyCode::Char('b') {
+            self.send_workflow_command(WorkflowCommand::ExportReportBundle);
+        } else if key_event.code == KeyCode::Esc {
+            self.should_quit = true;
+        }
+    }
+
+    fn handle_coding_control_key(&mut self, key_event: KeyEvent, phase: WorkflowPhase) {
+        match key_event.code {
+            KeyCode::Char('p') if phase == WorkflowPhase::Paused => {
+                self.send_workflow_command(WorkflowCommand::Resume);
             }
+            KeyCode::Char('p') => self.send_workflow_command(WorkflowCommand::Pause),
+            KeyCode::Char('s') => self.send_workflow_command(WorkflowCommand::Stop),
+            KeyCode::Esc => self.should_quit = true,
+            _ => {}
         }
     }
 
     pub fn handle_paste(&mut self, text: String) {
-        match self.input_mode {
-            InputMode::WorkspaceConfirm
-            | InputMode::SessionDirInput => {
+        match self.screen {
+            Screen::WorkspaceConfirm | Screen::SessionDirInput => {
                 let cleaned = text.replace("\r\n", " ").replace(['\r', '\n'], " ");
                 self.insert_text_at_cursor(&cleaned);
             }
-            InputMode::ModeSelection => {}
-            InputMode::RequirementsInput
-            | InputMode::ClarificationAnswer
-            | InputMode::SpecClarificationAnswer
-            | InputMode::SpecFeedback
-            | InputMode::PlanClarificationAnswer
-            | InputMode::PlanFeedback
-            | InputMode::BuildTestCommandInput => {
+            Screen::ModeSelection | Screen::PreflightConfirm | Screen::CleanupConfirm => {}
+            Screen::SessionBrowser => {
+                self.session_filter.push_str(&text.replace(['\r', '\n'], " "));
+                self.selected_session_index = 0;
+            }
+            Screen::RequirementsInput => {
                 let cleaned = text.replace("\r\n", "\n").replace('\r', "\n");
                 self.insert_text_at_cursor(&cleaned);
             }
-            InputMode::AgentThinking | InputMode::Coding | InputMode::Done => {}
+            Screen::Workflow => {
+                if self.is_waiting_for_input() {
+                    let cleaned = text.replace("\r\n", "\n").replace('\r', "\n");
+                    self.insert_text_at_cursor(&cleaned);
+                }
+            }
         }
     }
 
     pub fn tick(&mut self) {
-        self.tick_agent_result();
-    }
-
-    fn tick_agent_result(&mut self) {
-        let receiver = match self.agent_result_receiver.take() {
-            Some(r) => r,
+        let events = match self.engine.as_mut() {
+            Some(engine) => engine.poll_events(),
             None => return,
         };
 
-        loop {
-            match receiver.try_recv() {
-                Ok(AgentStreamMessage::SessionName { name, date_dir }) => {
-                    if self.base_journal_dir.is_none()
-                        && let Some(ws) = &self.confirmed_workspace
-                    {
-                        self.base_journal_dir =
-                            Some(ws.join(".bear").join(&date_dir).join(&name));
-                    }
-                    let journal_dir = self.journal_dir();
-                    if let Some(user_request) = &self.confirmed_requirements
-                        && let Err(err) =
-                            spec_writing::save_user_request(&journal_dir, user_request)
-                    {
-                        self.add_system_message(
-                            &format!("사용자 요청 파일 저장 실패: {}", err),
-                        );
-                    }
-                    self.session_name = Some(name.clone());
-                    self.session_date_dir = Some(date_dir);
-
-                    if self.integration_branch.is_none()
-                        && let Some(ws) = self.confirmed_workspace.clone()
-                    {
-                        match coding::create_integration_branch(&ws, &name) {
-                            Ok(branch) => {
-                                self.add_system_message(
-                                    &format!("통합 브랜치 생성: {}", branch),
-                                );
-                                self.integration_branch = Some(branch);
-
-                                let user_request_path =
-                                    journal_dir.join("user-request.md");
-                                if user_request_path.exists()
-                                    && let Err(err) =
-                                        coding::commit_file_in_workspace(
-                                            &ws,
-                                            &user_request_path,
-                                            "Add user request",
-                                        )
-                                {
-                                    self.add_system_message(&format!(
-                                        "사용자 요청 파일 커밋 실패: {}",
-                                        err,
-                                    ));
-                                }
-                            }
-                            Err(err) => {
-                                self.add_system_message(&format!(
-                                    "통합 브랜치 생성 실패: {}",
-                                    err,
-                                ));
-                            }
-                        }
-                    }
-                }
-                Ok(AgentStreamMessage::StreamLine(line)) => {
-                    self.add_system_message(&line);
-                }
-                Ok(AgentStreamMessage::Completed(result)) => {
-                    self.claude_client = Some(result.client);
-                    match result.outcome {
-                        Ok(AgentOutcome::Clarification(response)) => {
-                            self.handle_clarification_response(response);
-                        }
-                        Ok(AgentOutcome::SpecWriting(response)) => {
-                            self.handle_spec_response(response);
-                        }
-                        Ok(AgentOutcome::Planning(response)) => {
-                            self.handle_plan_response(response);
-                        }
-                        Ok(AgentOutcome::TaskExtraction(response)) => {
-                            self.handle_task_extraction_response(response);
-                        }
-                        Ok(AgentOutcome::CodingTaskCompleted(result)) => {
-                            self.handle_coding_task_result(result);
-                        }
-                        Ok(AgentOutcome::ReviewCompleted(result)) => {
-                            self.handle_review_result(result);
-                        }
-                        Ok(AgentOutcome::ConflictResolutionCompleted(result)) => {
-                            self.handle_conflict_resolution_result(result);
-                        }
-                        Ok(AgentOutcome::BuildTestCompleted(outcome)) => {
-                            self.handle_build_test_result(outcome);
-                        }
-                        Ok(AgentOutcome::BuildTestRepairCompleted(result)) => {
-                            self.handle_build_test_repair_result(result);
-                        }
-                        Ok(AgentOutcome::FileValidation(result)) => {
-                            self.handle_file_validation_result(result);
-                        }
-                        Err(error_message) => {
-                            if matches!(self.input_mode, InputMode::Coding) {
-                                self.handle_coding_task_error(error_message);
-                            } else {
-                                self.handle_agent_error(error_message);
-                            }
-                        }
+        for event in events {
+            self.apply_workflow_event(event);
+        }
+    }
+
+    fn apply_workflow_event(&mut self, event: WorkflowEvent) {
+        match event {
+            WorkflowEvent::Message(content) | WorkflowEvent::AgentOutput(content) => {
+                self.add_system_message(&content);
+            }
+            WorkflowEvent::PhaseChanged(phase) => {
+                self.thinking_started_at = Instant::now();
+                match phase {
+                    // 이전 세션 파일 검증에 실패하면 엔진이 Idle로 돌아온다.
+                    WorkflowPhase::Idle if matches!(self.screen, Screen::Workflow) => {
+                        self.transition_to_session_dir_input();
                     }
-                    return;
-                }
-                Err(mpsc::TryRecvError::Empty) => {
-                    self.agent_result_receiver = Some(receiver);
-                    return;
-                }
-                Err(mpsc::TryRecvError::Disconnected) => {
-                    self.handle_agent_error("에이전트 통신이 중단되었습니다.".to_string());
-                    return;
+                    WorkflowPhase::Stopped | WorkflowPhase::Failed => self.should_quit = true,
+                    _ => {}
                 }
             }
         }
@@ -391,55 +216,60 @@ impl App {
     }
 
     pub fn is_waiting_for_input(&self) -> bool {
-        matches!(
-            self.input_mode,
-            InputMode::WorkspaceConfirm
-                | InputMode::SessionDirInput
-                | InputMode::RequirementsInput
-                | InputMode::ClarificationAnswer
-                | InputMode::SpecClarificationAnswer
-                | InputMode::SpecFeedback
-                | InputMode::PlanClarificationAnswer
-                | InputMode::PlanFeedback
-                | InputMode::BuildTestCommandInput
-        )
+        match self.screen {
+            Screen::WorkspaceConfirm | Screen::SessionDirInput | Screen::RequirementsInput => true,
+            Screen::ModeSelection
+            | Screen::PreflightConfirm
+            | Screen::SessionBrowser
+            | Screen::CleanupConfirm => false,
+            Screen::Workflow => self
+                .workflow_phase()
+                .is_some_and(|phase| phase != WorkflowPhase::Idle && phase.is_waiting_for_input()),
+        }
     }
 
     pub fn is_mode_selection(&self) -> bool {
-        matches!(self.input_mode, InputMode::ModeSelection)
+        matches!(self.screen, Screen::ModeSelection)
     }
 
     pub fn selected_mode_index(&self) -> usize {
         self.selected_mode_index
     }
 
-    fn journal_dir(&self) -> PathBuf {
-        if let Some(coding_state) = &self.coding_state
-            && let Some(worktree_info) = &coding_state.current_task_worktree
-            && let (Some(date), Some(name)) = (&self.session_date_dir, &self.session_name)
-        {
-            return worktree_info.worktree_path.join(".bear").join(date).join(name);
-        }
-        self.workspace_journal_dir()
+    pub fn is_session_browser(&self) -> bool {
+        matches!(self.screen, Screen::SessionBrowser)
     }
 
-    fn workspace_journal_dir(&self) -> PathBuf {
-        if let Some(dir) = &self.base_journal_dir {
-            return dir.clone();
-        }
-        match (&self.confirmed_workspace, &self.session_date_dir, &self.session_name) {
-            (Some(ws), Some(date), Some(name)) => ws.join(".bear").join(date).join(name),
-            _ => PathBuf::new(),
-        }
+    pub fn session_filter(&self) -> &str {
+        &self.session_filter
+    }
+
+    pub fn selected_session_index(&self) -> usize {
+        self.selected_session_index
+    }
+
+    /// 검색어로 거른 세션 목록의 표시용 줄.
+    pub fn session_browser_lines(&self) -> Vec<String> {
+        self.filtered_sessions()
+            .into_iter()
+            .map(session_catalog::format_session_line)
+            .collect()
+    }
+
+    fn filtered_sessions(&self) -> Vec<&SessionSummary> {
+        session_catalog::filter_sessions(&self.sessions, &self.session_filter)
     }
 
     pub fn is_thinking(&self) -> bool {
-        matches!(self.input_mode, InputMode::AgentThinking | InputMode::Coding)
+        matches!(
+            self.workflow_phase(),
+            Some(WorkflowPhase::AgentWorking | WorkflowPhase::Coding)
+        )
     }
 
     pub fn thinking_indicator(&self) -> &'static str {
         let dots = (self.thinking_started_at.elapsed().as_millis() / 500) % 4;
-        if matches!(self.input_mode, InputMode::Coding) {
+        if self.workflow_phase() == Some(WorkflowPhase::Coding) {
             match dots {
                 0 => "Coding",
                 1 => "Coding.",
@@ -457,37 +287,65 @@ impl App {
     }
 
     pub fn help_text(&self) -> &str {
-        match self.input_mode {
-            InputMode::WorkspaceConfirm
-            | InputMode::SessionDirInput => "[Enter] Confirm  [Esc] Quit",
-            InputMode::ModeSelection => {
-                "[1-2] Select  [Up/Down] Navigate  [Enter] Confirm  [Esc] Quit"
-            }
-            InputMode::RequirementsInput
-            | InputMode::ClarificationAnswer
-            | InputMode::SpecClarificationAnswer
-            | InputMode::PlanClarificationAnswer => {
+        match self.screen {
+            Screen::WorkspaceConfirm | Screen::SessionDirInput => "[Enter] Confirm  [Esc] Quit",
+            Screen::ModeSelection => {
+                "[1-4] Select  [Up/Down] Navigate  [Enter] Confirm  [Esc] Quit"
+            }
+            Screen::SessionBrowser => {
+                "[Type] Filter  [Up/Down] Navigate  [Enter] Resume  [Esc] Back"
+            }
+            Screen::PreflightConfirm => "[Y] Apply fixes  [N] Choose another workspace  [Esc] Quit",
+            Screen::CleanupConfirm => "[Y] Prune  [N] Cancel  [Esc] Quit",
+            Screen::RequirementsInput => self.answer_help_text(),
+            Screen::Workflow => self.workflow_help_text(),
+        }
+    }
+
+    fn workflow_help_text(&self) -> &str {
+        match self.workflow_phase() {
+            Some(
+                WorkflowPhase::ClarificationAnswer
+                | WorkflowPhase::SpecClarificationAnswer
+                | WorkflowPhase::PlanClarificationAnswer
+                | WorkflowPhase::BuildTestCommandInput,
+            ) => self.answer_help_text(),
+            Some(WorkflowPhase::SpecFeedback | WorkflowPhase::PlanFeedback) => {
                 if self.keyboard_enhancement_enabled {
-                    "[Enter] Submit  [Shift+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
+                    "[Enter] Submit feedback  [Ctrl+A] Approve  [Shift+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
                 } else {
-                    "[Enter] Submit  [Alt+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
+                    "[Enter] Submit feedback  [Ctrl+A] Approve  [Alt+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
                 }
             }
-            InputMode::SpecFeedback | InputMode::PlanFeedback => {
+            Some(WorkflowPhase::TaskReview) => {
                 if self.keyboard_enhancement_enabled {
-                    "[Enter] Submit feedback  [Ctrl+A] Approve  [Shift+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
+                    "[Enter] Apply command  [Ctrl+A] Start coding  [Shift+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
                 } else {
-                    "[Enter] Submit feedback  [Ctrl+A] Approve  [Alt+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
+                    "[Enter] Apply command  [Ctrl+A] Start coding  [Alt+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
                 }
             }
-            InputMode::BuildTestCommandInput => {
+            Some(WorkflowPhase::AcceptanceReview) => {
                 if self.keyboard_enhancement_enabled {
-                    "[Enter] Submit  [Shift+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
+                    "[Enter] Request changes  [Ctrl+A] Approve  [Shift+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
                 } else {
-                    "[Enter] Submit  [Alt+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
+                    "[Enter] Request changes  [Ctrl+A] Approve  [Alt+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
                 }
             }
-            InputMode::AgentThinking | InputMode::Coding | InputMode::Done => "[Esc] Quit",
+            Some(WorkflowPhase::Coding) => {
+                "[P] Pause after current task  [S] Stop after current task  [Esc] Quit"
+            }
+            Some(WorkflowPhase::Paused) => "[P] Resume  [S] Stop  [Esc] Quit",
+            Some(WorkflowPhase::Delivery) => "[Enter] Choose delivery method  [Esc] Quit",
+            Some(WorkflowPhase::Done) => "[B] Bundle report and artifacts  [Esc] Quit",
+            _ => "[Esc] Quit",
+        }
+    }
+
+    fn answer_help_text(&self) -> &'static str {
+        if self.keyboard_enhancement_enabled {
+            "[Enter] Submit  [Shift+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
+        } else {
+            "[Enter] Submit  [Alt+Enter] New line  [Ctrl+G] Editor  [Esc] Quit"
         }
     }
 
@@ -508,13 +366,8 @@ impl App {
                     path
                 };
                 self.add_user_message(&workspace.display().to_string());
-                self.add_system_message(&format!(
-                    "워크스페이스가 설정되었습니다: {}",
-                    workspace.display()
-                ));
-                self.confirmed_workspace = Some(workspace);
                 self.clear_input();
-                self.transition_to_mode_selection();
+                self.run_preflight_checks(workspace);
             }
             _ => {
                 self.handle_single_line_key(key_event);
@@ -522,6 +375,90 @@ impl App {
         }
     }
 
+    /// 워크스페이스가 세션을 시작할 수 있는 git 저장소인지 점검한다. 문제가 없으면 바로 진행하고,
+    /// 모두 Bear가 해결할 수 있으면 조치를 제안하며, 그렇지 않으면 다른 워크스페이스를 고르게 한다.
+    fn run_preflight_checks(&mut self, workspace: PathBuf) {
+        let issues = preflight::run_preflight_checks(&workspace);
+        if issues.is_empty() {
+            self.confirm_workspace(workspace);
+            return;
+        }
+
+        self.add_system_message(&preflight::format_preflight_report(&issues));
+        match preflight::planned_remedies(&issues) {
+            Some(remedies) => {
+                self.add_system_message("위 조치를 실행하고 계속할까요? (y/n)");
+                self.pending_workspace = Some(workspace);
+                self.pending_remedies = remedies;
+                self.screen = Screen::PreflightConfirm;
+            }
+            None => {
+                self.add_system_message(
+                    "이 상태에서는 세션을 시작할 수 없습니다. 문제를 정리한 뒤 워크스페이스 절대 경로를 다시 입력하거나, Enter를 눌러 현재 워크스페이스를 사용하세요.",
+                );
+                self.screen = Screen::WorkspaceConfirm;
+            }
+        }
+    }
+
+    fn handle_preflight_confirm(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                self.add_user_message("y");
+                self.apply_preflight_remedies();
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                self.add_user_message("n");
+                self.pending_workspace = None;
+                self.pending_remedies.clear();
+                self.add_system_message(
+                    "새로운 워크스페이스 절대 경로를 입력하거나, Enter를 눌러 현재 워크스페이스를 사용하세요.",
+                );
+                self.screen = Screen::WorkspaceConfirm;
+            }
+            KeyCode::Esc => self.should_quit = true,
+            _ => {}
+        }
+    }
+
+    fn apply_preflight_remedies(&mut self) {
+        let workspace = self.pending_workspace.take().unwrap();
+        for remedy in std::mem::take(&mut self.pending_remedies) {
+            if let Err(err) = preflight::apply_remedy(&workspace, remedy) {
+                self.add_system_message(&format!("조치 실패: {}", err));
+                self.screen = Screen::WorkspaceConfirm;
+                return;
+            }
+            self.add_system_message(&format!("완료: {}", remedy.description()));
+        }
+        // 새로 만든 저장소에서는 커밋이 없는 등 다른 문제가 드러날 수 있으므로 다시 점검한다.
+        self.run_preflight_checks(workspace);
+    }
+
+    fn confirm_workspace(&mut self, workspace: PathBuf) {
+        let project_config = match project_config::load_project_config(&workspace) {
+            Ok(project_config) => project_config,
+            Err(err) => {
+                self.add_system_message(&format!(
+                    "프로젝트 설정을 읽을 수 없습니다: {}\n설정을 고친 뒤 워크스페이스 절대 경로를 다시 입력하거나, Enter를 눌러 현재 워크스페이스를 사용하세요.",
+                    err
+                ));
+                self.screen = Screen::WorkspaceConfirm;
+                return;
+            }
+        };
+        self.add_system_message(&format!(
+            "워크스페이스가 설정되었습니다: {}",
+            workspace.display()
+        ));
+        self.engine = Some(Engine::new(
+            self.config.api_key().to_string(),
+            workspace,
+            project_config,
+        ));
+        self.transition_to_mode_selection();
+    }
+
     fn handle_multiline_input(
         &mut self,
         key_event: KeyEvent,
@@ -594,11 +531,13 @@ impl App {
                 self.selected_mode_index = self.selected_mode_index.saturating_sub(1);
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                self.selected_mode_index = (self.selected_mode_index + 1).min(1);
+                self.selected_mode_index = (self.selected_mode_index + 1).min(3);
             }
             KeyCode::Enter => self.select_work_mode(self.selected_mode_index),
             KeyCode::Char('1') => self.select_work_mode(0),
             KeyCode::Char('2') => self.select_work_mode(1),
+            KeyCode::Char('3') => self.select_work_mode(2),
+            KeyCode::Char('4') => self.select_work_mode(3),
             KeyCode::Esc => self.should_quit = true,
             _ => {}
         }
@@ -609,13 +548,17 @@ impl App {
 
         let label = match index {
             0 => "처음부터 만들기",
-            _ => "이전 세션 이어서",
+            1 => "이전 세션 이어서",
+            2 => "Bear 워크트리/브랜치 정리",
+            _ => "세션 브라우저",
         };
         self.add_user_message(label);
 
         match index {
             0 => self.transition_to_requirements_input(),
             1 => self.transition_to_session_dir_input(),
+            2 => self.scan_cleanup_candidates(),
+            3 => self.open_session_browser(),
             _ => unreachable!(),
         }
     }
@@ -626,22 +569,148 @@ impl App {
             "작업 모드를 선택하세요:\n\
              \n\
              1. 처음부터 만들기\n\
-             2. 이전 세션 이어서",
+             2. 이전 세션 이어서\n\
+             3. Bear 워크트리/브랜치 정리\n\
+             4. 세션 브라우저",
         );
-        self.input_mode = InputMode::ModeSelection;
+        self.screen = Screen::ModeSelection;
+    }
+
+    fn open_session_browser(&mut self) {
+        let workspace = self.engine.as_ref().unwrap().workspace().to_path_buf();
+        self.sessions = session_catalog::scan_sessions(&workspace);
+        if self.sessions.is_empty() {
+            self.add_system_message("이전 세션이 없습니다.");
+            self.transition_to_mode_selection();
+            return;
+        }
+
+        self.add_system_message(&format!(
+            "{}개의 세션을 찾았습니다. 검색어를 입력해 목록을 좁히고, Enter로 선택한 세션을 이어서 진행하세요.",
+            self.sessions.len(),
+        ));
+        self.session_filter.clear();
+        self.selected_session_index = 0;
+        self.screen = Screen::SessionBrowser;
+    }
+
+    fn handle_session_browser(&mut self, key_event: KeyEvent) {
+        let matched_count = self.filtered_sessions().len();
+        match key_event.code {
+            KeyCode::Up => {
+                self.selected_session_index = self.selected_session_index.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.selected_session_index =
+                    (self.selected_session_index + 1).min(matched_count.saturating_sub(1));
+            }
+            KeyCode::Enter => self.resume_selected_session(),
+            KeyCode::Backspace => {
+                self.session_filter.pop();
+                self.selected_session_index = 0;
+            }
+            KeyCode::Char(c) if !key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.session_filter.push(c);
+                self.selected_session_index = 0;
+            }
+            KeyCode::Esc => {
+                self.sessions.clear();
+                self.transition_to_mode_selection();
+            }
+            _ => {}
+        }
+    }
+
+    fn resume_selected_session(&mut self) {
+        let Some(session) = self
+            .filtered_sessions()
+            .get(self.selected_session_index)
+            .map(|session| (*session).clone())
+        else {
+            return;
+        };
+
+        self.add_user_message(&session_catalog::format_session_line(&session));
+        if let Err(err) = self.start_resume(session.dir.display().to_string()) {
+            self.add_system_message(&err.to_string());
+            return;
+        }
+        self.sessions.clear();
+    }
+
+    fn scan_cleanup_candidates(&mut self) {
+        let workspace = self.engine.as_ref().unwrap().workspace().to_path_buf();
+        let resources = match cleanup::scan_bear_resources(&workspace) {
+            Ok(resources) => resources,
+            Err(err) => {
+                self.add_system_message(&format!("Bear 리소스 조회 실패: {}", err));
+                self.transition_to_mode_selection();
+                return;
+            }
+        };
+
+        self.add_system_message(&cleanup::format_resource_list(&resources));
+
+        let prunable_count = resources.iter().filter(|r| r.is_prunable()).count();
+        if prunable_count == 0 {
+            self.transition_to_mode_selection();
+            return;
+        }
+
+        self.add_system_message(&format!(
+            "유지 표시가 없는 {}개 항목을 정리하고 git worktree prune을 실행할까요? (y/n)",
+            prunable_count,
+        ));
+        self.cleanup_candidates = resources;
+        self.screen = Screen::CleanupConfirm;
+    }
+
+    fn handle_cleanup_confirm(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                self.add_user_message("y");
+                self.prune_cleanup_candidates();
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                self.add_user_message("n");
+                self.cleanup_candidates.clear();
+                self.transition_to_mode_selection();
+            }
+            KeyCode::Esc => self.should_quit = true,
+            _ => {}
+        }
+    }
+
+    fn prune_cleanup_candidates(&mut self) {
+        let workspace = self.engine.as_ref().unwrap().workspace().to_path_buf();
+        let candidates = std::mem::take(&mut self.cleanup_candidates);
+        let errors = cleanup::prune_bear_resources(&workspace, &candidates);
+
+        if errors.is_empty() {
+            self.add_system_message("정리가 완료되었습니다.");
+        } else {
+            self.add_system_message(&format!(
+                "일부 항목을 정리하지 못했습니다:\n{}",
+                errors.join("\n"),
+            ));
+        }
+        self.transition_to_mode_selection();
     }
 
     fn transition_to_session_dir_input(&mut self) {
         self.add_system_message(
             "이전 세션 디렉토리 경로를 입력하세요. (절대 경로 또는 상대 경로)",
         );
-        self.input_mode = InputMode::SessionDirInput;
+        self.screen = Screen::SessionDirInput;
         self.clear_input();
     }
 
     fn transition_to_requirements_input(&mut self) {
-        self.add_system_message("구현할 요구사항을 입력하세요.");
-        self.input_mode = InputMode::RequirementsInput;
+        self.add_system_message(
+            "구현할 요구사항을 입력하세요.\n\
+             `@<경로>` 줄로 Markdown 파일, 디렉토리, GitHub/GitLab 이슈 JSON을 첨부할 수 있습니다.",
+        );
+        self.screen = Screen::RequirementsInput;
     }
 
     fn submit_session_dir_path(&mut self) {
@@ -653,1787 +722,124 @@ impl App {
         self.add_user_message(&raw_path);
         self.clear_input();
 
-        let workspace = self.confirmed_workspace.clone().unwrap();
-        let resolved_dir =
-            match file_validation::validate_directory_locally(&raw_path, &workspace) {
-                Ok(dir) => dir,
-                Err(error_message) => {
-                    self.add_system_message(&error_message);
-                    self.add_system_message(
-                        "이전 세션 디렉토리 경로를 다시 입력하세요. (절대 경로 또는 상대 경로)",
-                    );
-                    return;
-                }
-            };
-
-        let spec_path = resolved_dir.join("spec.md");
-        if !spec_path.is_file() {
-            self.add_system_message(&format!(
-                "디렉토리에 spec.md 파일이 없습니다: {}",
-                resolved_dir.display()
-            ));
+        if let Err(err) = self.start_resume(raw_path) {
+            self.add_system_message(&err.to_string());
             self.add_system_message(
                 "이전 세션 디렉토리 경로를 다시 입력하세요. (절대 경로 또는 상대 경로)",
             );
-            return;
         }
-
-        let has_plan = resolved_dir.join("plan.md").is_file();
-
-        self.resumed_session_dir = Some(resolved_dir);
-        self.resumed_has_plan = has_plan;
-        self.pending_validation_kind = Some(FileKind::Spec);
-        self.add_system_message("스펙 파일을 검증 중입니다...");
-        self.start_file_content_validation(spec_path);
     }
 
-    fn start_file_content_validation(&mut self, path: PathBuf) {
-        if let Err(error_message) = self.ensure_claude_client() {
-            self.add_system_message(&format!("클라이언트 생성 실패: {}", error_message));
-            self.input_mode = InputMode::Done;
-            return;
-        }
-
-        let mut client = self.claude_client.take().expect("client must be available");
-        client.reset_session();
-        client.set_system_prompt(Some(file_validation::system_prompt().to_string()));
-
-        let kind = self.pending_validation_kind.unwrap();
-
-        let (sender, receiver) = mpsc::channel();
-        self.agent_result_receiver = Some(receiver);
-        self.input_mode = InputMode::AgentThinking;
-        self.thinking_started_at = Instant::now();
-
-        std::thread::spawn(move || {
-            let request = ClaudeCodeRequest {
-                user_prompt: file_validation::build_validation_prompt(&path, kind),
-                output_schema: file_validation::validation_schema(),
-            };
-
-            let outcome = client
-                .query::<FileValidationResponse>(&request)
-                .map(AgentOutcome::FileValidation)
-                .map_err(|err| err.to_string());
-
-            let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                client,
-                outcome,
-            }));
-        });
+    fn start_resume(&mut self, session_dir: String) -> Result<(), WorkflowError> {
+        let command = WorkflowCommand::ResumeSession { session_dir };
+        self.engine.as_mut().unwrap().handle_command(command)?;
+        self.screen = Screen::Workflow;
+        Ok(())
     }
 
-    fn handle_file_validation_result(&mut self, result: FileValidationResponse) {
-        let kind = self.pending_validation_kind.take().unwrap_or(FileKind::Spec);
-
-        if !result.valid {
-            self.add_system_message(&format!("파일 검증 실패: {}", result.reason));
-            self.resumed_session_dir = None;
-            self.resumed_has_plan = false;
-            self.transition_to_session_dir_input();
+    fn submit_requirements(&mut self) {
+        let requirements = self.input_buffer.trim().to_string();
+        if requirements.is_empty() {
             return;
         }
 
-        match kind {
-            FileKind::Spec => {
-                let session_dir = self.resumed_session_dir.clone().unwrap();
-                let spec_path = session_dir.join("spec.md");
-                match std::fs::read_to_string(&spec_path) {
-                    Ok(content) => {
-                        self.approved_spec = Some(content);
-                        self.add_system_message("스펙 파일이 검증되었습니다.");
-
-                        if self.resumed_has_plan {
-                            let plan_path = session_dir.join("plan.md");
-                            self.pending_validation_kind = Some(FileKind::Plan);
-                            self.add_system_message("플랜 파일을 검증 중입니다...");
-                            self.start_file_content_validation(plan_path);
-                        } else {
-                            self.start_resumed_session_workflow();
-                        }
-                    }
-                    Err(err) => {
-                        self.add_system_message(&format!("스펙 파일 읽기 실패: {}", err));
-                        self.resumed_session_dir = None;
-                        self.transition_to_session_dir_input();
-                    }
-                }
+        self.add_user_message(&requirements);
+        self.clear_input();
+
+        let command = WorkflowCommand::StartSession { requirements };
+        match self.engine.as_mut().unwrap().handle_command(command) {
+            Ok(()) => {
+                self.screen = Screen::Workflow;
+                self.tick();
             }
-            FileKind::Plan => {
-                let session_dir = self.resumed_session_dir.clone().unwrap();
-                let plan_path = session_dir.join("plan.md");
-                match std::fs::read_to_string(&plan_path) {
-                    Ok(content) => {
-                        self.last_plan_draft = Some(content);
-                        self.add_system_message("플랜 파일이 검증되었습니다.");
-                        self.start_resumed_session_workflow();
-                    }
-                    Err(err) => {
-                        self.add_system_message(&format!("플랜 파일 읽기 실패: {}", err));
-                        self.resumed_session_dir = None;
-                        self.resumed_has_plan = false;
-                        self.transition_to_session_dir_input();
-                    }
-                }
+            Err(err) => {
+                self.add_system_message(&err.to_string());
+                self.add_system_message("요구사항을 다시 입력하세요.");
             }
         }
     }
 
-    fn start_resumed_session_workflow(&mut self) {
-        if let Err(error_message) = self.ensure_claude_client() {
-            self.add_system_message(&format!("클라이언트 생성 실패: {}", error_message));
-            self.input_mode = InputMode::Done;
+    fn submit_workflow_input(&mut self) {
+        let input = self.input_buffer.trim().to_string();
+        if input.is_empty() {
             return;
         }
 
-        let mut client = self.claude_client.take().expect("client must be available");
-        client.reset_session();
+        if self.workflow_phase() == Some(WorkflowPhase::TaskReview)
+            && self.open_task_edit_template(&input)
+        {
+            return;
+        }
 
-        let has_plan = self.resumed_has_plan;
-        let resumed_dir = self.resumed_session_dir.clone().unwrap();
-        let workspace = self.confirmed_workspace.clone().unwrap();
+        self.add_user_message(&input);
+        self.clear_input();
 
-        let (sender, receiver) = mpsc::channel();
-        self.agent_result_receiver = Some(receiver);
-        self.input_mode = InputMode::AgentThinking;
-        self.thinking_started_at = Instant::now();
+        let command = match self.workflow_phase() {
+            Some(
+                WorkflowPhase::SpecFeedback
+                | WorkflowPhase::PlanFeedback
+                | WorkflowPhase::TaskReview
+                | WorkflowPhase::AcceptanceReview,
+            ) => WorkflowCommand::Feedback(input),
+            _ => WorkflowCommand::SubmitAnswer(input),
+        };
+        self.send_workflow_command(command);
+    }
 
-        if has_plan {
-            self.add_system_message(
-                "세션을 초기화하고 코드 구현을 시작합니다...",
-            );
-        } else {
-            self.add_system_message(
-                "세션을 초기화하고 개발 계획을 작성합니다...",
-            );
+    /// 한 줄짜리 `edit <ID>` 입력이면 해당 작업의 제목과 설명을 입력창에 채우고 외부 에디터를 연다.
+    /// 에디터에서 돌아오면 사용자가 Enter로 수정된 `edit` 명령을 제출한다.
+    fn open_task_edit_template(&mut self, input: &str) -> bool {
+        let Some(task_id) = input.strip_prefix("edit ").map(str::trim) else {
+            return false;
+        };
+        if task_id.contains(char::is_whitespace) {
+            return false;
         }
 
-        std::thread::spawn(move || {
-            let session_id = session_naming::generate_session_id();
-            let date_dir = session_naming::today_date_string();
-            let new_journal_dir = workspace
-                .join(".bear")
-                .join(&date_dir)
-                .join(&session_id);
-
-            if let Err(err) = std::fs::create_dir_all(&new_journal_dir) {
-                let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                    client,
-                    outcome: Err(format!("세션 디렉토리 생성 실패: {}", err)),
-                }));
-                return;
+        let template = self
+            .engine
+            .as_ref()
+            .unwrap()
+            .reviewed_tasks()
+            .iter()
+            .find(|task| task.task_id == task_id)
+            .map(task_review::build_edit_template);
+
+        match template {
+            Some(template) => {
+                self.cursor_position = template.chars().count();
+                self.input_buffer = template;
+                self.pending_external_editor = true;
+                true
             }
+            None => false,
+        }
+    }
 
-            let _ = sender.send(AgentStreamMessage::SessionName {
-                name: session_id,
-                date_dir,
-            });
-
-            // spec.md를 이전 세션에서 새 세션 디렉토리로 복사
-            let source_spec = resumed_dir.join("spec.md");
-            let dest_spec = new_journal_dir.join("spec.md");
-            if let Err(err) = std::fs::copy(&source_spec, &dest_spec) {
-                let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                    client,
-                    outcome: Err(format!("스펙 파일 복사 실패: {}", err)),
-                }));
-                return;
+    fn approve(&mut self, phase: WorkflowPhase) {
+        match phase {
+            WorkflowPhase::TaskReview => {
+                self.add_user_message("작업 목록 확정");
+                self.clear_input();
+            }
+            WorkflowPhase::AcceptanceReview => {
+                self.add_user_message("최종 코드 승인");
+                self.clear_input();
             }
+            _ => {}
+        }
+        self.send_workflow_command(WorkflowCommand::Approve);
+    }
 
-            // user-request.md 생성
-            let user_request_content = format!(
-                "{} 세션으로부터 재시작 되었음.",
-                resumed_dir.display()
-            );
-            let user_request_path = new_journal_dir.join("user-request.md");
-            let _ = std::fs::write(&user_request_path, &user_request_content);
-
-            if has_plan {
-                // plan.md를 이전 세션에서 새 세션 디렉토리로 복사
-                let source_plan = resumed_dir.join("plan.md");
-                let dest_plan = new_journal_dir.join("plan.md");
-                if let Err(err) = std::fs::copy(&source_plan, &dest_plan) {
-                    let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                        client,
-                        outcome: Err(format!("플랜 파일 복사 실패: {}", err)),
-                    }));
-                    return;
-                }
+    fn send_workflow_command(&mut self, command: WorkflowCommand) {
+        let result = self.engine.as_mut().unwrap().handle_command(command);
+        if let Err(err) = result {
+            self.add_system_message(&err.to_string());
+        }
+        // 명령 처리 중 발생한 메시지를 다음 렌더링 전에 반영한다.
+        self.tick();
+    }
 
-                // 태스크 추출 시작
-                client.set_system_prompt(
-                    Some(coding::task_extraction_system_prompt().to_string()),
-                );
-
-                let request = ClaudeCodeRequest {
-                    user_prompt: coding::build_task_extraction_prompt(&dest_plan),
-                    output_schema: coding::task_extraction_schema(),
-                };
-
-                let stream_sender = sender.clone();
-                let outcome = client
-                    .query_streaming::<TaskExtractionResponse, _>(&request, |line| {
-                        let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
-                    })
-                    .map(AgentOutcome::TaskExtraction)
-                    .map_err(|err| err.to_string());
-
-                let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                    client,
-                    outcome,
-                }));
-            } else {
-                // 플랜 작성 시작
-                client.set_system_prompt(Some(planning::system_prompt().to_string()));
-
-                let request = ClaudeCodeRequest {
-                    user_prompt: planning::build_initial_plan_prompt(
-                        &user_request_path,
-                        &dest_spec,
-                    ),
-                    output_schema: planning::plan_writing_schema(),
-                };
-
-                let stream_sender = sender.clone();
-                let outcome = client
-                    .query_streaming::<PlanWritingResponse, _>(&request, |line| {
-                        let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
-                    })
-                    .map(AgentOutcome::Planning)
-                    .map_err(|err| err.to_string());
-
-                let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                    client,
-                    outcome,
-                }));
-            }
-        });
-    }
-
-    fn submit_requirements(&mut self) {
-        let requirements = self.input_buffer.trim().to_string();
-        if requirements.is_empty() {
-            return;
-        }
-
-        self.add_user_message(&requirements);
-        self.confirmed_requirements = Some(requirements);
-        self.clear_input();
-
-        if let Err(error_message) = self.ensure_claude_client() {
-            self.add_system_message(&format!("클라이언트 생성 실패: {}", error_message));
-            self.input_mode = InputMode::Done;
-            return;
-        }
-
-        self.add_system_message("요구사항을 분석 중입니다. 잠시만 기다려 주세요.");
-        self.start_clarification_query();
-    }
-
-    fn submit_clarification_answer(&mut self) {
-        let answer = self.input_buffer.trim().to_string();
-        if answer.is_empty() {
-            return;
-        }
-
-        self.add_user_message(&answer);
-        self.clear_input();
-
-        let questions = std::mem::take(&mut self.current_round_questions);
-        self.qa_log.push(QaRound { questions, answer });
-
-        self.add_system_message("답변을 분석 중입니다. 잠시만 기다려 주세요.");
-        self.start_clarification_query();
-    }
-
-    fn ensure_claude_client(&mut self) -> Result<(), String> {
-        if self.claude_client.is_some() {
-            return Ok(());
-        }
-
-        let workspace = self.confirmed_workspace.clone().unwrap();
-        let client = ClaudeCodeClient::new(
-            self.config.api_key().to_string(),
-            workspace,
-            Some(clarification::system_prompt().to_string()),
-        )
-            .map_err(|err| err.to_string())?;
-
-        self.claude_client = Some(client);
-        Ok(())
-    }
-
-    fn start_clarification_query(&mut self) {
-        let mut client = self.claude_client.take().expect("client must be available");
-        let original_request = self.confirmed_requirements.clone().unwrap();
-        let qa_log = self.qa_log.clone();
-        let needs_session_name = self.session_name.is_none();
-
-        let (sender, receiver) = mpsc::channel();
-        self.agent_result_receiver = Some(receiver);
-        self.input_mode = InputMode::AgentThinking;
-        self.thinking_started_at = Instant::now();
-
-        std::thread::spawn(move || {
-            if needs_session_name {
-                let name = session_naming::generate_session_id();
-                let date_dir = session_naming::today_date_string();
-                let _ = sender.send(AgentStreamMessage::SessionName { name, date_dir });
-            }
-
-            let request = ClaudeCodeRequest {
-                user_prompt: clarification::build_user_prompt(&original_request, &qa_log),
-                output_schema: clarification::clarification_schema(),
-            };
-
-            let stream_sender = sender.clone();
-            let outcome = client
-                .query_streaming::<ClarificationQuestions, _>(&request, |line| {
-                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
-                })
-                .map(AgentOutcome::Clarification)
-                .map_err(|err| err.to_string());
-
-            let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult { client, outcome }));
-        });
-    }
-
-    fn handle_clarification_response(&mut self, response: ClarificationQuestions) {
-        if response.questions.is_empty() {
-            self.add_system_message("요구사항 분석이 완료되었습니다. 스펙 문서를 작성합니다.");
-            self.start_spec_writing_query(true);
-            return;
-        }
-
-        let mut message = String::from("스펙 작성을 위해 다음 질문에 답변해 주세요.\n");
-        for (i, question) in response.questions.iter().enumerate() {
-            message.push_str(&format!("\n{}. {}", i + 1, question));
-        }
-
-        self.current_round_questions = response.questions;
-        self.add_system_message(&message);
-        self.input_mode = InputMode::ClarificationAnswer;
-    }
-
-    fn handle_agent_error(&mut self, error_message: String) {
-        self.add_system_message(&format!("에이전트 오류: {}", error_message));
-        self.fatal_error = Some(error_message);
-        self.should_quit = true;
-    }
-
-    fn start_spec_writing_query(&mut self, is_initial: bool) {
-        let mut client = self.claude_client.take().expect("client must be available");
-
-        let qa_log = self.qa_log.clone();
-        let user_request_path = self.journal_dir().join("user-request.md");
-        let user_feedback = if is_initial {
-            None
-        } else {
-            self.messages
-                .iter()
-                .rev()
-                .find(|m| matches!(m.role, MessageRole::User))
-                .map(|m| m.content.clone())
-        };
-        let send_full_revision_instructions = if is_initial {
-            false
-        } else {
-            let should_send = !self.spec_revision_instructions_sent;
-            self.spec_revision_instructions_sent = true;
-            should_send
-        };
-
-        let (sender, receiver) = mpsc::channel();
-        self.agent_result_receiver = Some(receiver);
-        self.input_mode = InputMode::AgentThinking;
-        self.thinking_started_at = Instant::now();
-
-        std::thread::spawn(move || {
-            let user_prompt = if is_initial {
-                spec_writing::build_initial_spec_prompt(&user_request_path, &qa_log)
-            } else {
-                let feedback = user_feedback.unwrap_or_default();
-                if send_full_revision_instructions {
-                    spec_writing::build_revision_prompt(&feedback)
-                } else {
-                    spec_writing::build_followup_revision_prompt(&feedback)
-                }
-            };
-
-            let request = ClaudeCodeRequest {
-                user_prompt,
-                output_schema: spec_writing::spec_writing_schema(),
-            };
-
-            let stream_sender = sender.clone();
-            let outcome = client
-                .query_streaming::<SpecWritingResponse, _>(&request, |line| {
-                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
-                })
-                .map(AgentOutcome::SpecWriting)
-                .map_err(|err| err.to_string());
-
-            let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                client,
-                outcome,
-            }));
-        });
-    }
-
-    fn handle_spec_response(&mut self, response: SpecWritingResponse) {
-        match response.response_type {
-            SpecResponseType::SpecDraft => {
-                let draft = response.spec_draft.unwrap_or_default();
-
-                self.add_system_message(&format!(
-                    "스펙 드래프트가 작성되었습니다:\n\n{}\n\n피드백을 입력하거나, Ctrl+A를 눌러 승인하세요.",
-                    draft
-                ));
-                self.last_spec_draft = Some(draft);
-                self.input_mode = InputMode::SpecFeedback;
-            }
-            SpecResponseType::ClarifyingQuestions => {
-                let questions = response.clarifying_questions.unwrap_or_default();
-
-                let mut message = String::from("스펙 작성을 위해 추가 정보가 필요합니다.\n");
-                for (i, question) in questions.iter().enumerate() {
-                    message.push_str(&format!("\n{}. {}", i + 1, question));
-                }
-
-                self.spec_clarification_questions = questions;
-                self.add_system_message(&message);
-                self.input_mode = InputMode::SpecClarificationAnswer;
-            }
-            SpecResponseType::Approved => {
-                self.approve_spec();
-            }
-        }
-    }
-
-    fn submit_spec_clarification_answer(&mut self) {
-        let answer = self.input_buffer.trim().to_string();
-        if answer.is_empty() {
-            return;
-        }
-
-        self.add_user_message(&answer);
-        self.clear_input();
-
-        self.add_system_message("답변을 반영하여 스펙을 작성합니다.");
-        self.start_spec_writing_query(false);
-    }
-
-    fn submit_spec_feedback(&mut self) {
-        let feedback = self.input_buffer.trim().to_string();
-        if feedback.is_empty() {
-            return;
-        }
-
-        self.add_user_message(&feedback);
-        self.clear_input();
-
-        self.add_system_message("피드백을 반영하여 스펙을 수정합니다.");
-        self.start_spec_writing_query(false);
-    }
-
-    fn approve_spec(&mut self) {
-        let spec = match &self.last_spec_draft {
-            Some(spec) => spec.clone(),
-            None => {
-                self.add_system_message("승인할 스펙이 없습니다.");
-                return;
-            }
-        };
-
-        self.approved_spec = Some(spec.clone());
-
-        let journal_dir = self.journal_dir();
-        if let Err(err) = spec_writing::save_approved_spec(&journal_dir, &spec) {
-            self.add_system_message(&format!("스펙 파일 저장 실패: {}", err));
-        }
-
-        if let Some(ws) = &self.confirmed_workspace {
-            let spec_path = journal_dir.join("spec.md");
-            if let Err(err) = coding::commit_file_in_workspace(
-                ws,
-                &spec_path,
-                "Add approved specification",
-            ) {
-                self.add_system_message(&format!("스펙 파일 커밋 실패: {}", err));
-            }
-        }
-
-        self.add_system_message("스펙이 승인되었습니다. 개발 계획을 작성합니다.");
-        self.start_plan_writing_query(true);
-    }
-
-    fn start_plan_writing_query(&mut self, is_initial: bool) {
-        let mut client = self.claude_client.take().expect("client must be available");
-
-        if is_initial {
-            client.reset_session();
-            client.set_system_prompt(Some(planning::system_prompt().to_string()));
-        }
-
-        let journal_dir = self.journal_dir();
-        let user_request_path = journal_dir.join("user-request.md");
-        let spec_path = journal_dir.join("spec.md");
-        let user_feedback = if is_initial {
-            None
-        } else {
-            self.messages
-                .iter()
-                .rev()
-                .find(|m| matches!(m.role, MessageRole::User))
-                .map(|m| m.content.clone())
-        };
-
-        let (sender, receiver) = mpsc::channel();
-        self.agent_result_receiver = Some(receiver);
-        self.input_mode = InputMode::AgentThinking;
-        self.thinking_started_at = Instant::now();
-
-        std::thread::spawn(move || {
-            let user_prompt = if is_initial {
-                planning::build_initial_plan_prompt(&user_request_path, &spec_path)
-            } else {
-                let feedback = user_feedback.unwrap_or_default();
-                planning::build_plan_revision_prompt(&feedback)
-            };
-
-            let request = ClaudeCodeRequest {
-                user_prompt,
-                output_schema: planning::plan_writing_schema(),
-            };
-
-            let stream_sender = sender.clone();
-            let outcome = client
-                .query_streaming::<PlanWritingResponse, _>(&request, |line| {
-                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
-                })
-                .map(AgentOutcome::Planning)
-                .map_err(|err| err.to_string());
-
-            let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                client,
-                outcome,
-            }));
-        });
-    }
-
-    fn handle_plan_response(&mut self, response: PlanWritingResponse) {
-        match response.response_type {
-            PlanResponseType::PlanDraft => {
-                let draft = response.plan_draft.unwrap_or_default();
-
-                self.add_system_message(&format!(
-                    "개발 계획 드래프트가 작성되었습니다:\n\n{}\n\n피드백을 입력하거나, Ctrl+A를 눌러 승인하세요.",
-                    draft
-                ));
-                self.last_plan_draft = Some(draft);
-                self.input_mode = InputMode::PlanFeedback;
-            }
-            PlanResponseType::ClarifyingQuestions => {
-                let questions = response.clarifying_questions.unwrap_or_default();
-
-                let mut message = String::from("개발 계획 작성을 위해 추가 정보가 필요합니다.\n");
-                for (i, question) in questions.iter().enumerate() {
-                    message.push_str(&format!("\n{}. {}", i + 1, question));
-                }
-
-                self.plan_clarification_questions = questions;
-                self.add_system_message(&message);
-                self.input_mode = InputMode::PlanClarificationAnswer;
-            }
-            PlanResponseType::Approved => {
-                self.approve_plan();
-            }
-        }
-    }
-
-    fn submit_plan_clarification_answer(&mut self) {
-        let answer = self.input_buffer.trim().to_string();
-        if answer.is_empty() {
-            return;
-        }
-
-        self.add_user_message(&answer);
-        self.clear_input();
-
-        self.add_system_message("답변을 반영하여 개발 계획을 작성합니다.");
-        self.start_plan_writing_query(false);
-    }
-
-    fn submit_plan_feedback(&mut self) {
-        let feedback = self.input_buffer.trim().to_string();
-        if feedback.is_empty() {
-            return;
-        }
-
-        self.add_user_message(&feedback);
-        self.clear_input();
-
-        self.add_system_message("피드백을 반영하여 개발 계획을 수정합니다.");
-        self.start_plan_writing_query(false);
-    }
-
-    fn approve_plan(&mut self) {
-        let plan = match &self.last_plan_draft {
-            Some(plan) => plan.clone(),
-            None => {
-                self.add_system_message("승인할 개발 계획이 없습니다.");
-                return;
-            }
-        };
-
-        let journal_dir = self.journal_dir();
-        if let Err(err) = planning::save_approved_plan(&journal_dir, &plan) {
-            self.add_system_message(&format!("플랜 파일 저장 실패: {}", err));
-        }
-
-        if let Some(ws) = &self.confirmed_workspace {
-            let plan_path = journal_dir.join("plan.md");
-            if let Err(err) = coding::commit_file_in_workspace(
-                ws,
-                &plan_path,
-                "Add approved development plan",
-            ) {
-                self.add_system_message(&format!("플랜 파일 커밋 실패: {}", err));
-            }
-        }
-
-        self.add_system_message("개발 계획이 승인되었습니다. 작업 목록을 추출합니다.");
-        self.start_task_extraction();
-    }
-
-    fn start_task_extraction(&mut self) {
-        let mut client = self.claude_client.take().expect("client must be available");
-        client.reset_session();
-        client.set_system_prompt(Some(coding::task_extraction_system_prompt().to_string()));
-
-        let plan_path = self.journal_dir().join("plan.md");
-
-        let (sender, receiver) = mpsc::channel();
-        self.agent_result_receiver = Some(receiver);
-        self.input_mode = InputMode::AgentThinking;
-        self.thinking_started_at = Instant::now();
-
-        std::thread::spawn(move || {
-            let request = ClaudeCodeRequest {
-                user_prompt: coding::build_task_extraction_prompt(&plan_path),
-                output_schema: coding::task_extraction_schema(),
-            };
-
-            let stream_sender = sender.clone();
-            let outcome = client
-                .query_streaming::<TaskExtractionResponse, _>(&request, |line| {
-                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
-                })
-                .map(AgentOutcome::TaskExtraction)
-                .map_err(|err| err.to_string());
-
-            let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                client,
-                outcome,
-            }));
-        });
-    }
-
-    fn handle_task_extraction_response(&mut self, response: TaskExtractionResponse) {
-        if response.tasks.is_empty() {
-            self.add_system_message("추출된 작업이 없습니다.");
-            self.input_mode = InputMode::Done;
-            return;
-        }
-
-        let mut schedule_message = format!(
-            "{}개 작업이 추출되었습니다:\n",
-            response.tasks.len()
-        );
-        for (i, task) in response.tasks.iter().enumerate() {
-            schedule_message.push_str(&format!(
-                "\n{}. [{}] {}",
-                i + 1,
-                task.task_id,
-                task.title,
-            ));
-            if !task.dependencies.is_empty() {
-                schedule_message.push_str(&format!(
-                    " (의존: {})",
-                    task.dependencies.join(", "),
-                ));
-            }
-        }
-        self.add_system_message(&schedule_message);
-
-        let integration_branch = match &self.integration_branch {
-            Some(branch) => branch.clone(),
-            None => {
-                let workspace = self.confirmed_workspace.clone().unwrap();
-                let session_name = self
-                    .session_name
-                    .clone()
-                    .unwrap_or_else(|| "unnamed".to_string());
-                match coding::create_integration_branch(&workspace, &session_name) {
-                    Ok(branch) => {
-                        self.integration_branch = Some(branch.clone());
-                        branch
-                    }
-                    Err(err) => {
-                        self.add_system_message(
-                            &format!("Failed to create git branch: {}", err),
-                        );
-                        self.input_mode = InputMode::Done;
-                        return;
-                    }
-                }
-            }
-        };
-
-        self.add_system_message(&format!(
-            "코딩 워크스페이스 준비 완료.\n통합 브랜치: {}",
-            integration_branch,
-        ));
-
-        self.coding_state = Some(CodingPhaseState {
-            tasks: response.tasks,
-            current_task_index: 0,
-            task_reports: Vec::new(),
-            integration_branch,
-            current_task_worktree: None,
-            build_test_commands: None,
-        });
-
-        self.start_next_coding_task();
-    }
-
-    /// 다음 코딩 태스크에 필요한 데이터를 추출한다.
-    /// 남은 태스크가 없으면 None을 반환한다.
-    fn extract_next_coding_task_data(
-        &self,
-    ) -> Option<(CodingTask, usize, usize, Vec<PathBuf>)> {
-        let coding_state = self.coding_state.as_ref()?;
-        if coding_state.current_task_index >= coding_state.tasks.len() {
-            return None;
-        }
-
-        let task = coding_state.tasks[coding_state.current_task_index].clone();
-        let total = coding_state.tasks.len();
-        let index = coding_state.current_task_index;
-        let upstream_report_paths =
-            coding::collect_upstream_report_paths(&task, &coding_state.task_reports);
-
-        Some((task, total, index, upstream_report_paths))
-    }
-
-    fn start_next_coding_task(&mut self) {
-        let extracted = self.extract_next_coding_task_data();
-        let (task, total, index, upstream_report_paths) = match extracted {
-            Some(data) => data,
-            None => {
-                self.finish_coding_phase();
-                return;
-            }
-        };
-
-        self.add_system_message(&format!(
-            "작업 {}/{} 시작: [{}] {}",
-            index + 1,
-            total,
-            task.task_id,
-            task.title,
-        ));
-
-        let workspace = self.confirmed_workspace.clone().unwrap();
-        let integration_branch = self
-            .coding_state
-            .as_ref()
-            .unwrap()
-            .integration_branch
-            .clone();
-
-        let task_branch =
-            match coding::create_task_branch(&workspace, &integration_branch, &task.task_id) {
-                Ok(branch) => branch,
-                Err(err) => {
-                    self.add_system_message(&format!("태스크 브랜치 생성 실패: {}", err));
-                    self.save_and_advance_task(
-                        task.task_id.clone(),
-                        CodingTaskStatus::ImplementationBlocked,
-                        format!("태스크 브랜치 생성 실패: {}", err),
-                    );
-                    return;
-                }
-            };
-
-        let worktree_path = match coding::create_worktree(&workspace, &task_branch) {
-            Ok(path) => path,
-            Err(err) => {
-                self.add_system_message(&format!("워크트리 생성 실패: {}", err));
-                let _ = coding::delete_branch(&workspace, &task_branch);
-                self.save_and_advance_task(
-                    task.task_id.clone(),
-                    CodingTaskStatus::ImplementationBlocked,
-                    format!("워크트리 생성 실패: {}", err),
-                );
-                return;
-            }
-        };
-
-        self.add_system_message(&format!(
-            "태스크 워크트리 생성: {}\n브랜치: {}",
-            worktree_path.display(),
-            task_branch,
-        ));
-
-        let coding_state = self.coding_state.as_mut().unwrap();
-        coding_state.current_task_worktree = Some(TaskWorktreeInfo {
-            worktree_path: worktree_path.clone(),
-            task_branch,
-        });
-
-        let worktree_journal = self.journal_dir();
-        let workspace_journal = self.workspace_journal_dir();
-        for err in coding::copy_artifacts_to_worktree(
-            &workspace_journal,
-            &worktree_journal,
-            &["spec.md", "plan.md"],
-        ) {
-            self.add_system_message(&err);
-        }
-
-        let journal_dir = self.journal_dir();
-        let spec_path = journal_dir.join("spec.md");
-        let plan_path = journal_dir.join("plan.md");
-        let api_key = self.config.api_key().to_string();
-
-        let mut client = match ClaudeCodeClient::new(
-            api_key,
-            worktree_path,
-            Some(coding::coding_agent_system_prompt().to_string()),
-        ) {
-            Ok(c) => c,
-            Err(err) => {
-                self.add_system_message(&format!(
-                    "코딩 에이전트 클라이언트 생성 실패: {}",
-                    err,
-                ));
-                self.cleanup_current_task_worktree();
-                self.save_and_advance_task(
-                    task.task_id.clone(),
-                    CodingTaskStatus::ImplementationBlocked,
-                    format!("코딩 에이전트 클라이언트 생성 실패: {}", err),
-                );
-                return;
-            }
-        };
-
-        let (sender, receiver) = mpsc::channel();
-        self.agent_result_receiver = Some(receiver);
-        self.input_mode = InputMode::Coding;
-        self.thinking_started_at = Instant::now();
-
-        std::thread::spawn(move || {
-            let user_prompt = coding::build_coding_task_prompt(
-                &task,
-                &spec_path,
-                &plan_path,
-                &upstream_report_paths,
-                &integration_branch,
-            );
-
-            let request = ClaudeCodeRequest {
-                user_prompt,
-                output_schema: coding::coding_task_result_schema(),
-            };
-
-            let stream_sender = sender.clone();
-            let outcome = client
-                .query_streaming::<CodingTaskResult, _>(&request, |line| {
-                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
-                })
-                .map(AgentOutcome::CodingTaskCompleted)
-                .map_err(|err| err.to_string());
-
-            let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                client,
-                outcome,
-            }));
-        });
-    }
-
-    fn handle_coding_task_result(&mut self, result: CodingTaskResult) {
-        let task_id = {
-            let coding_state = self.coding_state.as_ref().unwrap();
-            coding_state.tasks[coding_state.current_task_index]
-                .task_id
-                .clone()
-        };
-
-        let status_label = match &result.status {
-            CodingTaskStatus::ImplementationSuccess => "SUCCESS",
-            CodingTaskStatus::ImplementationBlocked => "BLOCKED",
-        };
-        self.add_system_message(&format!(
-            "작업 [{}] 완료: {}",
-            task_id, status_label,
-        ));
-
-        if result.status == CodingTaskStatus::ImplementationBlocked {
-            self.review_state = None;
-            self.cleanup_current_task_worktree();
-            self.save_and_advance_task(task_id, result.status, result.report);
-            return;
-        }
-
-        let coding_client = self.claude_client.take();
-
-        match self.review_state.as_mut() {
-            None => {
-                self.review_state = Some(ReviewState {
-                    task_id: task_id.clone(),
-                    report: result.report.clone(),
-                    iteration_count: 0,
-                    reviewer_client: None,
-                    coding_client,
-                });
-            }
-            Some(rs) => {
-                rs.report = result.report.clone();
-                rs.coding_client = coding_client;
-            }
-        }
-
-        self.start_review();
-    }
-
-    fn start_review(&mut self) {
-        let review_state = self.review_state.as_ref().unwrap();
-        let is_followup = review_state.iteration_count > 0;
-        let task_id = review_state.task_id.clone();
-        let report = review_state.report.clone();
-
-        let coding_state = self.coding_state.as_ref().unwrap();
-        let worktree_info = coding_state.current_task_worktree.as_ref().unwrap();
-        let worktree_path = worktree_info.worktree_path.clone();
-
-        let git_commit_revision = match coding::get_latest_commit_revision(&worktree_path) {
-            Ok(rev) => rev,
-            Err(err) => {
-                self.add_system_message(&format!(
-                    "[{}] git 커밋 해시 조회 실패: {}. 리뷰 건너뜀.",
-                    task_id, err,
-                ));
-                self.finalize_review_and_proceed();
-                return;
-            }
-        };
-
-        let journal_dir = self.journal_dir();
-
-        let report_path = match coding::save_task_report(
-            &journal_dir, &task_id, &report,
-        ) {
-            Ok(path) => path,
-            Err(err) => {
-                self.add_system_message(&format!(
-                    "[{}] 리포트 저장 실패: {}. 리뷰 건너뜀.",
-                    task_id, err,
-                ));
-                self.finalize_review_and_proceed();
-                return;
-            }
-        };
-
-        let spec_path = journal_dir.join("spec.md");
-        let plan_path = journal_dir.join("plan.md");
-
-        let user_prompt = if is_followup {
-            coding::build_followup_review_prompt(
-                &spec_path, &plan_path, &report_path, &git_commit_revision,
-            )
-        } else {
-            coding::build_initial_review_prompt(
-                &spec_path, &plan_path, &report_path, &git_commit_revision,
-            )
-        };
-
-        let api_key = self.config.api_key().to_string();
-        let mut reviewer_client = match self.review_state.as_mut().unwrap().reviewer_client.take() {
-            Some(client) => client,
-            None => {
-                match ClaudeCodeClient::new(
-                    api_key,
-                    worktree_path.clone(),
-                    Some(coding::review_agent_system_prompt().to_string()),
-                ) {
-                    Ok(c) => c,
-                    Err(err) => {
-                        self.add_system_message(&format!(
-                            "[{}] 리뷰 에이전트 클라이언트 생성 실패: {}. 리뷰 건너뜀.",
-                            task_id, err,
-                        ));
-                        self.finalize_review_and_proceed();
-                        return;
-                    }
-                }
-            }
-        };
-        reviewer_client.set_working_directory(worktree_path);
-
-        let iteration_label = self.review_state.as_ref().unwrap().iteration_count + 1;
-        self.add_system_message(&format!(
-            "[{}] 코드 리뷰 시작 (iteration {})...",
-            task_id, iteration_label,
-        ));
-
-        let (sender, receiver) = mpsc::channel();
-        self.agent_result_receiver = Some(receiver);
-        self.input_mode = InputMode::Coding;
-        self.thinking_started_at = Instant::now();
-
-        std::thread::spawn(move || {
-            let request = ClaudeCodeRequest {
-                user_prompt,
-                output_schema: coding::review_result_schema(),
-            };
-
-            let stream_sender = sender.clone();
-            let outcome = reviewer_client
-                .query_streaming::<ReviewResult, _>(&request, |line| {
-                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
-                })
-                .map(AgentOutcome::ReviewCompleted)
-                .map_err(|err| err.to_string());
-
-            let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                client: reviewer_client,
-                outcome,
-            }));
-        });
-    }
-
-    fn handle_review_result(&mut self, result: ReviewResult) {
-        let reviewer_client = self.claude_client.take();
-        let review_state = self.review_state.as_mut().unwrap();
-        review_state.reviewer_client = reviewer_client;
-        review_state.iteration_count += 1;
-
-        let task_id = review_state.task_id.clone();
-
-        match result.review_result {
-            ReviewStatus::Approved => {
-                self.add_system_message(&format!("[{}] 코드 리뷰 승인.", task_id));
-                self.finalize_review_and_proceed();
-            }
-            ReviewStatus::RequestChanges => {
-                let iteration_count = self.review_state.as_ref().unwrap().iteration_count;
-
-                if iteration_count >= MAX_REVIEW_ITERATIONS {
-                    self.add_system_message(&format!(
-                        "[{}] 리뷰 최대 반복 횟수({}) 도달. 자동 승인 처리.",
-                        task_id, MAX_REVIEW_ITERATIONS,
-                    ));
-                    self.finalize_review_and_proceed();
-                    return;
-                }
-
-                self.add_system_message(&format!(
-                    "[{}] 리뷰어 변경 요청 (iteration {}/{}): {}",
-                    task_id, iteration_count, MAX_REVIEW_ITERATIONS,
-                    result.review_comment,
-                ));
-
-                self.start_coding_revision(result.review_comment);
-            }
-        }
-    }
-
-    fn finalize_review_and_proceed(&mut self) {
-        let review_state = self.review_state.take().unwrap();
-        let task_id = review_state.task_id;
-        let report = review_state.report;
-
-        self.claude_client = review_state.coding_client;
-
-        self.rebase_and_merge_task(task_id, report);
-    }
-
-    fn start_coding_revision(&mut self, review_comment: String) {
-        let coding_state = self.coding_state.as_ref().unwrap();
-        let task = coding_state.tasks[coding_state.current_task_index].clone();
-        let worktree_info = coding_state.current_task_worktree.as_ref().unwrap();
-        let worktree_path = worktree_info.worktree_path.clone();
-        let task_id = task.task_id.clone();
-
-        let journal_dir = self.journal_dir();
-        let spec_path = journal_dir.join("spec.md");
-        let plan_path = journal_dir.join("plan.md");
-
-        let integration_branch = coding_state.integration_branch.clone();
-        let user_prompt = coding::build_coding_revision_prompt(
-            &task, &spec_path, &plan_path, &review_comment, &integration_branch,
-        );
-
-        let mut client = match self.review_state.as_mut().unwrap().coding_client.take() {
-            Some(c) => c,
-            None => {
-                self.add_system_message(&format!(
-                    "[{}] 코딩 에이전트 세션을 찾을 수 없습니다. 리뷰 자동 승인 처리.",
-                    task_id,
-                ));
-                self.finalize_review_and_proceed();
-                return;
-            }
-        };
-        client.set_working_directory(worktree_path);
-
-        self.add_system_message(&format!(
-            "[{}] 리뷰 피드백 반영을 위한 코딩 에이전트 재시작...",
-            task_id,
-        ));
-
-        let (sender, receiver) = mpsc::channel();
-        self.agent_result_receiver = Some(receiver);
-        self.input_mode = InputMode::Coding;
-        self.thinking_started_at = Instant::now();
-
-        std::thread::spawn(move || {
-            let request = ClaudeCodeRequest {
-                user_prompt,
-                output_schema: coding::coding_task_result_schema(),
-            };
-
-            let stream_sender = sender.clone();
-            let outcome = client
-                .query_streaming::<CodingTaskResult, _>(&request, |line| {
-                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
-                })
-                .map(AgentOutcome::CodingTaskCompleted)
-                .map_err(|err| err.to_string());
-
-            let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                client,
-                outcome,
-            }));
-        });
-    }
-
-    fn rebase_and_merge_task(
-        &mut self,
-        task_id: String,
-        report: String,
-    ) {
-        let coding_state = self.coding_state.as_ref().unwrap();
-        let worktree_info = coding_state.current_task_worktree.as_ref().unwrap();
-        let worktree_path = worktree_info.worktree_path.clone();
-        let integration_branch = coding_state.integration_branch.clone();
-
-        self.add_system_message(&format!(
-            "[{}] 통합 브랜치로 리베이스 시작...",
-            task_id,
-        ));
-
-        match coding::rebase_onto_integration(&worktree_path, &integration_branch) {
-            Ok(RebaseOutcome::Success) => {
-                self.add_system_message(&format!("[{}] 리베이스 성공.", task_id));
-                self.verify_build_and_test(task_id, report);
-            }
-            Ok(RebaseOutcome::Conflict { conflicted_files }) => {
-                self.add_system_message(&format!(
-                    "[{}] 리베이스 충돌 발생 ({}개 파일). 충돌 해결 에이전트 시작...",
-                    task_id,
-                    conflicted_files.len(),
-                ));
-                self.start_conflict_resolution(
-                    task_id,
-                    conflicted_files,
-                    report,
-                );
-            }
-            Err(err) => {
-                self.add_system_message(&format!("[{}] 리베이스 실패: {}", task_id, err));
-                self.cleanup_current_task_worktree();
-                self.save_and_advance_task(
-                    task_id,
-                    CodingTaskStatus::ImplementationBlocked,
-                    format!("{}\n\n---\n리베이스 실패: {}", report, err),
-                );
-            }
-        }
-    }
-
-    fn handle_coding_task_error(&mut self, error_message: String) {
-        let task_id = {
-            let coding_state = self.coding_state.as_ref().unwrap();
-            coding_state.tasks[coding_state.current_task_index]
-                .task_id
-                .clone()
-        };
-
-        self.add_system_message(&format!(
-            "Task [{}] error: {}",
-            task_id, error_message,
-        ));
-
-        self.review_state = None;
-        self.cleanup_current_task_worktree();
-
-        let report = format!(
-            "IMPLEMENTATION_BLOCKED\n---\nAgent error: {}",
-            error_message,
-        );
-        let message = format!("Task [{}] error: {}", task_id, error_message);
-        self.save_and_advance_task(
-            task_id,
-            CodingTaskStatus::ImplementationBlocked,
-            report,
-        );
-        self.fatal_error = Some(message);
-        self.should_quit = true;
-    }
-
-    fn cleanup_current_task_worktree(&mut self) {
-        let workspace = self.confirmed_workspace.clone().unwrap();
-        let coding_state = self.coding_state.as_mut().unwrap();
-        if let Some(info) = coding_state.current_task_worktree.take() {
-            if let Err(err) = coding::remove_worktree(&workspace, &info.worktree_path) {
-                self.add_system_message(&format!("워크트리 제거 실패: {}", err));
-            }
-            if let Err(err) = coding::delete_branch(&workspace, &info.task_branch) {
-                self.add_system_message(&format!("태스크 브랜치 삭제 실패: {}", err));
-            }
-        }
-    }
-
-    fn verify_build_and_test(
-        &mut self,
-        task_id: String,
-        report: String,
-    ) {
-        let worktree_path = self
-            .coding_state
-            .as_ref()
-            .unwrap()
-            .current_task_worktree
-            .as_ref()
-            .unwrap()
-            .worktree_path
-            .clone();
-
-        let already_detected = self
-            .coding_state
-            .as_ref()
-            .unwrap()
-            .build_test_commands
-            .is_some();
-
-        if !already_detected {
-            if let Some(commands) = coding::detect_build_commands(&worktree_path) {
-                self.add_system_message(&format!(
-                    "[{}] 빌드 시스템 감지: build='{}', test='{}'",
-                    task_id, commands.build, commands.test,
-                ));
-                self.coding_state.as_mut().unwrap().build_test_commands = Some(commands);
-            } else {
-                self.add_system_message(
-                    "빌드 시스템을 자동 감지할 수 없습니다. 빌드 명령어를 입력해주세요:",
-                );
-                self.ask_build_command(task_id, report);
-                return;
-            }
-        }
-
-        self.start_build_test_execution(task_id, report, false);
-    }
-
-    fn ask_build_command(
-        &mut self,
-        task_id: String,
-        report: String,
-    ) {
-        self.pending_build_test = Some(PendingBuildTest {
-            task_id,
-            report,
-            is_retry: false,
-        });
-        self.build_test_command_phase = BuildTestCommandPhase::BuildCommand;
-        self.input_buffer.clear();
-        self.cursor_position = 0;
-        self.input_mode = InputMode::BuildTestCommandInput;
-    }
-
-    fn submit_build_test_command(&mut self) {
-        let command = self.input_buffer.trim().to_string();
-        if command.is_empty() {
-            return;
-        }
-        self.add_user_message(&command);
-        self.input_buffer.clear();
-        self.cursor_position = 0;
-
-        match self.build_test_command_phase {
-            BuildTestCommandPhase::BuildCommand => {
-                let coding_state = self.coding_state.as_mut().unwrap();
-                coding_state.build_test_commands = Some(BuildTestCommands {
-                    build: command,
-                    test: String::new(),
-                });
-                self.build_test_command_phase = BuildTestCommandPhase::TestCommand;
-                self.add_system_message("테스트 명령어를 입력해주세요 (예: make test):");
-            }
-            BuildTestCommandPhase::TestCommand => {
-                let coding_state = self.coding_state.as_mut().unwrap();
-                if let Some(ref mut commands) = coding_state.build_test_commands {
-                    commands.test = command;
-                }
-
-                let pending = self.pending_build_test.take().unwrap();
-                self.start_build_test_execution(
-                    pending.task_id,
-                    pending.report,
-                    pending.is_retry,
-                );
-            }
-        }
-    }
-
-    fn start_build_test_execution(
-        &mut self,
-        task_id: String,
-        report: String,
-        is_retry: bool,
-    ) {
-        let commands = self
-            .coding_state
-            .as_ref()
-            .unwrap()
-            .build_test_commands
-            .clone()
-            .unwrap();
-        let worktree_path = self
-            .coding_state
-            .as_ref()
-            .unwrap()
-            .current_task_worktree
-            .as_ref()
-            .unwrap()
-            .worktree_path
-            .clone();
-
-        self.add_system_message(&format!(
-            "[{}] 빌드/테스트 검증 시작...",
-            task_id,
-        ));
-
-        self.pending_build_test = Some(PendingBuildTest {
-            task_id,
-            report,
-            is_retry,
-        });
-
-        let client = self.claude_client.take().unwrap();
-        let (sender, receiver) = mpsc::channel();
-        self.agent_result_receiver = Some(receiver);
-        self.input_mode = InputMode::Coding;
-        self.thinking_started_at = Instant::now();
-
-        std::thread::spawn(move || {
-            let outcome = coding::run_build_and_test(&worktree_path, &commands)
-                .map(AgentOutcome::BuildTestCompleted);
-
-            let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                client,
-                outcome,
-            }));
-        });
-    }
-
-    fn handle_build_test_result(&mut self, outcome: BuildTestOutcome) {
-        let pending = self.pending_build_test.take().unwrap();
-
-        match outcome {
-            BuildTestOutcome::Success => {
-                self.add_system_message(&format!(
-                    "[{}] 빌드/테스트 검증 성공.",
-                    pending.task_id,
-                ));
-                self.ff_merge_and_advance(
-                    pending.task_id,
-                    pending.report,
-                );
-            }
-            BuildTestOutcome::BuildFailed { output } => {
-                self.handle_build_test_failure(pending, "빌드", output);
-            }
-            BuildTestOutcome::TestFailed { output } => {
-                self.handle_build_test_failure(pending, "테스트", output);
-            }
-        }
-    }
-
-    fn handle_build_test_failure(
-        &mut self,
-        pending: PendingBuildTest,
-        failure_type: &str,
-        output: String,
-    ) {
-        if pending.is_retry {
-            self.add_system_message(&format!(
-                "[{}] 수리 후 {} 재실패. 태스크 차단 처리.",
-                pending.task_id, failure_type,
-            ));
-            self.cleanup_current_task_worktree();
-            self.save_and_advance_task(
-                pending.task_id,
-                CodingTaskStatus::ImplementationBlocked,
-                format!("{}\n\n---\n빌드/테스트 실패:\n{}", pending.report, output),
-            );
-        } else {
-            self.add_system_message(&format!(
-                "[{}] {} 실패. 수리 에이전트 시작...",
-                pending.task_id, failure_type,
-            ));
-            self.start_build_test_repair(
-                pending.task_id,
-                pending.report,
-                output,
-            );
-        }
-    }
-
-    fn start_build_test_repair(
-        &mut self,
-        task_id: String,
-        report: String,
-        error_output: String,
-    ) {
-        self.pending_build_test = Some(PendingBuildTest {
-            task_id: task_id.clone(),
-            report,
-            is_retry: true,
-        });
-
-        let commands = self
-            .coding_state
-            .as_ref()
-            .unwrap()
-            .build_test_commands
-            .as_ref()
-            .unwrap();
-        let user_prompt = coding::build_build_test_repair_prompt(
-            &task_id,
-            &commands.build,
-            &commands.test,
-            &error_output,
-        );
-
-        let mut client = match self.claude_client.take() {
-            Some(c) => c,
-            None => {
-                self.add_system_message("수리 에이전트를 위한 세션을 찾을 수 없습니다.");
-                let pending = self.pending_build_test.take().unwrap();
-                self.cleanup_current_task_worktree();
-                self.save_and_advance_task(
-                    pending.task_id,
-                    CodingTaskStatus::ImplementationBlocked,
-                    format!(
-                        "{}\n\n---\n빌드/테스트 실패 (수리 불가):\n{}",
-                        pending.report, error_output,
-                    ),
-                );
-                return;
-            }
-        };
-
-        let (sender, receiver) = mpsc::channel();
-        self.agent_result_receiver = Some(receiver);
-        self.input_mode = InputMode::Coding;
-        self.thinking_started_at = Instant::now();
-
-        std::thread::spawn(move || {
-            let request = ClaudeCodeRequest {
-                user_prompt,
-                output_schema: coding::build_test_repair_result_schema(),
-            };
-
-            let stream_sender = sender.clone();
-            let outcome = client
-                .query_streaming::<BuildTestRepairResult, _>(&request, |line| {
-                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
-                })
-                .map(AgentOutcome::BuildTestRepairCompleted)
-                .map_err(|err| err.to_string());
-
-            let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                client,
-                outcome,
-            }));
-        });
-    }
-
-    fn handle_build_test_repair_result(&mut self, result: BuildTestRepairResult) {
-        let pending = self.pending_build_test.take().unwrap();
-
-        match result.status {
-            BuildTestRepairStatus::Fixed => {
-                self.add_system_message(&format!(
-                    "[{}] 수리 에이전트 완료. 빌드/테스트 재검증...",
-                    pending.task_id,
-                ));
-                self.start_build_test_execution(
-                    pending.task_id,
-                    pending.report,
-                    true,
-                );
-            }
-            BuildTestRepairStatus::FixFailed => {
-                self.add_system_message(&format!(
-                    "[{}] 수리 실패: {}",
-                    pending.task_id, result.report,
-                ));
-                self.cleanup_current_task_worktree();
-                self.save_and_advance_task(
-                    pending.task_id,
-                    CodingTaskStatus::ImplementationBlocked,
-                    format!(
-                        "{}\n\n---\n빌드/테스트 수리 실패: {}",
-                        pending.report, result.report,
-                    ),
-                );
-            }
-        }
-    }
-
-    fn ff_merge_and_advance(
-        &mut self,
-        task_id: String,
-        report: String,
-    ) {
-        let coding_state = self.coding_state.as_ref().unwrap();
-        let worktree_info = coding_state.current_task_worktree.as_ref().unwrap();
-        let worktree_path = worktree_info.worktree_path.clone();
-        let task_branch = worktree_info.task_branch.clone();
-
-        let date_dir = self.session_date_dir.clone().unwrap_or_default();
-        let session_name = self.session_name.clone().unwrap_or_default();
-
-        if let Err(err) = coding::save_and_commit_task_report_in_worktree(
-            &worktree_path, &date_dir, &session_name, &task_id, &report,
-        ) {
-            self.add_system_message(&format!(
-                "[{}] 워크트리 리포트 커밋 실패: {}. 리포트 없이 진행.",
-                task_id, err,
-            ));
-        }
-
-        self.add_system_message(&format!(
-            "[{}] 통합 브랜치로 fast-forward 머지 시작...",
-            task_id,
-        ));
-
-        let report_file_path = self.workspace_journal_dir().join(format!("{}.md", task_id));
-
-        let workspace = self.confirmed_workspace.clone().unwrap();
-        match coding::fast_forward_merge_task_branch(
-            &workspace,
-            &task_branch,
-        ) {
-            Ok(()) => {
-                self.add_system_message(&format!("[{}] fast-forward 머지 완료.", task_id));
-                self.cleanup_current_task_worktree();
-                self.advance_task(
-                    task_id,
-                    CodingTaskStatus::ImplementationSuccess,
-                    report,
-                    report_file_path,
-                );
-            }
-            Err(err) => {
-                self.add_system_message(&format!(
-                    "[{}] fast-forward 머지 실패: {}",
-                    task_id, err
-                ));
-                self.cleanup_current_task_worktree();
-                self.save_and_advance_task(
-                    task_id,
-                    CodingTaskStatus::ImplementationBlocked,
-                    format!("{}\n\n---\nfast-forward 머지 실패: {}", report, err),
-                );
-            }
-        }
-    }
-
-    fn start_conflict_resolution(
-        &mut self,
-        task_id: String,
-        conflicted_files: Vec<String>,
-        original_report: String,
-    ) {
-        self.pending_coding_report = Some(original_report);
-
-        let mut client = match self.claude_client.take() {
-            Some(c) => c,
-            None => {
-                self.add_system_message("충돌 해결을 위한 에이전트 세션을 찾을 수 없습니다.");
-                self.pending_coding_report = None;
-                let _ = coding::abort_rebase(
-                    &self
-                        .coding_state
-                        .as_ref()
-                        .unwrap()
-                        .current_task_worktree
-                        .as_ref()
-                        .unwrap()
-                        .worktree_path,
-                );
-                self.cleanup_current_task_worktree();
-                self.save_and_advance_task(
-                    task_id,
-                    CodingTaskStatus::ImplementationBlocked,
-                    "충돌 해결 세션을 찾을 수 없음".to_string(),
-                );
-                return;
-            }
-        };
-
-        let integration_branch = self
-            .coding_state
-            .as_ref()
-            .unwrap()
-            .integration_branch
-            .clone();
-
-        let user_prompt = coding::build_conflict_resolution_prompt(
-            &task_id,
-            &integration_branch,
-            &conflicted_files,
-        );
-
-        let (sender, receiver) = mpsc::channel();
-        self.agent_result_receiver = Some(receiver);
-        self.input_mode = InputMode::Coding;
-        self.thinking_started_at = Instant::now();
-
-        std::thread::spawn(move || {
-            let request = ClaudeCodeRequest {
-                user_prompt,
-                output_schema: coding::conflict_resolution_result_schema(),
-            };
-
-            let stream_sender = sender.clone();
-            let outcome = client
-                .query_streaming::<ConflictResolutionResult, _>(&request, |line| {
-                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
-                })
-                .map(AgentOutcome::ConflictResolutionCompleted)
-                .map_err(|err| err.to_string());
-
-            let _ = sender.send(AgentStreamMessage::Completed(AgentThreadResult {
-                client,
-                outcome,
-            }));
-        });
-    }
-
-    fn handle_conflict_resolution_result(&mut self, result: ConflictResolutionResult) {
-        let task_id = {
-            let coding_state = self.coding_state.as_ref().unwrap();
-            coding_state.tasks[coding_state.current_task_index]
-                .task_id
-                .clone()
-        };
-
-        match result.status {
-            ConflictResolutionStatus::ConflictResolved => {
-                self.add_system_message(&format!("[{}] 충돌 해결 완료.", task_id));
-                let report = self
-                    .pending_coding_report
-                    .take()
-                    .unwrap_or(result.report);
-                self.verify_build_and_test(task_id, report);
-            }
-            ConflictResolutionStatus::ConflictResolutionFailed => {
-                self.add_system_message(&format!(
-                    "[{}] 충돌 해결 실패: {}",
-                    task_id, result.report,
-                ));
-                let worktree_path = self
-                    .coding_state
-                    .as_ref()
-                    .unwrap()
-                    .current_task_worktree
-                    .as_ref()
-                    .unwrap()
-                    .worktree_path
-                    .clone();
-                let _ = coding::abort_rebase(&worktree_path);
-                self.pending_coding_report = None;
-                self.cleanup_current_task_worktree();
-                self.save_and_advance_task(
-                    task_id,
-                    CodingTaskStatus::ImplementationBlocked,
-                    format!("충돌 해결 실패: {}", result.report),
-                );
-            }
-        }
-    }
-
-    fn save_and_advance_task(
-        &mut self,
-        task_id: String,
-        status: CodingTaskStatus,
-        report: String,
-    ) {
-        self.advance_task(task_id, status, report, PathBuf::new());
-    }
-
-    fn advance_task(
-        &mut self,
-        task_id: String,
-        status: CodingTaskStatus,
-        report: String,
-        report_file_path: PathBuf,
-    ) {
-        let coding_state = self.coding_state.as_mut().unwrap();
-        coding_state.task_reports.push(TaskReport {
-            task_id,
-            status,
-            report,
-            report_file_path,
-        });
-        coding_state.current_task_index += 1;
-
-        self.start_next_coding_task();
-    }
-
-    fn finish_coding_phase(&mut self) {
-        let coding_state = self.coding_state.as_ref().unwrap();
-        let integration_branch = coding_state.integration_branch.clone();
-
-        let success_count = coding_state
-            .task_reports
-            .iter()
-            .filter(|r| r.status == CodingTaskStatus::ImplementationSuccess)
-            .count();
-        let blocked_count = coding_state
-            .task_reports
-            .iter()
-            .filter(|r| r.status == CodingTaskStatus::ImplementationBlocked)
-            .count();
-
-        self.add_system_message(&format!(
-            "코딩 단계 완료. 성공: {}, 차단: {}",
-            success_count, blocked_count,
-        ));
-
-        self.add_system_message(&format!(
-            "통합 브랜치가 유지됩니다: {}",
-            integration_branch,
-        ));
-
-        self.input_mode = InputMode::Done;
-    }
 
     pub fn open_external_editor(&mut self) {
         self.pending_external_editor = false;
@@ -215,6 +215,14 @@ impl TerminalWriter {
                 &mut self.stdout,
                 app.selected_mode_index(),
             )?;
+        } else if app.is_session_browser() {
+            line_count += write_session_browser(
+                &mut self.stdout,
+                app.session_filter(),
+                &app.session_browser_lines(),
+                app.selected_session_index(),
+                self.terminal_width,
+            )?;
         } else if app.is_waiting_for_input() {
             let result = write_input_lines(
                 &mut self.stdout,
@@ -430,11 +438,15 @@ fn build_right_column(max_width: usize) -> Vec<(String, style::Color, bool)> {
     lines
 }
 
-const MODE_LABELS: [&str; 2] = [
+const MODE_LABELS: [&str; 4] = [
     "1. 처음부터 만들기",
     "2. 이전 세션 이어서",
+    "3. Bear 워크트리/브랜치 정리",
+    "4. 세션 브라우저",
 ];
 
+const SESSION_BROWSER_VISIBLE_ROWS: usize = 10;
+
 fn write_mode_selection_menu(
     stdout: &mut Stdout,
     selected_index: usize,
@@ -475,6 +487,68 @@ fn write_mode_selection_menu(
     Ok(line_count)
 }
 
+/// 검색어 줄과, 선택된 세션이 보이도록 스크롤한 세션 목록을 그린다. 긴 줄은 터미널 폭에서 자른다.
+fn write_session_browser(
+    stdout: &mut Stdout,
+    filter: &str,
+    lines: &[String],
+    selected_index: usize,
+    max_width: u16,
+) -> Result<u16, std::io::Error> {
+    queue!(
+        stdout,
+        style::SetForegroundColor(style::Color::Cyan),
+        style::Print("검색: "),
+        style::ResetColor,
+        style::Print(filter),
+        style::Print("\r\n"),
+    )?;
+    let mut line_count: u16 = 1;
+
+    if lines.is_empty() {
+        queue!(
+            stdout,
+            style::SetForegroundColor(style::Color::DarkGrey),
+            style::Print("  일치하는 세션이 없습니다."),
+            style::ResetColor,
+            style::Print("\r\n"),
+        )?;
+        return Ok(line_count + 1);
+    }
+
+    let first_visible = selected_index.saturating_sub(SESSION_BROWSER_VISIBLE_ROWS - 1);
+    let content_width = (max_width as usize).saturating_sub(2);
+    for (i, line) in lines
+        .iter()
+        .enumerate()
+        .skip(first_visible)
+        .take(SESSION_BROWSER_VISIBLE_ROWS)
+    {
+        let is_selected = i == selected_index;
+        let marker = if is_selected { "> " } else { "  " };
+        let truncated = wrap_text_by_char_width(line, content_width)
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        queue!(
+            stdout,
+            style::SetForegroundColor(if is_selected {
+                style::Color::Cyan
+            } else {
+                style::Color::Reset
+            }),
+            style::Print(marker),
+            style::Print(truncated),
+            style::ResetColor,
+            style::Print("\r\n"),
+        )?;
+        line_count += 1;
+    }
+
+    Ok(line_count)
+}
+
 fn wrap_words(text: &str, max_width: usize) -> Vec<String> {
     if max_width == 0 {
         return vec![];
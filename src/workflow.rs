@@ -0,0 +1,122 @@
+mod acceptance;
+mod clarification;
+pub mod cleanup;
+pub mod coding;
+mod commit_policy;
+mod delivery;
+mod documentation;
+mod engine;
+mod error;
+mod file_validation;
+mod handoff;
+mod integration_review;
+mod journal_storage;
+mod planning;
+pub mod preflight;
+pub mod project_config;
+mod requirements_import;
+mod revision;
+pub mod session_catalog;
+mod session_naming;
+mod session_report;
+mod spec_writing;
+pub mod task_review;
+mod traceability;
+mod verification;
+mod verification_pipeline;
+
+pub use engine::Engine;
+pub use error::WorkflowError;
+
+/// 워크플로 엔진이 현재 머물러 있는 단계.
+/// 입력을 기다리는 단계에서만 대응하는 [`WorkflowCommand`]를 받는다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkflowPhase {
+    /// 새 세션 시작 또는 이전 세션 재개를 기다린다.
+    Idle,
+    /// 에이전트가 요구사항, 스펙, 플랜 또는 태스크 추출을 처리 중이다.
+    AgentWorking,
+    ClarificationAnswer,
+    SpecClarificationAnswer,
+    SpecFeedback,
+    PlanClarificationAnswer,
+    PlanFeedback,
+    /// 추출된 작업 목록을 사용자가 수정하고 확정하기를 기다린다.
+    TaskReview,
+    /// 코딩, 리뷰, 빌드/테스트, 머지 파이프라인이 실행 중이다.
+    Coding,
+    /// 현재 작업을 마친 뒤 다음 작업을 시작하기 전에 멈춰 있다.
+    Paused,
+    BuildTestCommandInput,
+    AcceptanceReview,
+    /// 통합 브랜치를 기준 브랜치에 머지하거나 패치/번들로 내보낼 방법을 기다린다.
+    Delivery,
+    Done,
+    /// 사용자 요청으로 진행 상태를 저장하고 세션을 중지했다.
+    Stopped,
+    /// 복구할 수 없는 오류로 세션이 중단되었다.
+    Failed,
+}
+
+impl WorkflowPhase {
+    /// 사용자 입력을 기다리는 단계인지 여부.
+    pub fn is_waiting_for_input(self) -> bool {
+        matches!(
+            self,
+            WorkflowPhase::Idle
+                | WorkflowPhase::ClarificationAnswer
+                | WorkflowPhase::SpecClarificationAnswer
+                | WorkflowPhase::SpecFeedback
+                | WorkflowPhase::PlanClarificationAnswer
+                | WorkflowPhase::PlanFeedback
+                | WorkflowPhase::TaskReview
+                | WorkflowPhase::BuildTestCommandInput
+                | WorkflowPhase::AcceptanceReview
+                | WorkflowPhase::Delivery
+        )
+    }
+
+    /// 승인 명령을 받을 수 있는 단계인지 여부.
+    pub fn accepts_approval(self) -> bool {
+        matches!(
+            self,
+            WorkflowPhase::SpecFeedback
+                | WorkflowPhase::PlanFeedback
+                | WorkflowPhase::TaskReview
+                | WorkflowPhase::AcceptanceReview
+        )
+    }
+}
+
+/// 엔진을 구동하는 쪽(TUI, 헤드리스 러너 등)이 보내는 명령.
+#[derive(Debug, Clone)]
+pub enum WorkflowCommand {
+    /// 요구사항으로 새 세션을 시작한다. [`WorkflowPhase::Idle`]에서만 유효하다.
+    StartSession { requirements: String },
+    /// spec.md(및 선택적으로 plan.md)가 있는 이전 세션 디렉토리에서 재개한다.
+    ResumeSession { session_dir: String },
+    /// 질문에 대한 답변 또는 빌드/테스트 명령어를 제출한다.
+    SubmitAnswer(String),
+    /// 스펙/플랜 드래프트에 대한 피드백, 작업 목록 수정 명령 또는 최종 코드에 대한 변경 요청을 제출한다.
+    Feedback(String),
+    Approve,
+    /// 현재 작업의 리뷰/빌드/머지가 끝나면 다음 작업을 시작하지 않고 멈춘다.
+    Pause,
+    /// 일시 정지를 풀고 다음 작업을 시작한다. 아직 멈추기 전이면 일시 정지 예약을 취소한다.
+    Resume,
+    /// 현재 작업이 끝나면 진행 상태를 저널에 저장하고 세션을 중지한다.
+    Stop,
+    /// 세션 보고서와 저널의 모든 산출물을 tar.gz 하나로 묶는다. [`WorkflowPhase::Done`]에서만 유효하다.
+    ExportReportBundle,
+    Cancel,
+}
+
+/// 엔진이 진행 상황을 알리기 위해 내보내는 이벤트.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkflowEvent {
+    /// 사용자에게 보여줄 시스템 메시지.
+    Message(String),
+    /// 실행 중인 에이전트의 스트리밍 출력 한 줄.
+    AgentOutput(String),
+    PhaseChanged(WorkflowPhase),
+}
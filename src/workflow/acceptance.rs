@@ -0,0 +1,315 @@
+use std::path::{Path, PathBuf};
+
+use super::coding::{CodingTask, CodingTaskKind, CodingTaskStatus, TaskReport, TaskRetry};
+
+const MAX_TITLE_CHARS: usize = 60;
+
+/// 코딩 단계 종료 후 사용자에게 보여줄 통합 브랜치 요약 메시지를 만든다.
+pub fn build_acceptance_summary(
+    task_reports: &[TaskReport],
+    integration_branch: &str,
+    commit_log: &str,
+    diff_stat: &str,
+) -> String {
+    let mut summary = format!("통합 브랜치 요약: {}\n", integration_branch);
+
+    summary.push_str("\n작업 결과:");
+    for report in task_reports {
+        let status_label = match report.status {
+            CodingTaskStatus::ImplementationSuccess => "SUCCESS",
+            CodingTaskStatus::ImplementationBlocked => "BLOCKED",
+        };
+        summary.push_str(&format!("\n- [{}] {}", report.task_id, status_label));
+    }
+
+    summary.push_str("\n\n커밋 목록:\n");
+    summary.push_str(non_empty_or_placeholder(commit_log));
+
+    summary.push_str("\n\n변경 파일:\n");
+    summary.push_str(non_empty_or_placeholder(diff_stat));
+
+    let blocked = blocked_task_ids(task_reports);
+    if !blocked.is_empty() {
+        summary.push_str(&format!(
+            "\n\n차단된 작업: {}\n\
+             다시 실행하려면 첫 줄에 `/rerun <ID> [<ID> ...]`를, 다음 줄부터 추가 지침을 입력하세요.",
+            blocked.join(", "),
+        ));
+    }
+
+    summary.push_str(
+        "\n\n최종 코드를 검토한 뒤 추가 변경 요청을 입력하거나, Ctrl+A를 눌러 승인하세요.",
+    );
+    summary
+}
+
+/// 가장 최근 보고서가 BLOCKED인 태스크 ID를 처음 실행된 순서대로 반환한다.
+pub fn blocked_task_ids(task_reports: &[TaskReport]) -> Vec<String> {
+    let mut task_ids: Vec<String> = Vec::new();
+    for report in task_reports {
+        if !task_ids.contains(&report.task_id) {
+            task_ids.push(report.task_id.clone());
+        }
+    }
+
+    task_ids.retain(|task_id| {
+        task_reports
+            .iter()
+            .rev()
+            .find(|r| &r.task_id == task_id)
+            .is_some_and(|r| r.status == CodingTaskStatus::ImplementationBlocked)
+    });
+    task_ids
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RerunRequest {
+    pub task_ids: Vec<String>,
+    pub guidance: Option<String>,
+}
+
+/// `/rerun TASK-01 TASK-03` 형식의 첫 줄을 해석하고, 나머지 줄은 추가 지침으로 사용한다.
+/// 재실행 명령이 아니면 None을 반환한다.
+pub fn parse_rerun_request(input: &str) -> Option<RerunRequest> {
+    let (first_line, rest) = input.split_once('\n').unwrap_or((input, ""));
+    let task_ids = parse_command_ids(first_line, "/rerun")?;
+    let guidance = Some(rest.trim().to_string()).filter(|g| !g.is_empty());
+
+    Some(RerunRequest { task_ids, guidance })
+}
+
+/// `<command> ID1 ID2` 형식의 한 줄에서 공백이나 쉼표로 나눈 ID 목록을 읽는다.
+/// 다른 명령이면 None을 반환한다.
+pub fn parse_command_ids(line: &str, command: &str) -> Option<Vec<String>> {
+    let arguments = line.trim().strip_prefix(command)?;
+    if !arguments.is_empty() && !arguments.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    Some(
+        arguments
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// 재실행 전 차단 보고서를 보존할 경로. 같은 태스크를 여러 번 재실행해도 덮어쓰지 않도록 시도 번호를 붙인다.
+pub fn blocked_report_file_path(dir: &Path, task_id: &str, attempt: usize) -> PathBuf {
+    dir.join(format!("{}-blocked-{}.md", task_id, attempt))
+}
+
+/// 원래 태스크를 이전 차단 보고서와 사용자 지침을 포함한 재실행 태스크로 만든다.
+pub fn build_rerun_task(
+    task: &CodingTask,
+    previous_report_path: PathBuf,
+    guidance: Option<String>,
+) -> CodingTask {
+    CodingTask {
+        retry: Some(TaskRetry {
+            previous_report_path,
+            guidance,
+        }),
+        ..task.clone()
+    }
+}
+
+fn non_empty_or_placeholder(text: &str) -> &str {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        "(없음)"
+    } else {
+        trimmed
+    }
+}
+
+/// 사용자의 변경 요청을 기존 파이프라인에서 처리할 새 코딩 태스크로 변환한다.
+pub fn build_change_request_task(tasks: &[CodingTask], change_request: &str) -> CodingTask {
+    let description = format!(
+        "코딩 단계 완료 후 사용자가 최종 코드를 검토하고 요청한 추가 변경 사항입니다. \
+         통합 브랜치의 현재 코드를 기준으로 아래 요청을 반영하세요.\n\n\
+         사용자 변경 요청:\n{}",
+        change_request,
+    );
+
+    CodingTask {
+        task_id: next_task_id(tasks),
+        title: change_request_title(change_request),
+        description,
+        dependencies: Vec::new(),
+        kind: CodingTaskKind::Implementation,
+        retry: None,
+        requirement_ids: Vec::new(),
+    }
+}
+
+/// 기존 태스크 ID 중 가장 큰 번호 다음의 `TASK-xx` ID를 반환한다.
+pub fn next_task_id(tasks: &[CodingTask]) -> String {
+    let next_number = tasks
+        .iter()
+        .filter_map(|task| task.task_id.strip_prefix("TASK-"))
+        .filter_map(|number| number.parse::<usize>().ok())
+        .max()
+        .map_or(0, |max| max + 1);
+
+    format!("TASK-{:02}", next_number)
+}
+
+fn change_request_title(change_request: &str) -> String {
+    let first_line = change_request
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+
+    if first_line.chars().count() <= MAX_TITLE_CHARS {
+        return format!("사용자 변경 요청: {}", first_line);
+    }
+
+    let truncated: String = first_line.chars().take(MAX_TITLE_CHARS).collect();
+    format!("사용자 변경 요청: {}...", truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::coding::TaskTrace;
+
+    fn make_task(task_id: &str) -> CodingTask {
+        CodingTask {
+            task_id: task_id.to_string(),
+            title: "작업".to_string(),
+            description: "설명".to_string(),
+            dependencies: vec![],
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn next_task_id_follows_highest_number() {
+        let tasks = vec![make_task("TASK-00"), make_task("TASK-07"), make_task("TASK-03")];
+        assert_eq!(next_task_id(&tasks), "TASK-08");
+    }
+
+    #[test]
+    fn next_task_id_starts_from_zero_without_tasks() {
+        assert_eq!(next_task_id(&[]), "TASK-00");
+    }
+
+    #[test]
+    fn next_task_id_ignores_non_numeric_ids() {
+        let tasks = vec![make_task("TASK-01"), make_task("CUSTOM")];
+        assert_eq!(next_task_id(&tasks), "TASK-02");
+    }
+
+    #[test]
+    fn change_request_task_contains_request() {
+        let tasks = vec![make_task("TASK-00")];
+        let task = build_change_request_task(&tasks, "\n로그인 버튼 색상을 변경해주세요.\n상세 내용");
+
+        assert_eq!(task.task_id, "TASK-01");
+        assert_eq!(task.title, "사용자 변경 요청: 로그인 버튼 색상을 변경해주세요.");
+        assert!(task.description.contains("상세 내용"));
+        assert!(task.dependencies.is_empty());
+    }
+
+    #[test]
+    fn change_request_title_is_truncated() {
+        let long_request = "가".repeat(100);
+        let task = build_change_request_task(&[], &long_request);
+
+        assert!(task.title.ends_with("..."));
+        assert!(task.title.chars().count() < 100);
+    }
+
+    #[test]
+    fn acceptance_summary_lists_tasks_and_git_info() {
+        let reports = vec![
+            TaskReport {
+                task_id: "TASK-00".to_string(),
+                status: CodingTaskStatus::ImplementationSuccess,
+                report: String::new(),
+                report_file_path: PathBuf::new(),
+                trace: TaskTrace::default(),
+            },
+            TaskReport {
+                task_id: "TASK-01".to_string(),
+                status: CodingTaskStatus::ImplementationBlocked,
+                report: String::new(),
+                report_file_path: PathBuf::new(),
+                trace: TaskTrace::default(),
+            },
+        ];
+
+        let summary = build_acceptance_summary(
+            &reports,
+            "bear/integration/session-abc",
+            "abc1234 Add feature",
+            " src/lib.rs | 2 +-",
+        );
+
+        assert!(summary.contains("bear/integration/session-abc"));
+        assert!(summary.contains("[TASK-00] SUCCESS"));
+        assert!(summary.contains("[TASK-01] BLOCKED"));
+        assert!(summary.contains("abc1234 Add feature"));
+        assert!(summary.contains("src/lib.rs"));
+        assert!(summary.contains("차단된 작업: TASK-01"));
+    }
+
+    fn make_report(task_id: &str, status: CodingTaskStatus) -> TaskReport {
+        TaskReport {
+            task_id: task_id.to_string(),
+            status,
+            report: String::new(),
+            report_file_path: PathBuf::new(),
+            trace: TaskTrace::default(),
+        }
+    }
+
+    #[test]
+    fn blocked_task_ids_use_latest_report() {
+        let reports = vec![
+            make_report("TASK-00", CodingTaskStatus::ImplementationBlocked),
+            make_report("TASK-01", CodingTaskStatus::ImplementationBlocked),
+            make_report("TASK-02", CodingTaskStatus::ImplementationSuccess),
+            make_report("TASK-00", CodingTaskStatus::ImplementationSuccess),
+        ];
+
+        assert_eq!(blocked_task_ids(&reports), vec!["TASK-01".to_string()]);
+    }
+
+    #[test]
+    fn parse_rerun_request_reads_ids_and_guidance() {
+        let request = parse_rerun_request("/rerun TASK-01, TASK-03\n\n목 서버를 사용하세요.\n").unwrap();
+
+        assert_eq!(request.task_ids, vec!["TASK-01".to_string(), "TASK-03".to_string()]);
+        assert_eq!(request.guidance.as_deref(), Some("목 서버를 사용하세요."));
+
+        let without_guidance = parse_rerun_request("/rerun TASK-01").unwrap();
+        assert_eq!(without_guidance.guidance, None);
+
+        assert_eq!(parse_rerun_request("/rerunning"), None);
+        assert_eq!(parse_rerun_request("버튼 색상을 바꿔주세요"), None);
+    }
+
+    #[test]
+    fn rerun_task_keeps_identity_and_adds_retry() {
+        let task = make_task("TASK-01");
+        let path = blocked_report_file_path(Path::new("/journal"), "TASK-01", 1);
+
+        let rerun = build_rerun_task(&task, path.clone(), Some("지침".to_string()));
+
+        assert_eq!(path, PathBuf::from("/journal/TASK-01-blocked-1.md"));
+        assert_eq!(rerun.task_id, "TASK-01");
+        assert_eq!(rerun.retry.unwrap().previous_report_path, path);
+    }
+
+    #[test]
+    fn acceptance_summary_uses_placeholder_for_empty_git_info() {
+        let summary = build_acceptance_summary(&[], "bear/integration/x", "", "  ");
+        assert_eq!(summary.matches("(없음)").count(), 2);
+    }
+}
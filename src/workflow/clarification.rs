@@ -0,0 +1,323 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ClarificationQuestions {
+    pub questions: Vec<ClarificationQuestion>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ClarificationQuestion {
+    pub question: String,
+    #[serde(default)]
+    pub choices: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClarificationAnswer {
+    Text(String),
+    Skipped,
+    DeferredToAgent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuestionAnswer {
+    pub question: String,
+    pub answer: ClarificationAnswer,
+}
+
+#[derive(Clone)]
+pub struct QaRound {
+    pub answers: Vec<QuestionAnswer>,
+}
+
+impl QaRound {
+    /// 프롬프트에 넣을 질문별 답변 목록. 건너뛴 질문과 에이전트에게 맡긴 질문은 그 의도를 함께 적는다.
+    pub fn format_for_prompt(&self) -> String {
+        let mut result = String::new();
+        for (i, entry) in self.answers.iter().enumerate() {
+            let answer = match &entry.answer {
+                ClarificationAnswer::Text(text) => text.clone(),
+                ClarificationAnswer::Skipped => {
+                    "(skipped by the user; do NOT assume an answer, keep it as an open question)"
+                        .to_string()
+                }
+                ClarificationAnswer::DeferredToAgent => {
+                    "(the user deferred this decision to the assistant; choose a reasonable option and record it as an explicit assumption)"
+                        .to_string()
+                }
+            };
+            result.push_str(&format!(
+                "Question {}: {}\nUser's answer: {}\n\n",
+                i + 1,
+                entry.question,
+                answer,
+            ));
+        }
+        result
+    }
+}
+
+const SKIP_COMMAND: &str = "/skip";
+const DEFER_COMMAND: &str = "/defer";
+
+/// 질문 하나에 대한 입력을 해석한다. 선택지가 있는 질문에서 번호만 입력하면 해당 선택지를 답변으로 쓴다.
+pub fn parse_question_answer(input: &str, question: &ClarificationQuestion) -> ClarificationAnswer {
+    let trimmed = input.trim();
+    if trimmed == SKIP_COMMAND {
+        return ClarificationAnswer::Skipped;
+    }
+    if trimmed == DEFER_COMMAND {
+        return ClarificationAnswer::DeferredToAgent;
+    }
+
+    let chosen = trimmed
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| number.checked_sub(1))
+        .and_then(|index| question.choices.get(index));
+    match chosen {
+        Some(choice) => ClarificationAnswer::Text(choice.clone()),
+        None => ClarificationAnswer::Text(trimmed.to_string()),
+    }
+}
+
+/// 한 번에 하나씩 보여줄 질문 메시지. `position`은 0부터 시작한다.
+pub fn format_question_prompt(question: &ClarificationQuestion, position: usize, total: usize) -> String {
+    let mut message = format!("질문 {}/{}: {}", position + 1, total, question.question);
+    for (i, choice) in question.choices.iter().enumerate() {
+        message.push_str(&format!("\n  {}) {}", i + 1, choice));
+    }
+
+    if question.choices.is_empty() {
+        message.push_str("\n\n답변을 입력하세요.");
+    } else {
+        message.push_str("\n\n번호로 선택하거나 직접 답변을 입력하세요.");
+    }
+    message.push_str(&format!(
+        " {}로 건너뛰고, {}로 에이전트에게 결정을 맡길 수 있습니다.",
+        SKIP_COMMAND, DEFER_COMMAND,
+    ));
+    message
+}
+
+pub fn system_prompt() -> &'static str {
+    r#"# Terminology
+
+In this document, the term **"spec"** is used as shorthand for **"specification"**. Unless explicitly qualified, "spec" refers to a software specification (not a "code", "standard", or other non-software usage of the term).
+
+---
+
+# Role
+
+You are the **specification-driven development** assistant whose sole responsibility is to produce a high-quality software specification, which satisfies the user request, and iteratively refine written specifications based on the user's feedback.
+
+You MUST NOT perform implementation work of any kind. This includes (but is not limited to) writing or modifying source code, running commands, executing tests, changing files, generating patches, making configuration edits, creating pull requests, or taking any action that directly completes the requested task.
+
+For every user request, you MUST respond only with specification content: clarify requirements, define scope and non-scope, document assumptions and constraints, specify interfaces and acceptance criteria, and capture open questions. If the user asks you to "just do it", you MUST convert the request into a spec and ask for any missing information instead of executing the work.
+
+---
+
+# High-level Philosophy (non-negotiable)
+
+- The spec MUST describe WHAT the system/module MUST do (externally observable behavior and contracts), not HOW it is implemented internally.
+- However, any strict interface SHOULD be a contract and MAY be specified concretely (function signatures, types, error model), if it has consumers (e.g., external clients or internal modules) that are hard to change without breaking compatibility.
+- The spec MUST be testable: it MUST include acceptance criteria that can be validated via automated tests or reproducible manual steps.
+- The spec MUST explicitly call out assumptions, non-goals, and open questions.
+
+---
+
+# What MUST NOT be in the Spec (unless explicitly requested by the user)
+
+- Concrete internal implementation mechanisms such as:
+  - Threading model choices (mutex/strand, executor placement, etc.)
+  - Specific database schema/table names unless the schema itself is a published contract for other consumers
+  - Specific file paths or class layouts
+  - Specific library choices unless mandated by product constraints
+- Detailed task breakdown, file-by-file change plan, or sequencing steps (these belong to the Planner/Implementer phases)
+
+If the user asks for these, capture them in:
+- `Open questions` (if undecided), or
+- `Non-functional constraints` (only if it is a hard constraint)
+
+---
+
+# Datetime Handling Rule (mandatory)
+
+You **MUST** get the current datetime using Python scripts from the local system in `Asia/Seoul` timezone, not from your LLM model, whenever you need current datetime or timestamp."#
+}
+
+const USER_PROMPT_TEMPLATE: &str = r#"Given the original user request AND the full clarification Q&A log so far, generate clarification questions that reduce ambiguity and de-risk implementation.
+
+If, in your judgment, there are no remaining clarification questions that are necessary to begin writing the spec, return an empty array for "questions".
+
+Coverage requirements (only when questions are non-empty):
+When you ask questions, they MUST collectively cover the following areas across the set of questions (each area should be covered by at least one question):
+1) Scope boundaries: what is explicitly in-scope vs out-of-scope.
+2) Primary consumer: who will use the deliverable and in what environment/workflow.
+3) Success definition: what "done" means and how success will be validated.
+4) Key edge cases: the most important corner cases or tricky scenarios.
+5) Error expectations: expected failure modes, error handling, and user-visible behavior.
+
+Constraints:
+- Output MUST be valid JSON that conforms to the provided JSON Schema.
+- Provide 0–5 questions total.
+- Each question should be precise, answerable, and non-overlapping.
+- When a question has a small set of sensible answers, provide them in "choices" (2–5 short options). Omit "choices" for open-ended questions.
+- Inspect the current workspace using the available tools. Read the files required to understand the context and to avoid asking questions that are already answered by existing files.
+- Do NOT ask questions that you can infer from the workspace files.
+- Do NOT ask questions that are purely preference/subjective unless they materially impact scope or correctness.
+
+Original user request (verbatim):
+<<<
+{{ORIGINAL_REQUEST_TEXT}}
+>>>
+
+Attached requirement source files (may be empty). They are part of the original request; you MUST read every listed file using the available tools before generating questions:
+<<<
+{{ATTACHED_FILES_TEXT}}
+>>>
+
+Clarification Q&A log so far (may be empty). Each entry is one of the assistant's questions followed by the user's answer to that question:
+<<<
+{{QA_LOG_TEXT}}
+>>>
+
+Your output MUST conform to the given JSON Schema.
+"#;
+
+pub fn build_user_prompt(
+    original_request: &str,
+    attached_files: &[PathBuf],
+    qa_log: &[QaRound],
+) -> String {
+    let qa_log_text = if qa_log.is_empty() {
+        String::new()
+    } else {
+        format_qa_log(qa_log)
+    };
+    let attached_files_text = attached_files
+        .iter()
+        .map(|path| format!("- {}", path.display()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    USER_PROMPT_TEMPLATE
+        .replace("{{ORIGINAL_REQUEST_TEXT}}", original_request)
+        .replace("{{ATTACHED_FILES_TEXT}}", &attached_files_text)
+        .replace("{{QA_LOG_TEXT}}", &qa_log_text)
+}
+
+/// CLI에 전달할 JSON Schema. 프롬프트 내 스키마(minItems: 3)와 달리
+/// minItems: 0으로 설정하여 "질문 없음"(빈 배열) 응답을 허용한다.
+pub fn clarification_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "questions": {
+                "type": "array",
+                "minItems": 0,
+                "maxItems": 5,
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "question": {
+                            "type": "string",
+                            "minLength": 5
+                        },
+                        "choices": {
+                            "type": "array",
+                            "maxItems": 5,
+                            "items": { "type": "string", "minLength": 1 }
+                        }
+                    },
+                    "required": ["question"],
+                    "additionalProperties": false
+                }
+            }
+        },
+        "required": ["questions"],  
+        "additionalProperties": false
+    })
+}
+
+fn format_qa_log(qa_log: &[QaRound]) -> String {
+    let mut result = String::new();
+
+    for round in qa_log {
+        result.push_str(&round.format_for_prompt());
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_prompt_lists_attached_files() {
+        let attached = vec![PathBuf::from("/ws/.bear/20260101/session/sources/01-login.md")];
+
+        let prompt = build_user_prompt("로그인 기능", &attached, &[]);
+
+        assert!(prompt.contains("로그인 기능"));
+        assert!(prompt.contains("- /ws/.bear/20260101/session/sources/01-login.md"));
+        assert!(!prompt.contains("{{"));
+    }
+
+    fn choice_question() -> ClarificationQuestion {
+        ClarificationQuestion {
+            question: "어떤 인증 방식을 사용하나요?".to_string(),
+            choices: vec!["OAuth".to_string(), "비밀번호".to_string()],
+        }
+    }
+
+    #[test]
+    fn parse_question_answer_handles_commands_and_choices() {
+        let question = choice_question();
+
+        assert_eq!(parse_question_answer(" /skip ", &question), ClarificationAnswer::Skipped);
+        assert_eq!(parse_question_answer("/defer", &question), ClarificationAnswer::DeferredToAgent);
+        assert_eq!(parse_question_answer("2", &question), ClarificationAnswer::Text("비밀번호".to_string()));
+        assert_eq!(parse_question_answer("3", &question), ClarificationAnswer::Text("3".to_string()));
+        assert_eq!(parse_question_answer("SSO", &question), ClarificationAnswer::Text("SSO".to_string()));
+    }
+
+    #[test]
+    fn question_prompt_lists_choices() {
+        let message = format_question_prompt(&choice_question(), 1, 3);
+
+        assert!(message.starts_with("질문 2/3: 어떤 인증 방식을 사용하나요?"));
+        assert!(message.contains("1) OAuth\n  2) 비밀번호"));
+        assert!(message.contains("/skip"));
+    }
+
+    #[test]
+    fn questions_deserialize_with_optional_choices() {
+        let json = r#"{"questions": [{"question": "범위는 어디까지인가요?"}, {"question": "DB는?", "choices": ["SQLite", "Postgres"]}]}"#;
+
+        let parsed: ClarificationQuestions = serde_json::from_str(json).unwrap();
+
+        assert!(parsed.questions[0].choices.is_empty());
+        assert_eq!(parsed.questions[1].choices.len(), 2);
+    }
+
+    #[test]
+    fn qa_round_marks_skipped_and_deferred_answers() {
+        let round = QaRound {
+            answers: vec![
+                QuestionAnswer { question: "Q1".to_string(), answer: ClarificationAnswer::Text("A1".to_string()) },
+                QuestionAnswer { question: "Q2".to_string(), answer: ClarificationAnswer::Skipped },
+                QuestionAnswer { question: "Q3".to_string(), answer: ClarificationAnswer::DeferredToAgent },
+            ],
+        };
+
+        let text = round.format_for_prompt();
+
+        assert!(text.contains("Question 1: Q1\nUser's answer: A1"));
+        assert!(text.contains("Question 2: Q2\nUser's answer: (skipped by the user"));
+        assert!(text.contains("Question 3: Q3\nUser's answer: (the user deferred"));
+    }
+}
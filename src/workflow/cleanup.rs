@@ -0,0 +1,612 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use uuid::Uuid;
+
+use crate::git::{self, GitError};
+
+use super::coding;
+use super::commit_policy::{INTEGRATION_BRANCH_PREFIX, TASK_BRANCH_PREFIX};
+use super::journal_storage;
+
+const SESSION_ID_LENGTH: usize = 36;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BearResourceKind {
+    Worktree,
+    TaskBranch,
+    IntegrationBranch,
+}
+
+/// 리소스와 세션 저널의 대응 관계.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionLink {
+    /// 저널에 coding-state.json이 있어 이어서 진행할 수 있는 세션.
+    Resumable(PathBuf),
+    /// 저널은 있지만 이어서 진행할 상태가 없는 세션.
+    Finished(PathBuf),
+    /// 브랜치 이름의 세션에 해당하는 저널이 없다.
+    Missing,
+    /// 이름만으로 세션을 알 수 없는 리소스 (태스크 브랜치, 워크트리).
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+pub struct BearResource {
+    pub kind: BearResourceKind,
+    pub branch: Option<String>,
+    pub worktree_path: Option<PathBuf>,
+    pub age_days: Option<u64>,
+    pub merged: bool,
+    pub checked_out: bool,
+    pub session: SessionLink,
+}
+
+impl BearResource {
+    /// 이미 머지된 리소스만 정리한다. 머지되지 않은 작업, 현재 체크아웃된 브랜치,
+    /// 재개 가능한 세션의 통합 브랜치는 정리 대상에서 제외한다.
+    pub fn is_prunable(&self) -> bool {
+        self.merged && !self.checked_out && !matches!(self.session, SessionLink::Resumable(_))
+    }
+
+    fn display_name(&self) -> String {
+        match (&self.worktree_path, &self.branch) {
+            (Some(path), _) => path.display().to_string(),
+            (None, Some(branch)) => branch.clone(),
+            (None, None) => "(unknown)".to_string(),
+        }
+    }
+}
+
+struct WorktreeEntry {
+    path: PathBuf,
+    head: Option<String>,
+    branch: Option<String>,
+}
+
+/// 워크스페이스에 남아 있는 Bear 워크트리와 `bear/task/*`, `bear/integration/*` 브랜치를 찾아
+/// 나이, 머지 여부, 세션 저널과의 대응 관계를 함께 반환한다.
+/// 태스크 브랜치는 HEAD나 통합 브랜치에 들어갔으면 머지된 것으로 보고,
+/// 통합 브랜치와 워크트리는 기준 브랜치인 HEAD에 들어갔을 때만 머지된 것으로 본다.
+pub fn scan_bear_resources(workspace: &Path) -> Result<Vec<BearResource>, GitError> {
+    let current_branch = current_branch(workspace);
+    let branches = list_bear_branches(workspace)?;
+    let integration_branches: Vec<String> = branches
+        .iter()
+        .filter(|b| b.starts_with(INTEGRATION_BRANCH_PREFIX))
+        .cloned()
+        .collect();
+
+    let mut resources = Vec::new();
+
+    for entry in list_bear_worktrees(workspace)? {
+        let revision = entry.head.clone().unwrap_or_else(|| "HEAD".to_string());
+        // 통합 워크트리에는 세션 저널이 있으므로 세션 상태를 따른다.
+        let session = match &entry.branch {
+            Some(branch) if branch.starts_with(INTEGRATION_BRANCH_PREFIX) => {
+                link_integration_branch_to_session(workspace, branch)
+            }
+            _ => SessionLink::Unknown,
+        };
+        resources.push(BearResource {
+            kind: BearResourceKind::Worktree,
+            age_days: commit_age_days(workspace, &revision),
+            merged: is_merged_into_any(workspace, &revision, &[]),
+            branch: entry.branch,
+            worktree_path: Some(entry.path),
+            checked_out: false,
+            session,
+        });
+    }
+
+    for branch in branches {
+        let (kind, session) = if branch.starts_with(INTEGRATION_BRANCH_PREFIX) {
+            (
+                BearResourceKind::IntegrationBranch,
+                link_integration_branch_to_session(workspace, &branch),
+            )
+        } else {
+            (BearResourceKind::TaskBranch, SessionLink::Unknown)
+        };
+        let merge_targets: &[String] = match kind {
+            BearResourceKind::TaskBranch => &integration_branches,
+            _ => &[],
+        };
+
+        resources.push(BearResource {
+            kind,
+            age_days: commit_age_days(workspace, &branch),
+            merged: is_merged_into_any(workspace, &branch, merge_targets),
+            checked_out: current_branch.as_deref() == Some(branch.as_str()),
+            branch: Some(branch),
+            worktree_path: None,
+            session,
+        });
+    }
+
+    Ok(resources)
+}
+
+pub fn format_resource_list(resources: &[BearResource]) -> String {
+    if resources.is_empty() {
+        return "정리할 Bear 워크트리나 브랜치가 없습니다.".to_string();
+    }
+
+    let mut message = String::from("Bear가 만든 워크트리와 브랜치:\n");
+    for resource in resources {
+        let kind = match resource.kind {
+            BearResourceKind::Worktree => "워크트리",
+            BearResourceKind::TaskBranch => "태스크 브랜치",
+            BearResourceKind::IntegrationBranch => "통합 브랜치",
+        };
+        let age = resource
+            .age_days
+            .map_or("알 수 없음".to_string(), |days| format!("{}일 전", days));
+        let merged = if resource.merged { "머지됨" } else { "머지 안 됨" };
+        let session = match &resource.session {
+            SessionLink::Resumable(_) => ", 재개 가능한 세션",
+            SessionLink::Finished(_) => ", 세션 저널 있음",
+            SessionLink::Missing => ", 세션 저널 없음",
+            SessionLink::Unknown => "",
+        };
+        let note = if resource.checked_out {
+            " (현재 체크아웃됨, 유지)"
+        } else if !resource.is_prunable() {
+            " (유지)"
+        } else {
+            ""
+        };
+
+        message.push_str(&format!(
+            "\n- [{}] {} — 마지막 커밋 {}, {}{}{}",
+            kind,
+            resource.display_name(),
+            age,
+            merged,
+            session,
+            note,
+        ));
+    }
+    message
+}
+
+/// 정리 가능한 리소스를 워크트리, 브랜치 순서로 제거하고 `git worktree prune`을 실행한다.
+/// 커밋되지 않은 변경이 있는 워크트리와 git이 머지되지 않았다고 보는 브랜치는 지우지 않고
+/// 실패한 항목의 오류 메시지로 모아 반환한다.
+pub fn prune_bear_resources(workspace: &Path, resources: &[BearResource]) -> Vec<String> {
+    let mut errors = Vec::new();
+    let prunable: Vec<&BearResource> = resources.iter().filter(|r| r.is_prunable()).collect();
+
+    for resource in &prunable {
+        if resource.kind != BearResourceKind::Worktree {
+            continue;
+        }
+        let Some(path) = &resource.worktree_path else { continue };
+        // 이미 삭제된 디렉토리는 아래의 `git worktree prune`이 정리한다.
+        if path.exists()
+            && let Err(err) = remove_clean_worktree(workspace, path)
+        {
+            errors.push(format!("{}: {}", path.display(), err));
+        }
+    }
+
+    if let Err(err) = prune_worktree_metadata(workspace) {
+        errors.push(err.to_string());
+    }
+
+    for resource in &prunable {
+        if resource.kind == BearResourceKind::Worktree {
+            continue;
+        }
+        let Some(branch) = &resource.branch else { continue };
+        if let Err(err) = delete_merged_branch(workspace, branch) {
+            errors.push(format!("{}: {}", branch, err));
+        }
+    }
+
+    errors
+}
+
+/// `--force` 없이 제거하므로 git이 수정되거나 추적되지 않은 파일이 있는 워크트리를 거부한다.
+fn remove_clean_worktree(workspace: &Path, worktree_path: &Path) -> Result<(), GitError> {
+    git::run(workspace, ["worktree", "remove", &worktree_path.display().to_string()])?;
+    Ok(())
+}
+
+/// `-d`로 지우므로 git이 HEAD에 머지되었다고 확인한 브랜치만 삭제된다.
+fn delete_merged_branch(workspace: &Path, branch: &str) -> Result<(), GitError> {
+    git::run(workspace, ["branch", "-d", branch])?;
+    Ok(())
+}
+
+fn prune_worktree_metadata(workspace: &Path) -> Result<(), GitError> {
+    git::run(workspace, ["worktree", "prune"])?;
+    Ok(())
+}
+
+fn current_branch(workspace: &Path) -> Option<String> {
+    // detached HEAD이면 출력 없이 종료 코드 1로 끝난다.
+    git::run(workspace, ["symbolic-ref", "--short", "-q", "HEAD"])
+        .ok()
+        .filter(|branch| !branch.is_empty())
+}
+
+pub(super) fn list_bear_branches(workspace: &Path) -> Result<Vec<String>, GitError> {
+    let stdout = git::run(
+        workspace,
+        [
+            "for-each-ref",
+            "--format=%(refname:short)",
+            "refs/heads/bear/task/",
+            "refs/heads/bear/integration/",
+        ],
+    )?;
+
+    let branches = stdout
+        .lines()
+        .filter(|line| {
+            line.starts_with(TASK_BRANCH_PREFIX) || line.starts_with(INTEGRATION_BRANCH_PREFIX)
+        })
+        .map(str::to_string)
+        .collect();
+    Ok(branches)
+}
+
+fn list_bear_worktrees(workspace: &Path) -> Result<Vec<WorktreeEntry>, GitError> {
+    let workspace_dir_name = workspace
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("workspace");
+    let bear_worktree_prefixes = [
+        format!("{}{}", workspace_dir_name, coding::TASK_WORKTREE_NAME_MARKER),
+        format!("{}{}", workspace_dir_name, coding::INTEGRATION_WORKTREE_NAME_MARKER),
+    ];
+
+    let entries = list_worktrees(workspace)?
+        .into_iter()
+        .filter(|entry| {
+            entry
+                .path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|name| bear_worktree_prefixes.iter().any(|prefix| name.starts_with(prefix)))
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// 주어진 브랜치가 체크아웃된 워크트리 경로. 사용자의 워크스페이스일 수도 있다.
+pub(super) fn find_branch_worktree(workspace: &Path, branch: &str) -> Option<PathBuf> {
+    list_worktrees(workspace)
+        .ok()?
+        .into_iter()
+        .find(|entry| entry.branch.as_deref() == Some(branch))
+        .map(|entry| entry.path)
+}
+
+/// `bear/integration/*` 브랜치가 체크아웃된 워크트리 경로. 세션 저널은 이 워크트리에 있다.
+pub(super) fn list_integration_worktrees(workspace: &Path) -> Vec<PathBuf> {
+    list_worktrees(workspace)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| {
+            entry
+                .branch
+                .as_deref()
+                .is_some_and(|branch| branch.starts_with(INTEGRATION_BRANCH_PREFIX))
+        })
+        .map(|entry| entry.path)
+        .filter(|path| path != workspace)
+        .collect()
+}
+
+fn list_worktrees(workspace: &Path) -> Result<Vec<WorktreeEntry>, GitError> {
+    let stdout = git::run(workspace, ["worktree", "list", "--porcelain"])?;
+    Ok(parse_worktree_list(&stdout))
+}
+
+fn parse_worktree_list(porcelain: &str) -> Vec<WorktreeEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<WorktreeEntry> = None;
+
+    for line in porcelain.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            entries.extend(current.take());
+            current = Some(WorktreeEntry {
+                path: PathBuf::from(path),
+                head: None,
+                branch: None,
+            });
+        } else if let Some(entry) = current.as_mut() {
+            if let Some(head) = line.strip_prefix("HEAD ") {
+                entry.head = Some(head.to_string());
+            } else if let Some(branch) = line.strip_prefix("branch refs/heads/") {
+                entry.branch = Some(branch.to_string());
+            }
+        }
+    }
+    entries.extend(current);
+    entries
+}
+
+fn commit_age_days(workspace: &Path, revision: &str) -> Option<u64> {
+    let committed_at: u64 = git::run(workspace, ["log", "-1", "--format=%ct", revision])
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(now.saturating_sub(committed_at) / SECONDS_PER_DAY)
+}
+
+/// 리비전이 현재 HEAD 또는 주어진 브랜치 중 하나에 이미 포함되어 있는지 확인한다.
+fn is_merged_into_any(workspace: &Path, revision: &str, targets: &[String]) -> bool {
+    std::iter::once("HEAD")
+        .chain(targets.iter().map(String::as_str))
+        .any(|target| is_ancestor(workspace, revision, target))
+}
+
+fn is_ancestor(workspace: &Path, revision: &str, target: &str) -> bool {
+    git::succeeds(workspace, ["merge-base", "--is-ancestor", revision, target])
+}
+
+/// 통합 브랜치 이름(기본 `bear/integration/<session-id>-<uuid>`)에서 세션 ID 후보를 찾아
+/// 브랜치의 워크트리, 워크스페이스 또는 코드 밖 저널 루트의 `.bear/<date>/<session-id>` 저널 디렉토리를 찾는다.
+fn link_integration_branch_to_session(workspace: &Path, branch: &str) -> SessionLink {
+    let session_ids = session_id_candidates(branch);
+    if session_ids.is_empty() {
+        return SessionLink::Missing;
+    }
+
+    let mut journal_roots = find_branch_worktree(workspace, branch)
+        .into_iter()
+        .chain([workspace.to_path_buf(), journal_storage::external_journal_root(workspace)]);
+    let journal = journal_roots.find_map(|root| {
+        session_ids
+            .iter()
+            .find_map(|session_id| find_session_journal(&root, session_id))
+    });
+
+    match journal {
+        Some(dir) if dir.join(coding::CODING_STATE_FILE_NAME).is_file() => {
+            SessionLink::Resumable(dir)
+        }
+        Some(dir) => SessionLink::Finished(dir),
+        None => SessionLink::Missing,
+    }
+}
+
+/// 브랜치 템플릿에 따라 세션 ID의 위치가 달라지므로 UUID 형식인 부분 문자열을 모두 후보로 본다.
+fn session_id_candidates(branch: &str) -> Vec<&str> {
+    branch
+        .char_indices()
+        .filter_map(|(start, _)| branch.get(start..start + SESSION_ID_LENGTH))
+        .filter(|candidate| Uuid::parse_str(candidate).is_ok())
+        .collect()
+}
+
+fn find_session_journal(workspace: &Path, session_id: &str) -> Option<PathBuf> {
+    let date_dirs = fs::read_dir(workspace.join(".bear")).ok()?;
+    date_dirs
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path().join(session_id))
+        .find(|candidate| candidate.is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let _ = git::run(dir, args);
+    }
+
+    fn init_git_repo(dir: &Path) {
+        run_git(dir, &["init"]);
+        run_git(dir, &["symbolic-ref", "HEAD", "refs/heads/master"]);
+        run_git(dir, &["config", "user.email", "test@test.com"]);
+        run_git(dir, &["config", "user.name", "Test"]);
+        run_git(dir, &["config", "commit.gpgsign", "false"]);
+        fs::write(dir.join("init.txt"), "init").unwrap();
+        run_git(dir, &["add", "init.txt"]);
+        run_git(dir, &["commit", "-m", "initial commit"]);
+    }
+
+    const SESSION_ID: &str = "0b5a3c1e-1111-4222-8333-444455556666";
+
+    #[test]
+    fn parse_worktree_list_reads_path_head_and_branch() {
+        let porcelain = "worktree /repo\nHEAD aaa\nbranch refs/heads/master\n\n\
+                         worktree /repo-bear-worktree-1\nHEAD bbb\nbranch refs/heads/bear/task/TASK-00-x\n\n\
+                         worktree /repo-detached\nHEAD ccc\ndetached\n";
+
+        let entries = parse_worktree_list(porcelain);
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].path, PathBuf::from("/repo-bear-worktree-1"));
+        assert_eq!(entries[1].head.as_deref(), Some("bbb"));
+        assert_eq!(entries[1].branch.as_deref(), Some("bear/task/TASK-00-x"));
+        assert_eq!(entries[2].branch, None);
+    }
+
+    #[test]
+    fn scan_finds_branches_with_merge_status_and_session_link() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("repo");
+        fs::create_dir(&workspace).unwrap();
+        init_git_repo(&workspace);
+
+        let integration = format!("bear/integration/{}-ffff", SESSION_ID);
+        run_git(&workspace, &["branch", &integration]);
+        run_git(&workspace, &["checkout", "-b", "bear/task/TASK-00-abcd"]);
+        fs::write(workspace.join("task.txt"), "task").unwrap();
+        run_git(&workspace, &["add", "task.txt"]);
+        run_git(&workspace, &["commit", "-m", "task commit"]);
+        run_git(&workspace, &["checkout", "master"]);
+
+        let journal_dir = workspace.join(".bear").join("20260101").join(SESSION_ID);
+        fs::create_dir_all(&journal_dir).unwrap();
+
+        let resources = scan_bear_resources(&workspace).unwrap();
+
+        let task = resources
+            .iter()
+            .find(|r| r.kind == BearResourceKind::TaskBranch)
+            .unwrap();
+        assert!(!task.merged);
+        assert_eq!(task.age_days, Some(0));
+        assert!(!task.is_prunable());
+
+        let integration_resource = resources
+            .iter()
+            .find(|r| r.kind == BearResourceKind::IntegrationBranch)
+            .unwrap();
+        assert!(integration_resource.merged);
+        assert_eq!(integration_resource.session, SessionLink::Finished(journal_dir.clone()));
+
+        fs::write(journal_dir.join("coding-state.json"), "{}").unwrap();
+        let resources = scan_bear_resources(&workspace).unwrap();
+        let integration_resource = resources
+            .iter()
+            .find(|r| r.kind == BearResourceKind::IntegrationBranch)
+            .unwrap();
+        assert!(!integration_resource.is_prunable());
+    }
+
+    #[test]
+    fn prune_removes_worktrees_and_branches_but_keeps_checked_out_branch() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("repo");
+        fs::create_dir(&workspace).unwrap();
+        init_git_repo(&workspace);
+
+        let integration = format!("bear/integration/{}-ffff", SESSION_ID);
+        run_git(&workspace, &["checkout", "-b", &integration]);
+        run_git(&workspace, &["branch", "bear/task/TASK-00-abcd"]);
+        let worktree_path = temp_dir.path().join("repo-bear-worktree-1234");
+        run_git(
+            &workspace,
+            &["worktree", "add", &worktree_path.display().to_string(), "bear/task/TASK-00-abcd"],
+        );
+
+        let resources = scan_bear_resources(&workspace).unwrap();
+        assert_eq!(resources.len(), 3);
+
+        let errors = prune_bear_resources(&workspace, &resources);
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        assert!(!worktree_path.exists());
+        let remaining = scan_bear_resources(&workspace).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert!(remaining[0].checked_out);
+        assert_eq!(remaining[0].branch.as_deref(), Some(integration.as_str()));
+    }
+
+    #[test]
+    fn prune_keeps_unmerged_branches_and_dirty_worktrees() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("repo");
+        fs::create_dir(&workspace).unwrap();
+        init_git_repo(&workspace);
+
+        run_git(&workspace, &["checkout", "-b", "bear/task/TASK-00-abcd"]);
+        fs::write(workspace.join("task.txt"), "task").unwrap();
+        run_git(&workspace, &["add", "task.txt"]);
+        run_git(&workspace, &["commit", "-m", "task commit"]);
+        run_git(&workspace, &["checkout", "master"]);
+        run_git(&workspace, &["branch", "bear/task/TASK-01-abcd"]);
+        let worktree_path = temp_dir.path().join("repo-bear-worktree-1234");
+        run_git(
+            &workspace,
+            &["worktree", "add", &worktree_path.display().to_string(), "bear/task/TASK-01-abcd"],
+        );
+        fs::write(worktree_path.join("work.txt"), "uncommitted").unwrap();
+
+        let mut resources = scan_bear_resources(&workspace).unwrap();
+        // 호출하는 쪽이 모두 정리 대상으로 표시해도 git은 변경이 남았거나 머지되지 않은 작업을 지우지 않는다.
+        for resource in &mut resources {
+            resource.merged = true;
+        }
+        let errors = prune_bear_resources(&workspace, &resources);
+
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(worktree_path.join("work.txt").exists());
+        assert!(git::succeeds(&workspace, ["rev-parse", "--verify", "bear/task/TASK-00-abcd"]));
+    }
+
+    #[test]
+    fn integration_work_is_merged_only_once_it_reaches_head() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("repo");
+        fs::create_dir(&workspace).unwrap();
+        init_git_repo(&workspace);
+
+        let integration = format!("bear/integration/{}-ffff", SESSION_ID);
+        let other_integration = format!("bear/integration/{}-eeee", SESSION_ID);
+        let worktree_path = temp_dir.path().join("repo-bear-integration-1234");
+        run_git(
+            &workspace,
+            &["worktree", "add", "-b", &integration, &worktree_path.display().to_string()],
+        );
+        fs::write(worktree_path.join("feature.txt"), "feature").unwrap();
+        run_git(&worktree_path, &["add", "feature.txt"]);
+        run_git(&worktree_path, &["commit", "-m", "task merged into integration"]);
+        run_git(&workspace, &["branch", &other_integration, &integration]);
+        run_git(&workspace, &["branch", "bear/task/TASK-00-abcd", &integration]);
+
+        let resources = scan_bear_resources(&workspace).unwrap();
+
+        let merged_of = |kind: BearResourceKind| -> Vec<bool> {
+            resources.iter().filter(|r| r.kind == kind).map(|r| r.merged).collect()
+        };
+        assert_eq!(merged_of(BearResourceKind::Worktree), vec![false]);
+        assert_eq!(merged_of(BearResourceKind::IntegrationBranch), vec![false, false]);
+        assert_eq!(merged_of(BearResourceKind::TaskBranch), vec![true]);
+    }
+
+    #[test]
+    fn integration_worktree_with_resumable_journal_is_kept() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("repo");
+        fs::create_dir(&workspace).unwrap();
+        init_git_repo(&workspace);
+
+        let integration = format!("bear/integration/{}-ffff", SESSION_ID);
+        let worktree_path = temp_dir.path().join("repo-bear-integration-1234");
+        run_git(
+            &workspace,
+            &["worktree", "add", "-b", &integration, &worktree_path.display().to_string()],
+        );
+        let journal_dir = worktree_path.join(".bear").join("20260101").join(SESSION_ID);
+        fs::create_dir_all(&journal_dir).unwrap();
+        fs::write(journal_dir.join("coding-state.json"), "{}").unwrap();
+
+        let resources = scan_bear_resources(&workspace).unwrap();
+
+        assert_eq!(resources.len(), 2);
+        assert!(resources.iter().all(|r| r.session == SessionLink::Resumable(journal_dir.clone())));
+        assert!(resources.iter().all(|r| !r.is_prunable()));
+        assert_eq!(list_integration_worktrees(&workspace), vec![worktree_path]);
+    }
+
+    #[test]
+    fn session_id_candidates_follow_custom_branch_templates() {
+        let default_branch = format!("bear/integration/{}-9f1c7d2e-0000-4000-8000-000000000000", SESSION_ID);
+        let custom_branch = format!("bear/integration/team-a/{}/x-ffff", SESSION_ID);
+
+        assert_eq!(session_id_candidates(&default_branch)[0], SESSION_ID);
+        assert_eq!(session_id_candidates(&custom_branch), vec![SESSION_ID]);
+        assert!(session_id_candidates("bear/integration/manual").is_empty());
+    }
+
+    #[test]
+    fn format_resource_list_reports_empty_workspace() {
+        assert_eq!(
+            format_resource_list(&[]),
+            "정리할 Bear 워크트리나 브랜치가 없습니다."
+        );
+    }
+}
@@ -1,11 +1,17 @@
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::claude_code_client::usage::UsageTotals;
+use crate::git::{self, GitError};
+
+use super::commit_policy::{CommitContext, CommitPolicy, ReportCommitMode};
+use super::journal_storage::JOURNAL_BRANCH;
+use super::verification_pipeline::{StageFailure, VerificationStage};
+
 // ---------------------------------------------------------------------------
 // Types
 // ---------------------------------------------------------------------------
@@ -21,15 +27,53 @@ pub struct CodingTask {
     pub title: String,
     pub description: String,
     pub dependencies: Vec<String>,
+    #[serde(default)]
+    pub kind: CodingTaskKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<TaskRetry>,
+    /// 이 태스크가 구현하는 스펙 요구사항 ID(`REQ-001` 형식).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requirement_ids: Vec<String>,
+}
+
+/// 차단되었던 태스크를 다시 실행할 때 코딩 에이전트에 전달하는 이전 시도 정보.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct TaskRetry {
+    pub previous_report_path: PathBuf,
+    pub guidance: Option<String>,
+}
+
+/// 태스크를 처리할 에이전트 역할. 계획에서 추출된 태스크는 모두 구현 태스크이다.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
+pub enum CodingTaskKind {
+    #[default]
+    Implementation,
+    Documentation,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct CodingTaskResult {
     pub status: CodingTaskStatus,
     pub report: String,
+    #[serde(default)]
+    pub requirement_tests: Vec<RequirementTests>,
 }
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+/// 코딩 에이전트가 보고한, 요구사항 하나를 검증하는 테스트 목록.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RequirementTests {
+    pub requirement_id: String,
+    pub tests: Vec<String>,
+}
+
+/// 추적성 매트릭스를 만들기 위해 태스크 보고서에 남기는 요구사항별 테스트와 통합된 커밋.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct TaskTrace {
+    pub requirement_tests: Vec<RequirementTests>,
+    pub commits: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum CodingTaskStatus {
     #[serde(rename = "IMPLEMENTATION_SUCCESS")]
     ImplementationSuccess,
@@ -43,12 +87,15 @@ pub struct CodingPhaseState {
     pub task_reports: Vec<TaskReport>,
     pub integration_branch: String,
     pub current_task_worktree: Option<TaskWorktreeInfo>,
-    pub build_test_commands: Option<BuildTestCommands>,
+    /// 이번 세션에서 태스크마다 실행할 검증 파이프라인. 처음 검증할 때 정한다.
+    pub verification_pipeline: Option<Vec<VerificationStage>>,
 }
 
 pub struct TaskWorktreeInfo {
     pub worktree_path: PathBuf,
     pub task_branch: String,
+    /// 워크트리를 만들 때의 `refs/heads`, `refs/tags` 목록. 에이전트가 건드린 ref를 찾는 기준이다.
+    pub ref_snapshot: Vec<String>,
 }
 
 pub enum RebaseOutcome {
@@ -70,23 +117,34 @@ pub enum ConflictResolutionStatus {
     ConflictResolutionFailed,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TaskReport {
     pub task_id: String,
     pub status: CodingTaskStatus,
     pub report: String,
     pub report_file_path: PathBuf,
+    #[serde(default)]
+    pub trace: TaskTrace,
 }
 
-#[derive(Clone)]
-pub struct BuildTestCommands {
-    pub build: String,
-    pub test: String,
-}
+pub const CODING_STATE_FILE_NAME: &str = "coding-state.json";
+pub const TASK_WORKTREE_NAME_MARKER: &str = "-bear-worktree-";
+pub const INTEGRATION_WORKTREE_NAME_MARKER: &str = "-bear-integration-";
 
-pub enum BuildTestOutcome {
-    Success,
-    BuildFailed { output: String },
-    TestFailed { output: String },
+/// 중지된 코딩 단계를 나중에 이어가기 위해 저널에 저장하는 진행 상태.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CodingStateSnapshot {
+    pub integration_branch: String,
+    pub integration_base_commit: Option<String>,
+    /// 세션을 시작할 때 체크아웃되어 있던 브랜치. 전달 단계에서 머지 대상이 된다.
+    #[serde(default)]
+    pub integration_base_branch: Option<String>,
+    pub tasks: Vec<CodingTask>,
+    pub next_task_index: usize,
+    pub task_reports: Vec<TaskReport>,
+    /// 중지할 때까지 쓴 에이전트 사용량. 이어서 진행하면 여기서부터 다시 센다.
+    #[serde(default)]
+    pub usage: UsageTotals,
 }
 
 #[derive(Debug, Deserialize)]
@@ -136,9 +194,13 @@ pub fn task_extraction_schema() -> serde_json::Value {
                         "dependencies": {
                             "type": "array",
                             "items": { "type": "string" }
+                        },
+                        "requirement_ids": {
+                            "type": "array",
+                            "items": { "type": "string", "pattern": "^REQ-[0-9]{3,}$" }
                         }
                     },
-                    "required": ["task_id", "title", "description", "dependencies"],
+                    "required": ["task_id", "title", "description", "dependencies", "requirement_ids"],
                     "additionalProperties": false
                 },
                 "minItems": 1
@@ -159,9 +221,24 @@ pub fn coding_task_result_schema() -> serde_json::Value {
             },
             "report": {
                 "type": "string"
+            },
+            "requirement_tests": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "requirement_id": { "type": "string" },
+                        "tests": {
+                            "type": "array",
+                            "items": { "type": "string" }
+                        }
+                    },
+                    "required": ["requirement_id", "tests"],
+                    "additionalProperties": false
+                }
             }
         },
-        "required": ["status", "report"],
+        "required": ["status", "report", "requirement_tests"],
         "additionalProperties": false
     })
 }
@@ -230,6 +307,7 @@ Rules:
 - The maximum number of tasks allowed in a single plan is 100 (i.e., "TASK-00" through "TASK-99").
 - For each task, provide the title and a comprehensive description containing ALL implementation details from the plan: file paths, new symbols, edit intent, pseudocode, acceptance criteria.
 - List direct dependency task_ids in the "dependencies" array. If a task has no dependencies, use an empty array.
+- List the spec requirement IDs (e.g., "REQ-001") the task implements in the "requirement_ids" array, exactly as they appear in the plan or spec. Every requirement ID in the plan SHOULD be covered by at least one task. Use an empty array only for tasks that implement no specific requirement (e.g., pure refactoring or scaffolding).
 - Return tasks in topological order: tasks with no dependencies first, followed by tasks whose dependencies all appear earlier in the list.
 - If the plan contains no explicit task decomposition section, treat the entire plan as a single task with task id "TASK-00".
 - Output MUST be Korean for titles and descriptions, preserving code identifiers as-is.
@@ -636,17 +714,24 @@ Assigned task:
 <<<
 Task ID: {{TASK_ID}}
 Task Title: {{TASK_TITLE}}
+Requirement IDs: {{REQUIREMENT_IDS}}
 Task Description:
 {{TASK_DESCRIPTION}}
 >>>
 
+Requirement traceability:
+- For each requirement ID above, list in "requirement_tests" the tests (test function names or test file paths) that verify it, including existing tests you relied on.
+- Use an empty "tests" array for a requirement that no automated test verifies.
+
 You MUST read following files for context before writing code:
 - Specification:
   - {{SPEC_PATH}}
 - Plan:
   - {{PLAN_PATH}}
 - Implementation reports for upstream tasks (if available):
-  - {{UPSTREAM_REPORT_PATHS}}
+{{UPSTREAM_REPORT_PATHS}}
+- Handoff documents for upstream tasks, including transitive dependencies (if available):
+{{UPSTREAM_HANDOFF_PATHS}}{{RETRY_CONTEXT}}
 
 ---
 
@@ -658,28 +743,65 @@ pub fn build_coding_task_prompt(
     spec_path: &Path,
     plan_path: &Path,
     upstream_report_paths: &[PathBuf],
+    upstream_handoff_paths: &[PathBuf],
     integration_branch: &str,
 ) -> String {
-    let upstream_section = if upstream_report_paths.is_empty() {
-        "  - N/A".to_string()
-    } else {
-        upstream_report_paths
-            .iter()
-            .map(|p| format!("  - {}", p.display()))
-            .collect::<Vec<_>>()
-            .join("\n")
-    };
-
     CODING_USER_PROMPT_TEMPLATE
         .replace("{{TASK_ID}}", &task.task_id)
         .replace("{{TASK_TITLE}}", &task.title)
+        .replace("{{REQUIREMENT_IDS}}", &format_requirement_ids(&task.requirement_ids))
         .replace("{{TASK_DESCRIPTION}}", &task.description)
         .replace("{{SPEC_PATH}}", &spec_path.display().to_string())
         .replace("{{PLAN_PATH}}", &plan_path.display().to_string())
-        .replace("{{UPSTREAM_REPORT_PATHS}}", &upstream_section)
+        .replace("{{UPSTREAM_REPORT_PATHS}}", &format_path_list(upstream_report_paths))
+        .replace("{{UPSTREAM_HANDOFF_PATHS}}", &format_path_list(upstream_handoff_paths))
+        .replace("{{RETRY_CONTEXT}}", &format_retry_context(task.retry.as_ref()))
         .replace("{{INTEGRATION_BRANCH}}", integration_branch)
 }
 
+const RETRY_CONTEXT_TEMPLATE: &str = r#"
+
+---
+
+Previous attempt:
+This task was previously reported as IMPLEMENTATION_BLOCKED and is being re-run on top of the current integration branch.
+- You MUST read the previous blocked report and resolve the blockers it describes before reporting the result.
+- Previous blocked report:
+  - {{PREVIOUS_REPORT_PATH}}
+- Additional guidance from the user (takes precedence over the previous approach):
+<<<
+{{USER_GUIDANCE}}
+>>>"#;
+
+fn format_retry_context(retry: Option<&TaskRetry>) -> String {
+    let Some(retry) = retry else {
+        return String::new();
+    };
+
+    RETRY_CONTEXT_TEMPLATE
+        .replace("{{PREVIOUS_REPORT_PATH}}", &retry.previous_report_path.display().to_string())
+        .replace("{{USER_GUIDANCE}}", retry.guidance.as_deref().unwrap_or("N/A"))
+}
+
+fn format_requirement_ids(requirement_ids: &[String]) -> String {
+    if requirement_ids.is_empty() {
+        return "N/A".to_string();
+    }
+    requirement_ids.join(", ")
+}
+
+fn format_path_list(paths: &[PathBuf]) -> String {
+    if paths.is_empty() {
+        return "  - N/A".to_string();
+    }
+
+    paths
+        .iter()
+        .map(|p| format!("  - {}", p.display()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 // ---------------------------------------------------------------------------
 // Prompts – Conflict Resolution
 // ---------------------------------------------------------------------------
@@ -769,13 +891,13 @@ pub fn build_conflict_resolution_prompt(
 
 const BUILD_TEST_REPAIR_PROMPT_TEMPLATE: &str = r#"# Build/Test Failure Resolution Prompt (commit-first, regression-aware)
 
-After rebasing onto the integration branch, the build or tests failed for task {{TASK_ID}}.
+After rebasing onto the integration branch, verification failed for task {{TASK_ID}}.
 
-Build command: {{BUILD_COMMAND}}
-Test command: {{TEST_COMMAND}}
+Verification pipeline (stages run in this order, paths relative to the worktree root):
+{{PIPELINE}}
 
-Error output:
-{{ERROR_OUTPUT}}
+Failed stages:
+{{FAILED_STAGES}}
 
 Hard requirement (do this before changing code):
 You MUST determine whether the failure is caused by (a) integration branch changes, (b) this task's changes, or (c) an interaction between them. Do NOT start by patching files directly based only on the error text.
@@ -822,8 +944,8 @@ B) Apply the smallest correct fix in this task worktree:
    - If the correct fix belongs in the integration branch (pre-existing bug), still implement the minimal fix here only if it is safe and consistent with the integration direction; otherwise report that the upstream fix is required.
 
 C) Verify:
-   1. Run `{{BUILD_COMMAND}}` and confirm success.
-   2. Run `{{TEST_COMMAND}}` and confirm all tests pass.
+   1. Run every required stage above, in order, and confirm each succeeds.
+   2. Fix failed optional stages too when the fix stays within this task's scope.
    3. If you changed behavior, add/adjust the minimal test that proves the intended behavior (only if necessary and within the task scope).
 
 Failure rule:
@@ -837,15 +959,24 @@ Output requirements:
 
 pub fn build_build_test_repair_prompt(
     task_id: &str,
-    build_command: &str,
-    test_command: &str,
-    error_output: &str,
+    pipeline: &[VerificationStage],
+    failures: &[StageFailure],
 ) -> String {
+    let pipeline_section = pipeline
+        .iter()
+        .map(|stage| format!("- {}", stage.describe()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let failures_section = failures
+        .iter()
+        .map(|failure| format!("### {}\n\n```\n{}\n```", failure.stage.describe(), failure.output))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
     BUILD_TEST_REPAIR_PROMPT_TEMPLATE
         .replace("{{TASK_ID}}", task_id)
-        .replace("{{BUILD_COMMAND}}", build_command)
-        .replace("{{TEST_COMMAND}}", test_command)
-        .replace("{{ERROR_OUTPUT}}", error_output)
+        .replace("{{PIPELINE}}", &pipeline_section)
+        .replace("{{FAILED_STAGES}}", &failures_section)
 }
 
 // ---------------------------------------------------------------------------
@@ -972,8 +1103,10 @@ You MUST read following files before starting the review:
 - Specification: {{SPEC_PATH}}
 - Implementation plan: {{PLAN_PATH}}
 - Implementation report: {{IMPLEMENTATION_REPORT_PATH}}
-- Git commit:
-  - {{GIT_COMMIT_REVISION}}
+- Handoff documents for upstream tasks (if available):
+{{UPSTREAM_HANDOFF_PATHS}}
+- Task commit range (review every commit in it, e.g. `git log -p {{GIT_COMMIT_RANGE}}`):
+  - {{GIT_COMMIT_RANGE}}
 
 You MUST read the code changes from the provided workspace files using available tools.
 
@@ -983,13 +1116,15 @@ pub fn build_initial_review_prompt(
     spec_path: &Path,
     plan_path: &Path,
     report_path: &Path,
-    git_commit_revision: &str,
+    upstream_handoff_paths: &[PathBuf],
+    commit_range: &str,
 ) -> String {
     INITIAL_REVIEW_PROMPT_TEMPLATE
         .replace("{{SPEC_PATH}}", &spec_path.display().to_string())
         .replace("{{PLAN_PATH}}", &plan_path.display().to_string())
         .replace("{{IMPLEMENTATION_REPORT_PATH}}", &report_path.display().to_string())
-        .replace("{{GIT_COMMIT_REVISION}}", git_commit_revision)
+        .replace("{{UPSTREAM_HANDOFF_PATHS}}", &format_path_list(upstream_handoff_paths))
+        .replace("{{GIT_COMMIT_RANGE}}", commit_range)
 }
 
 const FOLLOWUP_REVIEW_PROMPT_TEMPLATE: &str = r#"# Instructions for Follow-up Code Review
@@ -1000,8 +1135,10 @@ You MUST read following files before starting the review:
 - Specification: {{SPEC_PATH}}
 - Implementation plan: {{PLAN_PATH}}
 - Follow-up implementation report: {{IMPLEMENTATION_REPORT_PATH}}
-- Git commit for the follow-up changes:
-  - {{GIT_COMMIT_REVISION}}
+- Handoff documents for upstream tasks (if available):
+{{UPSTREAM_HANDOFF_PATHS}}
+- Task commit range, with the follow-up changes as its newest commits (e.g. `git log -p {{GIT_COMMIT_RANGE}}`):
+  - {{GIT_COMMIT_RANGE}}
 
 You MUST read the code changes from the provided workspace files using available tools.
 
@@ -1011,13 +1148,15 @@ pub fn build_followup_review_prompt(
     spec_path: &Path,
     plan_path: &Path,
     report_path: &Path,
-    git_commit_revision: &str,
+    upstream_handoff_paths: &[PathBuf],
+    commit_range: &str,
 ) -> String {
     FOLLOWUP_REVIEW_PROMPT_TEMPLATE
         .replace("{{SPEC_PATH}}", &spec_path.display().to_string())
         .replace("{{PLAN_PATH}}", &plan_path.display().to_string())
         .replace("{{IMPLEMENTATION_REPORT_PATH}}", &report_path.display().to_string())
-        .replace("{{GIT_COMMIT_REVISION}}", git_commit_revision)
+        .replace("{{UPSTREAM_HANDOFF_PATHS}}", &format_path_list(upstream_handoff_paths))
+        .replace("{{GIT_COMMIT_RANGE}}", commit_range)
 }
 
 // ---------------------------------------------------------------------------
@@ -1079,314 +1218,453 @@ pub fn build_coding_revision_prompt(
         .replace("{{INTEGRATION_BRANCH}}", integration_branch)
 }
 
+const COMMIT_FIX_PROMPT_TEMPLATE: &str = r#"# Commit Verification Failed
+
+After your last turn on task {{TASK_ID}} ({{TASK_TITLE}}), the task worktree has the following problems:
+
+{{PROBLEMS}}
+
+Worktree context:
+- Task Branch: {{TASK_BRANCH}}
+- Integration Branch: {{INTEGRATION_BRANCH}}
+
+Instructions:
+1. Make sure the task branch `{{TASK_BRANCH}}` is checked out in this worktree.
+2. Move any work committed elsewhere onto the task branch, and delete branches or tags you created.
+3. Commit all changes for the task on the task branch. Do not modify the integration branch.
+4. If the task genuinely needs no code changes, report `IMPLEMENTATION_BLOCKED` and explain why.
+
+Output MUST be valid JSON conforming to the provided JSON Schema."#;
+
+pub fn build_commit_fix_prompt(
+    task: &CodingTask,
+    problems: &[String],
+    task_branch: &str,
+    integration_branch: &str,
+) -> String {
+    let problem_list = problems
+        .iter()
+        .map(|problem| format!("- {}", problem))
+        .collect::<Vec<_>>()
+        .join("\n");
+    COMMIT_FIX_PROMPT_TEMPLATE
+        .replace("{{TASK_ID}}", &task.task_id)
+        .replace("{{TASK_TITLE}}", &task.title)
+        .replace("{{PROBLEMS}}", &problem_list)
+        .replace("{{TASK_BRANCH}}", task_branch)
+        .replace("{{INTEGRATION_BRANCH}}", integration_branch)
+}
+
 // ---------------------------------------------------------------------------
 // Git Operations
 // ---------------------------------------------------------------------------
 
-pub fn create_integration_branch(
+/// Bear가 관리하는 통합 브랜치 워크트리. 세션의 모든 머지와 저널 커밋은 여기서 일어난다.
+#[derive(Debug, Clone)]
+pub struct IntegrationWorktree {
+    pub branch: String,
+    pub path: PathBuf,
+}
+
+/// 현재 HEAD에서 통합 브랜치를 만들어 전용 워크트리에 체크아웃한다.
+/// 사용자의 체크아웃과 HEAD는 바뀌지 않는다.
+pub fn create_integration_worktree(
     workspace: &Path,
     session_name: &str,
-) -> Result<String, String> {
-    let branch_name = format!("bear/integration/{}-{}", session_name, Uuid::new_v4());
-
-    let output = Command::new("git")
-        .current_dir(workspace)
-        .args(["checkout", "-b", &branch_name])
-        .output()
-        .map_err(|e| format!("failed to execute git checkout -b: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("failed to create integration branch: {}", stderr.trim()));
-    }
+    policy: &CommitPolicy,
+) -> Result<IntegrationWorktree, GitError> {
+    let branch_name = policy.integration_branch_name(session_name);
+    let worktree_path = bear_worktree_path(workspace, INTEGRATION_WORKTREE_NAME_MARKER);
+
+    git::run(
+        workspace,
+        [
+            "worktree",
+            "add",
+            "-b",
+            &branch_name,
+            &worktree_path.display().to_string(),
+            "HEAD",
+        ],
+    )?;
 
-    Ok(branch_name)
+    Ok(IntegrationWorktree {
+        branch: branch_name,
+        path: worktree_path,
+    })
+}
+
+/// 이미 있는 통합 브랜치를 새 전용 워크트리에 체크아웃한다. 중지된 세션을 이어갈 때 쓴다.
+pub fn add_integration_worktree(
+    workspace: &Path,
+    integration_branch: &str,
+) -> Result<PathBuf, GitError> {
+    let worktree_path = bear_worktree_path(workspace, INTEGRATION_WORKTREE_NAME_MARKER);
+    git::run(
+        workspace,
+        ["worktree", "add", &worktree_path.display().to_string(), integration_branch],
+    )?;
+    Ok(worktree_path)
 }
 
 pub fn create_worktree(
     workspace: &Path,
     integration_branch: &str,
-) -> Result<PathBuf, String> {
+) -> Result<PathBuf, GitError> {
+    let worktree_path = bear_worktree_path(workspace, TASK_WORKTREE_NAME_MARKER);
+    git::run(
+        workspace,
+        ["worktree", "add", &worktree_path.display().to_string(), integration_branch],
+    )?;
+    Ok(worktree_path)
+}
+
+/// 워크스페이스 옆에 `<workspace>-bear-<kind>-<uuid>` 경로를 만든다.
+fn bear_worktree_path(workspace: &Path, name_marker: &str) -> PathBuf {
     let workspace_dir_name = workspace
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("workspace");
 
-    let worktree_path = workspace
+    workspace
         .parent()
         .unwrap_or(workspace)
-        .join(format!("{}-bear-worktree-{}", workspace_dir_name, Uuid::new_v4()));
-
-    let output = Command::new("git")
-        .current_dir(workspace)
-        .args([
-            "worktree",
-            "add",
-            &worktree_path.display().to_string(),
-            integration_branch,
-        ])
-        .output()
-        .map_err(|e| format!("failed to execute git worktree add: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("failed to create worktree: {}", stderr.trim()));
-    }
-
-    Ok(worktree_path)
+        .join(format!("{}{}{}", workspace_dir_name, name_marker, Uuid::new_v4()))
 }
 
 pub fn remove_worktree(
     workspace: &Path,
     worktree_path: &Path,
-) -> Result<(), String> {
-    let output = Command::new("git")
-        .current_dir(workspace)
-        .args([
-            "worktree",
-            "remove",
-            "--force",
-            &worktree_path.display().to_string(),
-        ])
-        .output()
-        .map_err(|e| format!("failed to execute git worktree remove: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("failed to remove worktree: {}", stderr.trim()));
-    }
-
+) -> Result<(), GitError> {
+    git::run(
+        workspace,
+        ["worktree", "remove", "--force", &worktree_path.display().to_string()],
+    )?;
     Ok(())
 }
 
 pub fn create_task_branch(
     workspace: &Path,
     integration_branch: &str,
+    session_name: &str,
     task_id: &str,
-) -> Result<String, String> {
-    let branch_name = format!("bear/task/{}-{}", task_id, Uuid::new_v4());
-
-    let output = Command::new("git")
-        .current_dir(workspace)
-        .args(["branch", &branch_name, integration_branch])
-        .output()
-        .map_err(|e| format!("failed to execute git branch: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("failed to create task branch: {}", stderr.trim()));
-    }
-
+    policy: &CommitPolicy,
+) -> Result<String, GitError> {
+    let branch_name = policy.task_branch_name(session_name, task_id);
+    git::run(workspace, ["branch", &branch_name, integration_branch])?;
     Ok(branch_name)
 }
 
+/// 충돌 여부는 stderr 문구가 아니라 리베이스가 멈춘 저장소 상태와 unmerged 파일로 판단한다.
 pub fn rebase_onto_integration(
     worktree_path: &Path,
     integration_branch: &str,
-) -> Result<RebaseOutcome, String> {
-    let output = Command::new("git")
-        .current_dir(worktree_path)
-        .args(["rebase", integration_branch])
-        .output()
-        .map_err(|e| format!("failed to execute git rebase: {}", e))?;
-
-    if output.status.success() {
-        return Ok(RebaseOutcome::Success);
-    }
+) -> Result<RebaseOutcome, GitError> {
+    let error = match git::run(worktree_path, ["rebase", integration_branch]) {
+        Ok(_) => return Ok(RebaseOutcome::Success),
+        Err(error) => error,
+    };
 
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    if stderr.contains("CONFLICT") || stderr.contains("could not apply") {
+    if git::operation_in_progress(worktree_path)? == Some(git::Operation::Rebase) {
         let conflicted_files = list_conflicted_files(worktree_path)?;
         return Ok(RebaseOutcome::Conflict { conflicted_files });
     }
 
-    Err(format!("git rebase failed: {}", stderr.trim()))
+    Err(error)
 }
 
 pub fn list_conflicted_files(
     worktree_path: &Path,
-) -> Result<Vec<String>, String> {
-    let output = Command::new("git")
-        .current_dir(worktree_path)
-        .args(["diff", "--name-only", "--diff-filter=U"])
-        .output()
-        .map_err(|e| format!("failed to execute git diff: {}", e))?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let files: Vec<String> = stdout
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(String::from)
-        .collect();
-
-    Ok(files)
+) -> Result<Vec<String>, GitError> {
+    git::unmerged_files(worktree_path)
 }
 
-pub fn abort_rebase(worktree_path: &Path) -> Result<(), String> {
-    let output = Command::new("git")
-        .current_dir(worktree_path)
-        .args(["rebase", "--abort"])
-        .output()
-        .map_err(|e| format!("failed to execute git rebase --abort: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("failed to abort rebase: {}", stderr.trim()));
-    }
+pub fn abort_rebase(worktree_path: &Path) -> Result<(), GitError> {
+    git::run(worktree_path, ["rebase", "--abort"])?;
+    Ok(())
+}
 
+pub fn fast_forward_merge_task_branch(
+    workspace: &Path,
+    task_branch: &str,
+) -> Result<(), GitError> {
+    git::run(workspace, ["merge", "--ff-only", task_branch])?;
     Ok(())
 }
 
-pub fn detect_build_commands(worktree_path: &Path) -> Option<BuildTestCommands> {
-    let makefile_path = worktree_path.join("Makefile");
-    if makefile_path.exists()
-        && let Ok(content) = fs::read_to_string(&makefile_path)
-    {
-        let has_build = content.lines().any(|line| line.starts_with("build:"));
-        let has_test = content.lines().any(|line| line.starts_with("test:"));
-        if has_build && has_test {
-            return Some(BuildTestCommands {
-                build: "make build".to_string(),
-                test: "make test".to_string(),
-            });
-        }
-    }
+pub fn delete_branch(
+    workspace: &Path,
+    branch_name: &str,
+) -> Result<(), GitError> {
+    git::run(workspace, ["branch", "-D", branch_name])?;
+    Ok(())
+}
 
-    if worktree_path.join("Cargo.toml").exists() {
-        return Some(BuildTestCommands {
-            build: "cargo build".to_string(),
-            test: "cargo test".to_string(),
-        });
-    }
+pub fn get_latest_commit_revision(worktree_path: &Path) -> Result<String, GitError> {
+    git::run(worktree_path, ["rev-parse", "HEAD"])
+}
 
-    if let Some(commands) = detect_npm_commands(worktree_path) {
-        return Some(commands);
-    }
+/// 태스크 브랜치의 커밋 전체를 `<분기점>..<HEAD>` 해시 범위로 돌려준다.
+pub fn task_commit_range(worktree_path: &Path, integration_branch: &str) -> Result<String, GitError> {
+    let base_revision = git::run(worktree_path, ["merge-base", integration_branch, "HEAD"])?;
+    let head_revision = get_latest_commit_revision(worktree_path)?;
+    Ok(format!("{}..{}", base_revision, head_revision))
+}
 
-    if worktree_path.join("go.mod").exists() {
-        return Some(BuildTestCommands {
-            build: "go build ./...".to_string(),
-            test: "go test ./...".to_string(),
-        });
-    }
+pub fn list_commits_in_range(
+    workspace: &Path,
+    base_revision: &str,
+    branch: &str,
+) -> Result<String, GitError> {
+    let range = format!("{}..{}", base_revision, branch);
+    git::run(workspace, ["log", "--oneline", "--no-decorate", &range])
+}
 
-    None
+pub fn list_changed_files_in_range(
+    workspace: &Path,
+    base_revision: &str,
+    branch: &str,
+) -> Result<Vec<String>, GitError> {
+    let range = format!("{}..{}", base_revision, branch);
+    let stdout = git::run(workspace, ["diff", "--name-only", &range])?;
+    Ok(stdout
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect())
 }
 
-fn detect_npm_commands(worktree_path: &Path) -> Option<BuildTestCommands> {
-    let package_json_path = worktree_path.join("package.json");
-    let content = fs::read_to_string(&package_json_path).ok()?;
-    let parsed: serde_json::Value = serde_json::from_str(&content).ok()?;
-    let scripts = parsed.get("scripts")?;
+pub fn diff_stat_in_range(
+    workspace: &Path,
+    base_revision: &str,
+    branch: &str,
+) -> Result<String, GitError> {
+    let range = format!("{}..{}", base_revision, branch);
+    git::run(workspace, ["diff", "--stat", &range])
+}
 
-    let has_build = scripts.get("build").is_some();
-    let has_test = scripts.get("test").is_some();
+// ---------------------------------------------------------------------------
+// Agent Commit Verification
+// ---------------------------------------------------------------------------
 
-    if has_build && has_test {
-        Some(BuildTestCommands {
-            build: "npm run build".to_string(),
-            test: "npm test".to_string(),
-        })
-    } else {
-        None
-    }
+/// 에이전트 턴이 끝난 태스크 워크트리의 커밋 상태.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskCommitCheck {
+    /// 커밋되지 않은 변경이 있는 파일. 워크트리 안 세션 저널 사본은 제외한다.
+    pub uncommitted_files: Vec<String>,
+    /// 통합 브랜치에 없는 태스크 브랜치 커밋 수.
+    pub new_commit_count: usize,
+    /// HEAD가 태스크 브랜치가 아닐 때 체크아웃된 브랜치. detached HEAD면 `HEAD`.
+    pub unexpected_head: Option<String>,
+    /// 워크트리를 만든 뒤 새로 생기거나 움직인, 태스크 브랜치가 아닌 ref.
+    pub unexpected_refs: Vec<String>,
 }
 
-pub fn run_build_and_test(
-    worktree_path: &Path,
-    commands: &BuildTestCommands,
-) -> Result<BuildTestOutcome, String> {
-    let build_outcome = run_shell_command(worktree_path, &commands.build)?;
-    if !build_outcome.success {
-        return Ok(BuildTestOutcome::BuildFailed {
-            output: build_outcome.combined_output,
-        });
-    }
-
-    let test_outcome = run_shell_command(worktree_path, &commands.test)?;
-    if !test_outcome.success {
-        return Ok(BuildTestOutcome::TestFailed {
-            output: test_outcome.combined_output,
-        });
+impl TaskCommitCheck {
+    /// 코딩 에이전트에게 되돌려 보낼 문제. 비어 있으면 리뷰로 넘어갈 수 있다.
+    pub fn problems(&self, task_branch: &str, integration_branch: &str) -> Vec<String> {
+        let mut problems = Vec::new();
+        if let Some(head) = &self.unexpected_head {
+            problems.push(format!(
+                "`{}` is checked out instead of the task branch `{}`.",
+                head, task_branch,
+            ));
+        }
+        if !self.unexpected_refs.is_empty() {
+            problems.push(format!(
+                "Refs other than the task branch were created or moved: {}.",
+                self.unexpected_refs.join(", "),
+            ));
+        }
+        if !self.uncommitted_files.is_empty() {
+            problems.push(format!(
+                "Changes are not committed: {}.",
+                self.uncommitted_files.join(", "),
+            ));
+        }
+        if self.new_commit_count == 0 {
+            problems.push(format!(
+                "The task branch `{}` has no commits beyond `{}`.",
+                task_branch, integration_branch,
+            ));
+        }
+        problems
     }
-
-    Ok(BuildTestOutcome::Success)
 }
 
-struct ShellCommandResult {
-    success: bool,
-    combined_output: String,
+pub fn snapshot_refs(workspace: &Path) -> Result<Vec<String>, GitError> {
+    let stdout = git::run(
+        workspace,
+        ["for-each-ref", "--format=%(refname) %(objectname)", "refs/heads", "refs/tags"],
+    )?;
+    Ok(stdout.lines().map(String::from).collect())
 }
 
-fn run_shell_command(
-    working_dir: &Path,
-    command: &str,
-) -> Result<ShellCommandResult, String> {
-    let output = Command::new("timeout")
-        .current_dir(working_dir)
-        .args(["--signal=TERM", "--kill-after=15s", "180s", "sh", "-c", command])
-        .output()
-        .map_err(|e| format!("failed to execute '{}': {}", command, e))?;
+/// 태스크 브랜치에 새 커밋이 있는지, 커밋되지 않은 변경이나 예상 밖의 ref가 남았는지 확인한다.
+/// `journal_dir`는 워크트리 안의 세션 저널 사본으로, 커밋 대상이 아니므로 검사에서 뺀다.
+pub fn check_task_commits(
+    worktree: &TaskWorktreeInfo,
+    integration_branch: &str,
+    journal_dir: &Path,
+) -> Result<TaskCommitCheck, GitError> {
+    let worktree_path = &worktree.worktree_path;
+    let uncommitted_files = list_uncommitted_files(worktree_path, journal_dir)?;
+
+    let task_range = format!("{}..HEAD", integration_branch);
+    let new_commit_count = git::run(worktree_path, ["rev-list", "--count", &task_range])?
+        .parse()
+        .unwrap_or(0);
+
+    let head_branch = git::run(worktree_path, ["symbolic-ref", "--short", "-q", "HEAD"])
+        .ok()
+        .filter(|branch| !branch.is_empty());
+    let unexpected_head = match head_branch {
+        Some(branch) if branch == worktree.task_branch => None,
+        Some(branch) => Some(branch),
+        None => Some("HEAD".to_string()),
+    };
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    let combined_output = format!("--- stdout ---\n{}\n--- stderr ---\n{}", stdout, stderr);
+    let expected_refs = [
+        format!("refs/heads/{}", worktree.task_branch),
+        format!("refs/heads/{}", JOURNAL_BRANCH),
+    ];
+    let unexpected_refs = snapshot_refs(worktree_path)?
+        .into_iter()
+        .filter(|entry| !worktree.ref_snapshot.contains(entry))
+        .filter_map(|entry| entry.split_once(' ').map(|(name, _)| name.to_string()))
+        .filter(|name| !expected_refs.contains(name))
+        .collect();
 
-    Ok(ShellCommandResult {
-        success: output.status.success(),
-        combined_output,
+    Ok(TaskCommitCheck {
+        uncommitted_files,
+        new_commit_count,
+        unexpected_head,
+        unexpected_refs,
     })
 }
 
-pub fn fast_forward_merge_task_branch(
-    workspace: &Path,
-    task_branch: &str,
-) -> Result<(), String> {
-    let merge_output = Command::new("git")
-        .current_dir(workspace)
-        .args(["merge", "--ff-only", task_branch])
-        .output()
-        .map_err(|e| format!("failed to execute git merge --ff-only: {}", e))?;
-
-    if !merge_output.status.success() {
-        let stderr = String::from_utf8_lossy(&merge_output.stderr);
-        return Err(format!("failed to fast-forward merge: {}", stderr.trim()));
-    }
+/// 에이전트가 커밋하지 않고 남긴 변경을 커밋하고 커밋한 파일 수를 돌려준다.
+/// 워크트리를 지우면 사라질 작업을 남기기 위한 것이다.
+pub fn commit_remaining_changes(
+    worktree_path: &Path,
+    journal_dir: &Path,
+    commit_message: &str,
+    policy: &CommitPolicy,
+) -> Result<usize, GitError> {
+    let uncommitted_files = list_uncommitted_files(worktree_path, journal_dir)?;
+    if uncommitted_files.is_empty() {
+        return Ok(0);
+    }
+    let add_arguments = ["add".to_string(), "-A".to_string()]
+        .into_iter()
+        .chain(pathspec_without_journal(worktree_path, journal_dir));
+    git::run(worktree_path, add_arguments)?;
+    git::run(worktree_path, policy.commit_arguments(&["-m", commit_message]))?;
+    Ok(uncommitted_files.len())
+}
 
-    Ok(())
+fn list_uncommitted_files(worktree_path: &Path, journal_dir: &Path) -> Result<Vec<String>, GitError> {
+    let status_arguments = ["status", "--porcelain", "--untracked-files=all"]
+        .into_iter()
+        .map(String::from)
+        .chain(pathspec_without_journal(worktree_path, journal_dir));
+    let stdout = git::run(worktree_path, status_arguments)?;
+    Ok(stdout
+        .lines()
+        .filter_map(|line| line.get(3..))
+        .map(String::from)
+        .collect())
 }
 
-pub fn delete_branch(
-    workspace: &Path,
-    branch_name: &str,
-) -> Result<(), String> {
-    let output = Command::new("git")
-        .current_dir(workspace)
-        .args(["branch", "-D", branch_name])
-        .output()
-        .map_err(|e| format!("failed to execute git branch -D: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("failed to delete branch: {}", stderr.trim()));
+fn pathspec_without_journal(worktree_path: &Path, journal_dir: &Path) -> Vec<String> {
+    let mut pathspec = vec!["--".to_string(), ".".to_string()];
+    if let Ok(relative) = journal_dir.strip_prefix(worktree_path) {
+        pathspec.push(format!(":(exclude){}", relative.display()));
     }
+    pathspec
+}
 
-    Ok(())
+// ---------------------------------------------------------------------------
+// Task Commit Squashing
+// ---------------------------------------------------------------------------
+
+const REPORT_SUMMARY_MAX_LINES: usize = 6;
+
+/// 합친 태스크 커밋의 메시지 본문. 제목은 태스크 제목, 본문은 보고서의 첫 문단과 요구사항/태스크 ID다.
+pub fn build_squash_summary(task: &CodingTask, report: &str, requirement_ids: &[String]) -> String {
+    let mut summary = task.title.clone();
+    let report_summary = summarize_report(report);
+    if !report_summary.is_empty() {
+        summary.push_str("\n\n");
+        summary.push_str(&report_summary);
+    }
+    summary.push_str("\n\n");
+    if !requirement_ids.is_empty() {
+        summary.push_str(&format!("Requirements: {}\n", requirement_ids.join(", ")));
+    }
+    summary.push_str(&format!("Tasks: {}", task.task_id));
+    summary
 }
 
-pub fn get_latest_commit_revision(worktree_path: &Path) -> Result<String, String> {
-    let output = Command::new("git")
-        .current_dir(worktree_path)
-        .args(["rev-parse", "HEAD"])
-        .output()
-        .map_err(|e| format!("failed to execute git rev-parse: {}", e))?;
+/// 보고서에서 제목, 상태 줄, 구분선을 건너뛴 첫 문단을 최대 몇 줄까지 가져온다.
+fn summarize_report(report: &str) -> String {
+    report
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| {
+            line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("---")
+                || line.starts_with("IMPLEMENTATION_")
+        })
+        .take_while(|line| !line.is_empty())
+        .take(REPORT_SUMMARY_MAX_LINES)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("failed to get latest commit: {}", stderr.trim()));
+/// 통합 브랜치 뒤의 태스크 커밋을 하나로 합치고 합친 커밋 수를 돌려준다.
+/// 작성자와 작성 시각은 첫 커밋을 따르고, 다른 작성자는 `Co-authored-by:` 트레일러로 남긴다.
+/// 커밋에 실패하면 브랜치를 원래 커밋으로 되돌린다.
+pub fn squash_task_commits(
+    worktree_path: &Path,
+    integration_branch: &str,
+    commit_message: &str,
+    policy: &CommitPolicy,
+) -> Result<usize, GitError> {
+    let task_range = format!("{}..HEAD", integration_branch);
+    let author_log = git::run(
+        worktree_path,
+        ["log", "--reverse", "--format=%an <%ae>%x09%aI", &task_range],
+    )?;
+    let authors: Vec<(&str, &str)> = author_log
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .collect();
+    let Some(&(first_author, first_date)) = authors.first() else {
+        return Ok(0);
+    };
+
+    let mut extra_arguments = vec!["--author", first_author, "--date", first_date, "-m", commit_message];
+    let mut co_author_trailers: Vec<String> = Vec::new();
+    for &(author, _) in &authors {
+        let trailer = format!("Co-authored-by: {}", author);
+        if author != first_author && !co_author_trailers.contains(&trailer) {
+            co_author_trailers.push(trailer);
+        }
+    }
+    for trailer in &co_author_trailers {
+        extra_arguments.extend(["--trailer", trailer.as_str()]);
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    let original_head = get_latest_commit_revision(worktree_path)?;
+    let base_revision = git::run(worktree_path, ["merge-base", integration_branch, "HEAD"])?;
+    git::run(worktree_path, ["reset", "--soft", &base_revision])?;
+    if let Err(err) = git::run(worktree_path, policy.commit_arguments(&extra_arguments)) {
+        git::run(worktree_path, ["reset", "--soft", &original_head])?;
+        return Err(err);
+    }
+    Ok(authors.len())
 }
 
 // ---------------------------------------------------------------------------
@@ -1427,6 +1705,22 @@ pub fn save_task_report(
     Ok(file_path)
 }
 
+pub fn save_coding_state(dir: &Path, snapshot: &CodingStateSnapshot) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+
+    let file_path = dir.join(CODING_STATE_FILE_NAME);
+    let content = serde_json::to_string_pretty(snapshot)?;
+    fs::write(&file_path, content)?;
+
+    Ok(file_path)
+}
+
+pub fn load_coding_state(dir: &Path) -> io::Result<CodingStateSnapshot> {
+    let content = fs::read_to_string(dir.join(CODING_STATE_FILE_NAME))?;
+    let snapshot = serde_json::from_str(&content)?;
+    Ok(snapshot)
+}
+
 pub fn collect_upstream_report_paths(
     task: &CodingTask,
     completed_reports: &[TaskReport],
@@ -1434,8 +1728,10 @@ pub fn collect_upstream_report_paths(
     task.dependencies
         .iter()
         .filter_map(|dep_id| {
+            // 재실행한 태스크는 보고서가 여러 개이며, 가장 최근 보고서가 머지된 결과를 반영한다.
             completed_reports
                 .iter()
+                .rev()
                 .find(|r| &r.task_id == dep_id)
                 .map(|r| r.report_file_path.clone())
         })
@@ -1446,29 +1742,22 @@ pub fn commit_file_in_workspace(
     workspace: &Path,
     file_path: &Path,
     commit_message: &str,
-) -> Result<(), String> {
-    let add_output = Command::new("git")
-        .current_dir(workspace)
-        .args(["add", &file_path.display().to_string()])
-        .output()
-        .map_err(|e| format!("failed to git add: {}", e))?;
-
-    if !add_output.status.success() {
-        let stderr = String::from_utf8_lossy(&add_output.stderr);
-        return Err(format!("failed to git add: {}", stderr.trim()));
-    }
-
-    let commit_output = Command::new("git")
-        .current_dir(workspace)
-        .args(["commit", "-m", commit_message])
-        .output()
-        .map_err(|e| format!("failed to git commit: {}", e))?;
-
-    if !commit_output.status.success() {
-        let stderr = String::from_utf8_lossy(&commit_output.stderr);
-        return Err(format!("failed to git commit: {}", stderr.trim()));
-    }
+    policy: &CommitPolicy,
+) -> Result<(), GitError> {
+    commit_files_in_workspace(workspace, &[file_path.to_path_buf()], commit_message, policy)
+}
 
+/// 파일을 스테이징하고 정책의 작성자, 서명, sign-off로 커밋한다. 메시지는 이미 정책으로 완성된 것이어야 한다.
+pub fn commit_files_in_workspace(
+    workspace: &Path,
+    file_paths: &[PathBuf],
+    commit_message: &str,
+    policy: &CommitPolicy,
+) -> Result<(), GitError> {
+    let add_args = std::iter::once(Path::new("add").as_os_str())
+        .chain(file_paths.iter().map(|path| path.as_os_str()));
+    git::run(workspace, add_args)?;
+    git::run(workspace, policy.commit_arguments(&["-m", commit_message]))?;
     Ok(())
 }
 
@@ -1478,6 +1767,7 @@ pub fn save_and_commit_task_report_in_worktree(
     session_name: &str,
     task_id: &str,
     report: &str,
+    policy: &CommitPolicy,
 ) -> Result<PathBuf, String> {
     let report_dir = worktree_path
         .join(".bear")
@@ -1490,29 +1780,24 @@ pub fn save_and_commit_task_report_in_worktree(
     fs::write(&file_path, report)
         .map_err(|e| format!("failed to write report file: {}", e))?;
 
-    let add_output = Command::new("git")
-        .current_dir(worktree_path)
-        .args(["add", &file_path.display().to_string()])
-        .output()
+    git::run(worktree_path, ["add", &file_path.display().to_string()])
         .map_err(|e| format!("failed to git add report: {}", e))?;
 
-    if !add_output.status.success() {
-        let stderr = String::from_utf8_lossy(&add_output.stderr);
-        return Err(format!("failed to git add report: {}", stderr.trim()));
-    }
-
-    // Amend the previous commit (code changes) to include the task report,
-    // so they are recorded as a single commit.
-    let commit_output = Command::new("git")
-        .current_dir(worktree_path)
-        .args(["commit", "--amend", "--no-edit"])
-        .output()
-        .map_err(|e| format!("failed to git commit report: {}", e))?;
-
-    if !commit_output.status.success() {
-        let stderr = String::from_utf8_lossy(&commit_output.stderr);
-        return Err(format!("failed to commit report: {}", stderr.trim()));
-    }
+    let commit_arguments = match policy.report_commit {
+        // Amend the previous commit (code changes) to include the task report,
+        // so they are recorded as a single commit.
+        ReportCommitMode::Amend => policy.commit_arguments(&["--amend", "--no-edit"]),
+        ReportCommitMode::Separate => {
+            let context = CommitContext {
+                session: session_name,
+                task_id: Some(task_id),
+            };
+            let message = policy.render_task_message(&format!("Add implementation report for {}", task_id), context);
+            policy.commit_arguments(&["-m", &message])
+        }
+    };
+    git::run(worktree_path, commit_arguments)
+        .map_err(|e| format!("failed to commit report: {}", e))?;
 
     Ok(file_path)
 }
@@ -1524,6 +1809,9 @@ pub fn save_and_commit_task_report_in_worktree(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::process::Command;
+    use crate::workflow::commit_policy::CommitIdentity;
+    use crate::workflow::verification_pipeline::build_and_test_stages;
     use tempfile::TempDir;
 
     #[test]
@@ -1602,6 +1890,28 @@ mod tests {
         let result: CodingTaskResult = serde_json::from_value(json).unwrap();
 
         assert_eq!(result.status, CodingTaskStatus::ImplementationBlocked);
+        assert!(result.requirement_tests.is_empty());
+    }
+
+    #[test]
+    fn deserialize_coding_task_result_with_requirement_tests() {
+        let json = serde_json::json!({
+            "status": "IMPLEMENTATION_SUCCESS",
+            "report": "구현 완료",
+            "requirement_tests": [
+                { "requirement_id": "REQ-001", "tests": ["tests/login.rs::login_succeeds"] }
+            ]
+        });
+
+        let result: CodingTaskResult = serde_json::from_value(json).unwrap();
+
+        assert_eq!(
+            result.requirement_tests,
+            vec![RequirementTests {
+                requirement_id: "REQ-001".to_string(),
+                tests: vec!["tests/login.rs::login_succeeds".to_string()],
+            }]
+        );
     }
 
     #[test]
@@ -1620,11 +1930,16 @@ mod tests {
             title: "기본 타입 정의".to_string(),
             description: "핵심 타입을 정의합니다.".to_string(),
             dependencies: vec!["TASK-01".to_string()],
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: vec!["REQ-001".to_string(), "REQ-003".to_string()],
         };
 
         let spec_path = Path::new("/workspace/.bear/20260215/session/spec.md");
         let plan_path = Path::new("/workspace/.bear/20260215/session/plan.md");
         let upstream_paths = vec![PathBuf::from("/workspace/.bear/20260215/session/TASK-01.md")];
+        let handoff_paths =
+            vec![PathBuf::from("/workspace/.bear/20260215/session/HANDOFF-TASK-01.md")];
 
         let integration_branch = "bear/integration/test-session-abc123";
         let prompt = build_coding_task_prompt(
@@ -1632,6 +1947,7 @@ mod tests {
             spec_path,
             plan_path,
             &upstream_paths,
+            &handoff_paths,
             integration_branch,
         );
 
@@ -1641,6 +1957,8 @@ mod tests {
         assert!(prompt.contains(&spec_path.display().to_string()));
         assert!(prompt.contains(&plan_path.display().to_string()));
         assert!(prompt.contains("TASK-01.md"));
+        assert!(prompt.contains("HANDOFF-TASK-01.md"));
+        assert!(prompt.contains("Requirement IDs: REQ-001, REQ-003"));
         assert!(prompt.contains(integration_branch));
     }
 
@@ -1651,14 +1969,48 @@ mod tests {
             title: "독립 작업".to_string(),
             description: "의존성 없는 작업".to_string(),
             dependencies: vec![],
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: Vec::new(),
         };
 
         let spec_path = Path::new("/workspace/.bear/spec.md");
         let plan_path = Path::new("/workspace/.bear/plan.md");
         let prompt =
-            build_coding_task_prompt(&task, spec_path, plan_path, &[], "bear/integration/test");
+            build_coding_task_prompt(&task, spec_path, plan_path, &[], &[], "bear/integration/test");
 
         assert!(prompt.contains("N/A"));
+        assert!(!prompt.contains("Previous attempt"));
+    }
+
+    #[test]
+    fn build_coding_task_prompt_includes_retry_context() {
+        let task = CodingTask {
+            task_id: "TASK-01".to_string(),
+            title: "재실행 작업".to_string(),
+            description: "차단되었던 작업".to_string(),
+            dependencies: vec![],
+            kind: CodingTaskKind::Implementation,
+            retry: Some(TaskRetry {
+                previous_report_path: PathBuf::from("/ws/.bear/d/s/TASK-01-blocked-1.md"),
+                guidance: Some("외부 API 대신 목 서버를 사용하세요.".to_string()),
+            }),
+            requirement_ids: Vec::new(),
+        };
+
+        let prompt = build_coding_task_prompt(
+            &task,
+            Path::new("/ws/spec.md"),
+            Path::new("/ws/plan.md"),
+            &[],
+            &[],
+            "bear/integration/test",
+        );
+
+        assert!(prompt.contains("Previous attempt"));
+        assert!(prompt.contains("/ws/.bear/d/s/TASK-01-blocked-1.md"));
+        assert!(prompt.contains("외부 API 대신 목 서버를 사용하세요."));
+        assert!(prompt.ends_with("Integration Branch: bear/integration/test"));
     }
 
     #[test]
@@ -1675,6 +2027,48 @@ mod tests {
         assert_eq!(content, report_content);
     }
 
+    #[test]
+    fn save_and_load_coding_state() {
+        let temp_dir = TempDir::new().unwrap();
+        let snapshot = CodingStateSnapshot {
+            integration_branch: "bear/integration/test".to_string(),
+            integration_base_commit: Some("abc123".to_string()),
+            integration_base_branch: Some("main".to_string()),
+            tasks: vec![CodingTask {
+                task_id: "TASK-00".to_string(),
+                title: "작업".to_string(),
+                description: "설명".to_string(),
+                dependencies: vec![],
+                kind: CodingTaskKind::Implementation,
+                retry: None,
+                requirement_ids: Vec::new(),
+            }],
+            next_task_index: 1,
+            task_reports: vec![TaskReport {
+                task_id: "TASK-00".to_string(),
+                status: CodingTaskStatus::ImplementationBlocked,
+                report: "차단됨".to_string(),
+                report_file_path: PathBuf::from("/tmp/TASK-00.md"),
+                trace: TaskTrace::default(),
+            }],
+            usage: UsageTotals { query_count: 3, total_cost_usd: 0.25, agent_duration_ms: 900 },
+        };
+
+        let path = save_coding_state(temp_dir.path(), &snapshot).unwrap();
+        assert_eq!(path, temp_dir.path().join("coding-state.json"));
+
+        let loaded = load_coding_state(temp_dir.path()).unwrap();
+        assert_eq!(loaded.integration_branch, "bear/integration/test");
+        assert_eq!(loaded.integration_base_commit.as_deref(), Some("abc123"));
+        assert_eq!(loaded.tasks[0].task_id, "TASK-00");
+        assert_eq!(loaded.next_task_index, 1);
+        assert_eq!(loaded.usage, snapshot.usage);
+        assert_eq!(
+            loaded.task_reports[0].status,
+            CodingTaskStatus::ImplementationBlocked
+        );
+    }
+
     #[test]
     fn collect_upstream_report_paths_with_dependencies() {
         let task = CodingTask {
@@ -1682,6 +2076,9 @@ mod tests {
             title: "후속 작업".to_string(),
             description: "TASK-00, TASK-01에 의존".to_string(),
             dependencies: vec!["TASK-00".to_string(), "TASK-01".to_string()],
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: Vec::new(),
         };
 
         let reports = vec![
@@ -1690,12 +2087,14 @@ mod tests {
                 status: CodingTaskStatus::ImplementationSuccess,
                 report: "TASK-00 완료".to_string(),
                 report_file_path: PathBuf::from("/tmp/TASK-00.md"),
+                trace: TaskTrace::default(),
             },
             TaskReport {
                 task_id: "TASK-01".to_string(),
                 status: CodingTaskStatus::ImplementationSuccess,
                 report: "TASK-01 완료".to_string(),
                 report_file_path: PathBuf::from("/tmp/TASK-01.md"),
+                trace: TaskTrace::default(),
             },
         ];
 
@@ -1713,6 +2112,9 @@ mod tests {
             title: "독립 작업".to_string(),
             description: "의존성 없음".to_string(),
             dependencies: vec![],
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: Vec::new(),
         };
 
         let paths = collect_upstream_report_paths(&task, &[]);
@@ -1776,8 +2178,9 @@ mod tests {
         init_git_repo(workspace);
         make_commit(workspace, "init.txt", "init", "initial commit");
 
-        let integration = create_integration_branch(workspace, "test-session").unwrap();
-        let task_branch = create_task_branch(workspace, &integration, "TASK-00").unwrap();
+        let integration = create_integration_worktree(workspace, "test-session", &CommitPolicy::default()).unwrap();
+        let task_branch =
+            create_task_branch(workspace, &integration.branch, "test", "TASK-00", &CommitPolicy::default()).unwrap();
 
         assert!(task_branch.starts_with("bear/task/TASK-00-"));
 
@@ -1788,6 +2191,32 @@ mod tests {
             .unwrap();
         let stdout = String::from_utf8_lossy(&output.stdout);
         assert!(!stdout.trim().is_empty());
+
+        remove_worktree(workspace, &integration.path).unwrap();
+    }
+
+    #[test]
+    fn create_integration_worktree_leaves_workspace_checkout_untouched() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path();
+        init_git_repo(workspace);
+        make_commit(workspace, "init.txt", "init", "initial commit");
+        let head_before = get_latest_commit_revision(workspace).unwrap();
+
+        let integration = create_integration_worktree(workspace, "test-session", &CommitPolicy::default()).unwrap();
+        make_commit(&integration.path, "feature.txt", "feature", "feature commit");
+
+        assert!(integration.branch.starts_with("bear/integration/test-session-"));
+        assert_eq!(get_latest_commit_revision(workspace).unwrap(), head_before);
+        assert!(!workspace.join("feature.txt").exists());
+        let worktree_head = Command::new("git")
+            .current_dir(&integration.path)
+            .args(["rev-parse", "--abbrev-ref", "HEAD"])
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&worktree_head.stdout).trim(), integration.branch);
+
+        remove_worktree(workspace, &integration.path).unwrap();
     }
 
     #[test]
@@ -1797,16 +2226,18 @@ mod tests {
         init_git_repo(workspace);
         make_commit(workspace, "init.txt", "init", "initial commit");
 
-        let integration = create_integration_branch(workspace, "test").unwrap();
-        let task_branch = create_task_branch(workspace, &integration, "TASK-00").unwrap();
+        let integration = create_integration_worktree(workspace, "test", &CommitPolicy::default()).unwrap();
+        let task_branch =
+            create_task_branch(workspace, &integration.branch, "test", "TASK-00", &CommitPolicy::default()).unwrap();
         let worktree_path = create_worktree(workspace, &task_branch).unwrap();
         make_commit(&worktree_path, "task.txt", "task content", "task commit");
 
-        let result = rebase_onto_integration(&worktree_path, &integration).unwrap();
+        let result = rebase_onto_integration(&worktree_path, &integration.branch).unwrap();
 
         assert!(matches!(result, RebaseOutcome::Success));
 
         remove_worktree(workspace, &worktree_path).unwrap();
+        remove_worktree(workspace, &integration.path).unwrap();
     }
 
     #[test]
@@ -1816,27 +2247,18 @@ mod tests {
         init_git_repo(workspace);
         make_commit(workspace, "shared.txt", "original", "initial commit");
 
-        let integration = create_integration_branch(workspace, "test").unwrap();
-        let task_branch = create_task_branch(workspace, &integration, "TASK-00").unwrap();
+        let integration = create_integration_worktree(workspace, "test", &CommitPolicy::default()).unwrap();
+        let task_branch =
+            create_task_branch(workspace, &integration.branch, "test", "TASK-00", &CommitPolicy::default()).unwrap();
         let worktree_path = create_worktree(workspace, &task_branch).unwrap();
 
-        // 통합 브랜치에서 같은 파일 수정 (메인 워크스페이스에서 체크아웃해서 커밋)
-        Command::new("git")
-            .current_dir(workspace)
-            .args(["checkout", &integration])
-            .output()
-            .unwrap();
-        make_commit(workspace, "shared.txt", "integration change", "integration commit");
-        Command::new("git")
-            .current_dir(workspace)
-            .args(["checkout", "main"])
-            .output()
-            .unwrap();
+        // 통합 브랜치에서 같은 파일 수정 (통합 워크트리에서 커밋)
+        make_commit(&integration.path, "shared.txt", "integration change", "integration commit");
 
         // 태스크 브랜치에서 같은 파일을 다르게 수정
         make_commit(&worktree_path, "shared.txt", "task change", "task commit");
 
-        let result = rebase_onto_integration(&worktree_path, &integration).unwrap();
+        let result = rebase_onto_integration(&worktree_path, &integration.branch).unwrap();
 
         assert!(matches!(result, RebaseOutcome::Conflict { .. }));
         if let RebaseOutcome::Conflict { conflicted_files } = result {
@@ -1845,6 +2267,7 @@ mod tests {
 
         abort_rebase(&worktree_path).unwrap();
         remove_worktree(workspace, &worktree_path).unwrap();
+        remove_worktree(workspace, &integration.path).unwrap();
     }
 
     #[test]
@@ -1854,24 +2277,15 @@ mod tests {
         init_git_repo(workspace);
         make_commit(workspace, "shared.txt", "original", "initial commit");
 
-        let integration = create_integration_branch(workspace, "test").unwrap();
-        let task_branch = create_task_branch(workspace, &integration, "TASK-00").unwrap();
+        let integration = create_integration_worktree(workspace, "test", &CommitPolicy::default()).unwrap();
+        let task_branch =
+            create_task_branch(workspace, &integration.branch, "test", "TASK-00", &CommitPolicy::default()).unwrap();
         let worktree_path = create_worktree(workspace, &task_branch).unwrap();
 
-        Command::new("git")
-            .current_dir(workspace)
-            .args(["checkout", &integration])
-            .output()
-            .unwrap();
-        make_commit(workspace, "shared.txt", "integration", "integration commit");
-        Command::new("git")
-            .current_dir(workspace)
-            .args(["checkout", "main"])
-            .output()
-            .unwrap();
+        make_commit(&integration.path, "shared.txt", "integration", "integration commit");
 
         make_commit(&worktree_path, "shared.txt", "task", "task commit");
-        rebase_onto_integration(&worktree_path, &integration).unwrap();
+        rebase_onto_integration(&worktree_path, &integration.branch).unwrap();
         abort_rebase(&worktree_path).unwrap();
 
         // 리베이스 중단 후 정상 상태 확인
@@ -1884,6 +2298,7 @@ mod tests {
         assert!(stdout.trim().is_empty());
 
         remove_worktree(workspace, &worktree_path).unwrap();
+        remove_worktree(workspace, &integration.path).unwrap();
     }
 
     #[test]
@@ -1893,24 +2308,25 @@ mod tests {
         init_git_repo(workspace);
         make_commit(workspace, "init.txt", "init", "initial commit");
 
-        let integration = create_integration_branch(workspace, "test").unwrap();
-        let task_branch = create_task_branch(workspace, &integration, "TASK-00").unwrap();
+        let integration = create_integration_worktree(workspace, "test", &CommitPolicy::default()).unwrap();
+        let task_branch =
+            create_task_branch(workspace, &integration.branch, "test", "TASK-00", &CommitPolicy::default()).unwrap();
         let worktree_path = create_worktree(workspace, &task_branch).unwrap();
 
         make_commit(&worktree_path, "feature.txt", "feature", "feature commit");
         make_commit(&worktree_path, "feature2.txt", "feature2", "feature2 commit");
 
-        rebase_onto_integration(&worktree_path, &integration).unwrap();
+        rebase_onto_integration(&worktree_path, &integration.branch).unwrap();
 
         fast_forward_merge_task_branch(
-            workspace,
+            &integration.path,
             &task_branch,
         )
         .unwrap();
 
         // fast-forward 머지 후 태스크 브랜치의 커밋들이 그대로 통합 브랜치에 존재하는지 확인
         let log_output = Command::new("git")
-            .current_dir(workspace)
+            .current_dir(&integration.path)
             .args(["log", "--oneline", &format!("{}..HEAD", "master")])
             .output()
             .unwrap();
@@ -1921,6 +2337,7 @@ mod tests {
         assert!(commit_lines[1].contains("feature commit"));
 
         remove_worktree(workspace, &worktree_path).unwrap();
+        remove_worktree(workspace, &integration.path).unwrap();
     }
 
     #[test]
@@ -1930,8 +2347,9 @@ mod tests {
         init_git_repo(workspace);
         make_commit(workspace, "init.txt", "init", "initial commit");
 
-        let integration = create_integration_branch(workspace, "test").unwrap();
-        let task_branch = create_task_branch(workspace, &integration, "TASK-00").unwrap();
+        let integration = create_integration_worktree(workspace, "test", &CommitPolicy::default()).unwrap();
+        let task_branch =
+            create_task_branch(workspace, &integration.branch, "test", "TASK-00", &CommitPolicy::default()).unwrap();
 
         delete_branch(workspace, &task_branch).unwrap();
 
@@ -1942,6 +2360,8 @@ mod tests {
             .unwrap();
         let stdout = String::from_utf8_lossy(&output.stdout);
         assert!(stdout.trim().is_empty());
+
+        remove_worktree(workspace, &integration.path).unwrap();
     }
 
     #[test]
@@ -1951,30 +2371,22 @@ mod tests {
         init_git_repo(workspace);
         make_commit(workspace, "shared.txt", "original", "initial commit");
 
-        let integration = create_integration_branch(workspace, "test").unwrap();
-        let task_branch = create_task_branch(workspace, &integration, "TASK-00").unwrap();
+        let integration = create_integration_worktree(workspace, "test", &CommitPolicy::default()).unwrap();
+        let task_branch =
+            create_task_branch(workspace, &integration.branch, "test", "TASK-00", &CommitPolicy::default()).unwrap();
         let worktree_path = create_worktree(workspace, &task_branch).unwrap();
 
-        Command::new("git")
-            .current_dir(workspace)
-            .args(["checkout", &integration])
-            .output()
-            .unwrap();
-        make_commit(workspace, "shared.txt", "integration", "integration commit");
-        Command::new("git")
-            .current_dir(workspace)
-            .args(["checkout", "main"])
-            .output()
-            .unwrap();
+        make_commit(&integration.path, "shared.txt", "integration", "integration commit");
 
         make_commit(&worktree_path, "shared.txt", "task", "task commit");
-        rebase_onto_integration(&worktree_path, &integration).unwrap();
+        rebase_onto_integration(&worktree_path, &integration.branch).unwrap();
 
         let files = list_conflicted_files(&worktree_path).unwrap();
         assert_eq!(files, vec!["shared.txt"]);
 
         abort_rebase(&worktree_path).unwrap();
         remove_worktree(workspace, &worktree_path).unwrap();
+        remove_worktree(workspace, &integration.path).unwrap();
     }
 
     // -----------------------------------------------------------------------
@@ -2037,169 +2449,6 @@ mod tests {
         assert!(prompt.contains("git rebase --continue"));
     }
 
-    // -----------------------------------------------------------------------
-    // Build system detection tests
-    // -----------------------------------------------------------------------
-
-    #[test]
-    fn detect_build_commands_with_makefile() {
-        let temp_dir = TempDir::new().unwrap();
-        let makefile_content = "build:\n\tcargo build\n\ntest:\n\tcargo test\n";
-        fs::write(temp_dir.path().join("Makefile"), makefile_content).unwrap();
-
-        let result = detect_build_commands(temp_dir.path());
-        assert!(result.is_some());
-        let commands = result.unwrap();
-        assert_eq!(commands.build, "make build");
-        assert_eq!(commands.test, "make test");
-    }
-
-    #[test]
-    fn detect_build_commands_makefile_without_targets() {
-        let temp_dir = TempDir::new().unwrap();
-        let makefile_content = "clean:\n\trm -rf target\n";
-        fs::write(temp_dir.path().join("Makefile"), makefile_content).unwrap();
-
-        // Makefile에 build/test 타겟이 없으면 None
-        let result = detect_build_commands(temp_dir.path());
-        assert!(result.is_none());
-    }
-
-    #[test]
-    fn detect_build_commands_with_cargo_toml() {
-        let temp_dir = TempDir::new().unwrap();
-        fs::write(
-            temp_dir.path().join("Cargo.toml"),
-            "[package]\nname = \"test\"\n",
-        )
-        .unwrap();
-
-        let result = detect_build_commands(temp_dir.path());
-        assert!(result.is_some());
-        let commands = result.unwrap();
-        assert_eq!(commands.build, "cargo build");
-        assert_eq!(commands.test, "cargo test");
-    }
-
-    #[test]
-    fn detect_build_commands_with_package_json() {
-        let temp_dir = TempDir::new().unwrap();
-        let package_json = serde_json::json!({
-            "scripts": { "build": "tsc", "test": "jest" }
-        });
-        fs::write(
-            temp_dir.path().join("package.json"),
-            serde_json::to_string(&package_json).unwrap(),
-        )
-        .unwrap();
-
-        let result = detect_build_commands(temp_dir.path());
-        assert!(result.is_some());
-        let commands = result.unwrap();
-        assert_eq!(commands.build, "npm run build");
-        assert_eq!(commands.test, "npm test");
-    }
-
-    #[test]
-    fn detect_build_commands_with_go_mod() {
-        let temp_dir = TempDir::new().unwrap();
-        fs::write(
-            temp_dir.path().join("go.mod"),
-            "module example.com/test\n",
-        )
-        .unwrap();
-
-        let result = detect_build_commands(temp_dir.path());
-        assert!(result.is_some());
-        let commands = result.unwrap();
-        assert_eq!(commands.build, "go build ./...");
-        assert_eq!(commands.test, "go test ./...");
-    }
-
-    #[test]
-    fn detect_build_commands_returns_none_for_empty_dir() {
-        let temp_dir = TempDir::new().unwrap();
-        let result = detect_build_commands(temp_dir.path());
-        assert!(result.is_none());
-    }
-
-    #[test]
-    fn detect_build_commands_makefile_has_priority_over_cargo() {
-        let temp_dir = TempDir::new().unwrap();
-        fs::write(
-            temp_dir.path().join("Makefile"),
-            "build:\n\tcargo build\n\ntest:\n\tcargo test\n",
-        )
-        .unwrap();
-        fs::write(
-            temp_dir.path().join("Cargo.toml"),
-            "[package]\nname = \"test\"\n",
-        )
-        .unwrap();
-
-        let result = detect_build_commands(temp_dir.path());
-        assert!(result.is_some());
-        let commands = result.unwrap();
-        assert_eq!(commands.build, "make build");
-        assert_eq!(commands.test, "make test");
-    }
-
-    // -----------------------------------------------------------------------
-    // Build/test execution tests
-    // -----------------------------------------------------------------------
-
-    #[test]
-    fn run_build_and_test_success() {
-        let temp_dir = TempDir::new().unwrap();
-        let commands = BuildTestCommands {
-            build: "true".to_string(),
-            test: "true".to_string(),
-        };
-
-        let result = run_build_and_test(temp_dir.path(), &commands).unwrap();
-        assert!(matches!(result, BuildTestOutcome::Success));
-    }
-
-    #[test]
-    fn run_build_and_test_build_failure() {
-        let temp_dir = TempDir::new().unwrap();
-        let commands = BuildTestCommands {
-            build: "false".to_string(),
-            test: "true".to_string(),
-        };
-
-        let result = run_build_and_test(temp_dir.path(), &commands).unwrap();
-        assert!(matches!(result, BuildTestOutcome::BuildFailed { .. }));
-    }
-
-    #[test]
-    fn run_build_and_test_test_failure() {
-        let temp_dir = TempDir::new().unwrap();
-        let commands = BuildTestCommands {
-            build: "true".to_string(),
-            test: "false".to_string(),
-        };
-
-        let result = run_build_and_test(temp_dir.path(), &commands).unwrap();
-        assert!(matches!(result, BuildTestOutcome::TestFailed { .. }));
-    }
-
-    #[test]
-    fn run_build_and_test_captures_output() {
-        let temp_dir = TempDir::new().unwrap();
-        let commands = BuildTestCommands {
-            build: "echo build_ok && exit 1".to_string(),
-            test: "true".to_string(),
-        };
-
-        let result = run_build_and_test(temp_dir.path(), &commands).unwrap();
-        if let BuildTestOutcome::BuildFailed { output } = result {
-            assert!(output.contains("build_ok"));
-        } else {
-            panic!("expected BuildFailed");
-        }
-    }
-
     // -----------------------------------------------------------------------
     // Build/test repair schema and prompt tests
     // -----------------------------------------------------------------------
@@ -2237,16 +2486,18 @@ mod tests {
 
     #[test]
     fn build_test_repair_prompt_contains_context() {
-        let prompt = build_build_test_repair_prompt(
-            "TASK-01",
-            "make build",
-            "make test",
-            "error: cannot find module",
-        );
+        let mut pipeline = build_and_test_stages("make build", "make test");
+        pipeline[1].required = false;
+        let failures = vec![StageFailure {
+            stage: pipeline[0].clone(),
+            output: "error: cannot find module".to_string(),
+        }];
+
+        let prompt = build_build_test_repair_prompt("TASK-01", &pipeline, &failures);
 
         assert!(prompt.contains("TASK-01"));
-        assert!(prompt.contains("make build"));
-        assert!(prompt.contains("make test"));
+        assert!(prompt.contains("- build (required): `make build`"));
+        assert!(prompt.contains("- test (optional): `make test`"));
         assert!(prompt.contains("cannot find module"));
     }
 
@@ -2297,13 +2548,15 @@ mod tests {
             Path::new("/workspace/.bear/spec.md"),
             Path::new("/workspace/.bear/plan.md"),
             Path::new("/workspace/.bear/TASK-00.md"),
-            "abc1234",
+            &[PathBuf::from("/workspace/.bear/HANDOFF-TASK-02.md")],
+            "abc1234..def5678",
         );
 
         assert!(prompt.contains("spec.md"));
         assert!(prompt.contains("plan.md"));
         assert!(prompt.contains("TASK-00.md"));
-        assert!(prompt.contains("abc1234"));
+        assert!(prompt.contains("git log -p abc1234..def5678"));
+        assert!(prompt.contains("HANDOFF-TASK-02.md"));
         assert!(prompt.contains("Initial Code Review"));
     }
 
@@ -2313,6 +2566,7 @@ mod tests {
             Path::new("/workspace/.bear/spec.md"),
             Path::new("/workspace/.bear/plan.md"),
             Path::new("/workspace/.bear/TASK-01.md"),
+            &[],
             "def5678",
         );
 
@@ -2330,6 +2584,9 @@ mod tests {
             title: "기본 타입 정의".to_string(),
             description: "핵심 타입을 정의합니다.".to_string(),
             dependencies: vec![],
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: Vec::new(),
         };
 
         let prompt = build_coding_revision_prompt(
@@ -2360,6 +2617,99 @@ mod tests {
         assert!(revision.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
+    #[test]
+    fn squash_keeps_first_author_and_credits_co_authors() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path();
+        init_git_repo(workspace);
+        make_commit(workspace, "init.txt", "init", "initial commit");
+        let policy = CommitPolicy::default();
+        let integration = create_integration_worktree(workspace, "test", &policy).unwrap();
+        let task_branch = create_task_branch(workspace, &integration.branch, "test", "TASK-00", &policy).unwrap();
+        let worktree_path = create_worktree(workspace, &task_branch).unwrap();
+        for (author, file) in [("Alice", "a.txt"), ("Bob", "b.txt"), ("Alice", "c.txt")] {
+            fs::write(worktree_path.join(file), file).unwrap();
+            git::run(&worktree_path, ["add", file]).unwrap();
+            let name = format!("user.name={}", author);
+            let email = format!("user.email={}@example.com", author.to_lowercase());
+            git::run(&worktree_path, ["-c", &name, "-c", &email, "commit", "-m", file]).unwrap();
+        }
+        let task = CodingTask {
+            task_id: "TASK-00".to_string(),
+            title: "Add login form".to_string(),
+            description: String::new(),
+            dependencies: vec![],
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: vec![],
+        };
+        let summary = build_squash_summary(
+            &task,
+            "# Report\n\nIMPLEMENTATION_SUCCESS\n\nAdded the login form.\nValidated inputs.\n\n## Tests\n- ok",
+            &["REQ-001".to_string()],
+        );
+
+        let squashed = squash_task_commits(&worktree_path, &integration.branch, &summary, &policy).unwrap();
+
+        assert_eq!(squashed, 3);
+        let task_range = format!("{}..HEAD", integration.branch);
+        assert_eq!(git::run(&worktree_path, ["rev-list", "--count", &task_range]).unwrap(), "1");
+        let head = git::run(&worktree_path, ["log", "-1", "--format=%an%n%B"]).unwrap();
+        assert_eq!(
+            head,
+            "Alice\n\
+             Add login form\n\n\
+             Added the login form.\n\
+             Validated inputs.\n\n\
+             Requirements: REQ-001\n\
+             Tasks: TASK-00\n\
+             Co-authored-by: Bob <bob@example.com>"
+        );
+        assert!(worktree_path.join("b.txt").exists());
+
+        remove_worktree(workspace, &worktree_path).unwrap();
+        remove_worktree(workspace, &integration.path).unwrap();
+    }
+
+    #[test]
+    fn commit_check_catches_uncommitted_missing_and_stray_work() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path();
+        init_git_repo(workspace);
+        make_commit(workspace, "init.txt", "init", "initial commit");
+        let policy = CommitPolicy::default();
+        let integration = create_integration_worktree(workspace, "test", &policy).unwrap();
+        let task_branch = create_task_branch(workspace, &integration.branch, "test", "TASK-00", &policy).unwrap();
+        let worktree = TaskWorktreeInfo {
+            ref_snapshot: snapshot_refs(workspace).unwrap(),
+            worktree_path: create_worktree(workspace, &task_branch).unwrap(),
+            task_branch,
+        };
+        let journal_dir = worktree.worktree_path.join(".bear").join("20260101").join("test");
+        fs::create_dir_all(&journal_dir).unwrap();
+        fs::write(journal_dir.join("TASK-00.md"), "report").unwrap();
+        fs::write(worktree.worktree_path.join("feature.txt"), "feature").unwrap();
+
+        let check = check_task_commits(&worktree, &integration.branch, &journal_dir).unwrap();
+        assert_eq!(check.uncommitted_files, vec!["feature.txt".to_string()]);
+        assert_eq!(check.new_commit_count, 0);
+        assert_eq!(check.problems(&worktree.task_branch, &integration.branch).len(), 2);
+
+        let committed = commit_remaining_changes(&worktree.worktree_path, &journal_dir, "Commit remaining changes", &policy);
+        assert_eq!(committed.unwrap(), 1);
+        let check = check_task_commits(&worktree, &integration.branch, &journal_dir).unwrap();
+        assert!(check.problems(&worktree.task_branch, &integration.branch).is_empty());
+
+        git::run(&worktree.worktree_path, ["branch", "stray"]).unwrap();
+        let check = check_task_commits(&worktree, &integration.branch, &journal_dir).unwrap();
+        assert_eq!(check.unexpected_refs, vec!["refs/heads/stray".to_string()]);
+        let range = task_commit_range(&worktree.worktree_path, &integration.branch).unwrap();
+        assert_eq!(git::run(&worktree.worktree_path, ["rev-list", "--count", &range]).unwrap(), "1");
+
+        remove_worktree(workspace, &worktree.worktree_path).unwrap();
+        remove_worktree(workspace, &integration.path).unwrap();
+    }
+
     #[test]
     fn save_and_commit_task_report_in_worktree_creates_committed_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -2367,8 +2717,9 @@ mod tests {
         init_git_repo(workspace);
         make_commit(workspace, "init.txt", "init", "initial commit");
 
-        let integration = create_integration_branch(workspace, "test").unwrap();
-        let task_branch = create_task_branch(workspace, &integration, "TASK-00").unwrap();
+        let integration = create_integration_worktree(workspace, "test", &CommitPolicy::default()).unwrap();
+        let task_branch =
+            create_task_branch(workspace, &integration.branch, "test", "TASK-00", &CommitPolicy::default()).unwrap();
         let worktree_path = create_worktree(workspace, &task_branch).unwrap();
         make_commit(&worktree_path, "feature.txt", "feature", "feature commit");
 
@@ -2378,6 +2729,7 @@ mod tests {
             "test-session",
             "TASK-00",
             "# Test Report\nImplementation complete.",
+            &CommitPolicy::default(),
         )
         .unwrap();
 
@@ -2408,23 +2760,91 @@ mod tests {
         assert!(show.contains("TASK-00.md"), "task report must be included in the code commit");
 
         remove_worktree(workspace, &worktree_path).unwrap();
+        remove_worktree(workspace, &integration.path).unwrap();
     }
 
     #[test]
-    fn commit_file_in_workspace_commits_file() {
+    fn separate_report_commit_follows_commit_policy() {
         let temp_dir = TempDir::new().unwrap();
         let workspace = temp_dir.path();
         init_git_repo(workspace);
         make_commit(workspace, "init.txt", "init", "initial commit");
+        let policy = CommitPolicy {
+            identity: Some(CommitIdentity {
+                name: "Bear Bot".to_string(),
+                email: "bear@example.com".to_string(),
+            }),
+            task_message_template: "docs({task_id}): {summary}".to_string(),
+            trailers: true,
+            report_commit: ReportCommitMode::Separate,
+            ..CommitPolicy::default()
+        };
+
+        let integration = create_integration_worktree(workspace, "test", &policy).unwrap();
+        let task_branch = create_task_branch(workspace, &integration.branch, "test", "TASK-00", &policy).unwrap();
+        let worktree_path = create_worktree(workspace, &task_branch).unwrap();
+        make_commit(&worktree_path, "feature.txt", "feature", "feature commit");
 
-        create_integration_branch(workspace, "test").unwrap();
+        save_and_commit_task_report_in_worktree(
+            &worktree_path,
+            "20260216",
+            "test-session",
+            "TASK-00",
+            "# Test Report",
+            &policy,
+        )
+        .unwrap();
+
+        let head = git::run(&worktree_path, ["log", "-1", "--format=%an <%ae>%n%B"]).unwrap();
+        assert_eq!(
+            head,
+            "Bear Bot <bear@example.com>\n\
+             docs(TASK-00): Add implementation report for TASK-00\n\n\
+             Bear-Session: test-session\n\
+             Bear-Task: TASK-00"
+        );
+        let previous = git::run(&worktree_path, ["log", "-1", "--format=%s", "HEAD~1"]).unwrap();
+        assert_eq!(previous, "feature commit");
+
+        remove_worktree(workspace, &worktree_path).unwrap();
+        remove_worktree(workspace, &integration.path).unwrap();
+    }
+
+    #[test]
+    fn list_commits_and_diff_stat_in_range() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path();
+        init_git_repo(workspace);
+        make_commit(workspace, "init.txt", "init", "initial commit");
+        let base = get_latest_commit_revision(workspace).unwrap();
+
+        let integration = create_integration_worktree(workspace, "test", &CommitPolicy::default()).unwrap();
+        make_commit(&integration.path, "feature.txt", "feature", "feature commit");
+
+        let commits = list_commits_in_range(workspace, &base, &integration.branch).unwrap();
+        assert_eq!(commits.lines().count(), 1);
+        assert!(commits.contains("feature commit"));
+
+        let diff_stat = diff_stat_in_range(workspace, &base, &integration.branch).unwrap();
+        assert!(diff_stat.contains("feature.txt"));
+        assert!(!diff_stat.contains("init.txt"));
+
+        remove_worktree(workspace, &integration.path).unwrap();
+    }
+
+    #[test]
+    fn commit_file_in_workspace_commits_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path();
+        init_git_repo(workspace);
+        make_commit(workspace, "init.txt", "init", "initial commit");
 
         let bear_dir = workspace.join(".bear").join("20260218").join("test-session");
         fs::create_dir_all(&bear_dir).unwrap();
         let file_path = bear_dir.join("user-request.md");
         fs::write(&file_path, "# User Request\nBuild a feature.").unwrap();
 
-        commit_file_in_workspace(workspace, &file_path, "Add user request").unwrap();
+        commit_file_in_workspace(workspace, &file_path, "Add user request", &CommitPolicy::default()).unwrap();
 
         let log_output = Command::new("git")
             .current_dir(workspace)
@@ -2451,7 +2871,7 @@ mod tests {
         make_commit(workspace, "init.txt", "init", "initial commit");
 
         let nonexistent = workspace.join("does-not-exist.md");
-        let result = commit_file_in_workspace(workspace, &nonexistent, "Should fail");
+        let result = commit_file_in_workspace(workspace, &nonexistent, "Should fail", &CommitPolicy::default());
         assert!(result.is_err());
     }
 }
@@ -0,0 +1,352 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+const SUMMARY_PLACEHOLDER: &str = "{summary}";
+const SESSION_PLACEHOLDER: &str = "{session}";
+const TASK_ID_PLACEHOLDER: &str = "{task_id}";
+const UUID_PLACEHOLDER: &str = "{uuid}";
+pub const TASK_BRANCH_PREFIX: &str = "bear/task/";
+pub const INTEGRATION_BRANCH_PREFIX: &str = "bear/integration/";
+const SESSION_TRAILER: &str = "Bear-Session";
+const TASK_TRAILER: &str = "Bear-Task";
+
+/// Bear와 에이전트가 만드는 커밋의 작성자, 메시지, 서명과 Bear 브랜치 이름 규칙.
+/// 기본값은 저장소의 git 설정을 그대로 쓰고 메시지를 바꾸지 않는다.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommitPolicy {
+    /// 작성자와 커미터로 쓸 사용자. 없으면 저장소 설정을 따른다.
+    pub identity: Option<CommitIdentity>,
+    /// 태스크 커밋 메시지 제목. `{summary}`, `{task_id}`, `{session}`을 쓸 수 있다.
+    pub task_message_template: String,
+    /// 저널 커밋 메시지 제목. `{summary}`, `{session}`을 쓸 수 있다.
+    pub journal_message_template: String,
+    /// `Bear-Session:`, `Bear-Task:` 트레일러를 붙일지 여부.
+    pub trailers: bool,
+    /// `Signed-off-by:` 트레일러를 붙일지 여부.
+    pub sign_off: bool,
+    /// 커밋 서명 방식. 없으면 저장소 설정을 따른다.
+    pub signing: Option<CommitSigning>,
+    pub report_commit: ReportCommitMode,
+    /// 태스크를 통합 브랜치에 머지하기 전에 태스크 커밋을 보고서 요약을 담은 커밋 하나로 합칠지 여부.
+    /// 별도 보고서 커밋은 합친 커밋 뒤에 따로 남는다.
+    pub squash_task_commits: bool,
+    /// `bear/integration/`으로 시작하고 `{session}`, `{uuid}`를 포함해야 한다.
+    pub integration_branch_template: String,
+    /// `bear/task/`로 시작하고 `{uuid}`를 포함해야 한다. `{task_id}`, `{session}`을 쓸 수 있다.
+    pub task_branch_template: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommitIdentity {
+    pub name: String,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommitSigning {
+    pub format: SigningFormat,
+    /// 서명 키. 없으면 git의 `user.signingkey` 설정을 따른다.
+    #[serde(default)]
+    pub key: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SigningFormat {
+    Gpg,
+    Ssh,
+}
+
+/// 태스크 보고서를 코드 커밋에 합칠지, 별도 커밋으로 남길지.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportCommitMode {
+    #[default]
+    Amend,
+    Separate,
+}
+
+/// 커밋 메시지 템플릿과 트레일러에 채울 세션과 태스크.
+#[derive(Debug, Clone, Copy)]
+pub struct CommitContext<'a> {
+    pub session: &'a str,
+    pub task_id: Option<&'a str>,
+}
+
+impl Default for CommitPolicy {
+    fn default() -> Self {
+        Self {
+            identity: None,
+            task_message_template: SUMMARY_PLACEHOLDER.to_string(),
+            journal_message_template: SUMMARY_PLACEHOLDER.to_string(),
+            trailers: false,
+            sign_off: false,
+            signing: None,
+            report_commit: ReportCommitMode::Amend,
+            squash_task_commits: false,
+            integration_branch_template: format!(
+                "{}{}-{}",
+                INTEGRATION_BRANCH_PREFIX, SESSION_PLACEHOLDER, UUID_PLACEHOLDER
+            ),
+            task_branch_template: format!("{}{}-{}", TASK_BRANCH_PREFIX, TASK_ID_PLACEHOLDER, UUID_PLACEHOLDER),
+        }
+    }
+}
+
+impl CommitPolicy {
+    /// 템플릿이 필요한 자리표시자를 갖추었는지 확인한다. 정리와 세션 목록은 브랜치 접두사로 Bear 브랜치를 찾는다.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, template) in [
+            ("task_message_template", &self.task_message_template),
+            ("journal_message_template", &self.journal_message_template),
+        ] {
+            if !template.contains(SUMMARY_PLACEHOLDER) {
+                return Err(format!("{} must contain {}", name, SUMMARY_PLACEHOLDER));
+            }
+        }
+        if !self.integration_branch_template.starts_with(INTEGRATION_BRANCH_PREFIX)
+            || !self.integration_branch_template.contains(SESSION_PLACEHOLDER)
+            || !self.integration_branch_template.contains(UUID_PLACEHOLDER)
+        {
+            return Err(format!(
+                "integration_branch_template must start with {} and contain {} and {}",
+                INTEGRATION_BRANCH_PREFIX, SESSION_PLACEHOLDER, UUID_PLACEHOLDER
+            ));
+        }
+        if !self.task_branch_template.starts_with(TASK_BRANCH_PREFIX)
+            || !self.task_branch_template.contains(UUID_PLACEHOLDER)
+        {
+            return Err(format!(
+                "task_branch_template must start with {} and contain {}",
+                TASK_BRANCH_PREFIX, UUID_PLACEHOLDER
+            ));
+        }
+        if let Some(identity) = &self.identity
+            && (identity.name.trim().is_empty() || identity.email.trim().is_empty())
+        {
+            return Err("identity needs both name and email".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn integration_branch_name(&self, session: &str) -> String {
+        self.integration_branch_template
+            .replace(SESSION_PLACEHOLDER, session)
+            .replace(UUID_PLACEHOLDER, &Uuid::new_v4().to_string())
+    }
+
+    pub fn task_branch_name(&self, session: &str, task_id: &str) -> String {
+        self.task_branch_template
+            .replace(SESSION_PLACEHOLDER, session)
+            .replace(TASK_ID_PLACEHOLDER, task_id)
+            .replace(UUID_PLACEHOLDER, &Uuid::new_v4().to_string())
+    }
+
+    /// 태스크의 코드나 보고서 커밋 메시지를 템플릿과 트레일러로 완성한다.
+    pub fn render_task_message(&self, summary: &str, context: CommitContext) -> String {
+        self.render_message(&self.task_message_template, summary, context)
+    }
+
+    /// Bear가 저널 파일을 기록하는 커밋 메시지를 템플릿과 트레일러로 완성한다.
+    pub fn render_journal_message(&self, summary: &str, context: CommitContext) -> String {
+        self.render_message(&self.journal_message_template, summary, context)
+    }
+
+    /// 통합 브랜치를 기준 브랜치에 머지하는 커밋 메시지. 제목과 본문은 PR 텍스트를 그대로 쓰고 트레일러만 붙인다.
+    pub fn render_delivery_message(&self, title: &str, body: &str, session: &str) -> String {
+        let mut message = title.to_string();
+        if !body.trim().is_empty() {
+            message.push_str("\n\n");
+            message.push_str(body.trim_end());
+        }
+        self.push_trailers(&mut message, CommitContext { session, task_id: None });
+        message
+    }
+
+    fn render_message(&self, template: &str, summary: &str, context: CommitContext) -> String {
+        let mut message = fill_template(template, summary, context);
+        self.push_trailers(&mut message, context);
+        message
+    }
+
+    fn push_trailers(&self, message: &mut String, context: CommitContext) {
+        if self.trailers {
+            message.push_str(&format!("\n\n{}: {}", SESSION_TRAILER, context.session));
+            if let Some(task_id) = context.task_id {
+                message.push_str(&format!("\n{}: {}", TASK_TRAILER, task_id));
+            }
+        }
+    }
+
+    /// 작성자, 커미터, 서명을 정하는 git 설정 값. Bear의 커밋과 에이전트 프로세스 모두에 적용한다.
+    pub fn git_config_overrides(&self) -> Vec<(String, String)> {
+        let mut overrides = Vec::new();
+        if let Some(identity) = &self.identity {
+            overrides.push(("user.name".to_string(), identity.name.clone()));
+            overrides.push(("user.email".to_string(), identity.email.clone()));
+        }
+        if let Some(signing) = &self.signing {
+            overrides.push(("commit.gpgsign".to_string(), "true".to_string()));
+            let format = match signing.format {
+                SigningFormat::Gpg => "openpgp",
+                SigningFormat::Ssh => "ssh",
+            };
+            overrides.push(("gpg.format".to_string(), format.to_string()));
+            if let Some(key) = &signing.key {
+                overrides.push(("user.signingkey".to_string(), key.clone()));
+            }
+        }
+        overrides
+    }
+
+    /// 설정 값을 서브커맨드 앞에 둘 `-c key=value` 인자로 바꾼다.
+    pub fn git_config_arguments(&self) -> Vec<String> {
+        let mut arguments = Vec::new();
+        for (key, value) in self.git_config_overrides() {
+            arguments.push("-c".to_string());
+            arguments.push(format!("{}={}", key, value));
+        }
+        arguments
+    }
+
+    /// `git commit`에 넘길 인자. 설정 값은 `-c`로 서브커맨드 앞에 둔다.
+    pub fn commit_arguments(&self, extra_arguments: &[&str]) -> Vec<String> {
+        let mut arguments = self.git_config_arguments();
+        arguments.push("commit".to_string());
+        if self.sign_off {
+            arguments.push("--signoff".to_string());
+        }
+        arguments.extend(extra_arguments.iter().map(|argument| argument.to_string()));
+        arguments
+    }
+
+    /// 에이전트 프로세스의 git이 같은 설정을 쓰도록 하는 `GIT_CONFIG_*` 환경 변수.
+    pub fn agent_environment(&self) -> Vec<(String, String)> {
+        let overrides = self.git_config_overrides();
+        if overrides.is_empty() {
+            return Vec::new();
+        }
+        let mut environment = vec![("GIT_CONFIG_COUNT".to_string(), overrides.len().to_string())];
+        for (index, (key, value)) in overrides.into_iter().enumerate() {
+            environment.push((format!("GIT_CONFIG_KEY_{}", index), key));
+            environment.push((format!("GIT_CONFIG_VALUE_{}", index), value));
+        }
+        environment
+    }
+
+    /// 코딩 에이전트 시스템 프롬프트에 덧붙일 커밋 규칙. 기본 정책이면 빈 문자열이다.
+    pub fn agent_commit_instructions(&self, context: CommitContext) -> String {
+        let mut rules = Vec::new();
+        if self.task_message_template != SUMMARY_PLACEHOLDER {
+            rules.push(format!(
+                "- The subject line MUST follow this template, replacing `<subject>` with your short subject: `{}`",
+                fill_template(&self.task_message_template, "<subject>", context)
+            ));
+        }
+        if self.trailers {
+            let mut trailers = format!("{}: {}", SESSION_TRAILER, context.session);
+            if let Some(task_id) = context.task_id {
+                trailers.push_str(&format!("\n  {}: {}", TASK_TRAILER, task_id));
+            }
+            rules.push(format!(
+                "- End the message with these trailers, after a blank line:\n  {}",
+                trailers
+            ));
+        }
+        if self.sign_off {
+            rules.push("- Commit with `git commit --signoff`.".to_string());
+        }
+        if rules.is_empty() {
+            return String::new();
+        }
+        format!(
+            "\n\n---\n\n# Project Commit Policy\n\nThese rules override the commit message format above:\n{}",
+            rules.join("\n")
+        )
+    }
+}
+
+fn fill_template(template: &str, summary: &str, context: CommitContext) -> String {
+    template
+        .replace(TASK_ID_PLACEHOLDER, context.task_id.unwrap_or_default())
+        .replace(SESSION_PLACEHOLDER, context.session)
+        .replace(SUMMARY_PLACEHOLDER, summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TASK_CONTEXT: CommitContext = CommitContext { session: "session-1", task_id: Some("TASK-02") };
+
+    #[test]
+    fn default_policy_keeps_messages_and_branch_layout() {
+        let policy = CommitPolicy::default();
+
+        assert!(policy.validate().is_ok());
+        assert_eq!(policy.render_journal_message("Add user request", TASK_CONTEXT), "Add user request");
+        assert_eq!(policy.commit_arguments(&["-m", "msg"]), vec!["commit", "-m", "msg"]);
+        assert!(policy.agent_environment().is_empty());
+        assert!(policy.agent_commit_instructions(TASK_CONTEXT).is_empty());
+        assert!(policy.integration_branch_name("session-1").starts_with("bear/integration/session-1-"));
+        assert!(policy.task_branch_name("session-1", "TASK-02").starts_with("bear/task/TASK-02-"));
+    }
+
+    #[test]
+    fn configured_policy_renders_template_trailers_and_git_settings() {
+        let policy: CommitPolicy = serde_json::from_value(serde_json::json!({
+            "identity": { "name": "Bear", "email": "bear@example.com" },
+            "task_message_template": "feat({task_id}): {summary}",
+            "trailers": true,
+            "sign_off": true,
+            "signing": { "format": "ssh", "key": "~/.ssh/id_ed25519.pub" },
+            "report_commit": "separate",
+            "task_branch_template": "bear/task/{session}/{task_id}-{uuid}"
+        }))
+        .unwrap();
+
+        assert!(policy.validate().is_ok());
+        assert_eq!(policy.report_commit, ReportCommitMode::Separate);
+        assert!(policy.task_branch_name("session-1", "TASK-02").starts_with("bear/task/session-1/TASK-02-"));
+        assert_eq!(
+            policy.render_task_message("add login", TASK_CONTEXT),
+            "feat(TASK-02): add login\n\nBear-Session: session-1\nBear-Task: TASK-02"
+        );
+        assert_eq!(
+            policy.render_journal_message("Save coding state", CommitContext { session: "session-1", task_id: None }),
+            "Save coding state\n\nBear-Session: session-1"
+        );
+        assert_eq!(
+            policy.render_delivery_message("로그인 기능", "## 요약\n", "session-1"),
+            "로그인 기능\n\n## 요약\n\nBear-Session: session-1"
+        );
+        let arguments = policy.commit_arguments(&["--amend", "--no-edit"]);
+        assert_eq!(&arguments[..2], ["-c", "user.name=Bear"]);
+        assert!(arguments.contains(&"gpg.format=ssh".to_string()));
+        assert_eq!(arguments[arguments.len() - 4..], ["commit", "--signoff", "--amend", "--no-edit"]);
+        let environment = policy.agent_environment();
+        assert_eq!(environment[0], ("GIT_CONFIG_COUNT".to_string(), "5".to_string()));
+        let instructions = policy.agent_commit_instructions(TASK_CONTEXT);
+        assert!(instructions.contains("`feat(TASK-02): <subject>`"));
+        assert!(instructions.contains("Bear-Task: TASK-02"));
+        assert!(instructions.contains("--signoff"));
+    }
+
+    #[test]
+    fn validate_rejects_templates_outside_bear_namespace() {
+        let policy = CommitPolicy {
+            integration_branch_template: "feature/{session}-{uuid}".to_string(),
+            ..CommitPolicy::default()
+        };
+        assert!(policy.validate().is_err());
+
+        let policy = CommitPolicy {
+            task_message_template: "feat: {task_id}".to_string(),
+            ..CommitPolicy::default()
+        };
+        assert!(policy.validate().is_err());
+    }
+}
@@ -0,0 +1,501 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::git::{self, GitError};
+
+use super::coding::{CodingTask, CodingTaskStatus, TaskReport};
+use super::commit_policy::CommitPolicy;
+
+pub const PULL_REQUEST_FILE_NAME: &str = "pull-request.md";
+/// 리베이스 전달에 쓰는 임시 브랜치의 접두사. 통합 브랜치는 리베이스하지 않고 그대로 둔다.
+const REBASE_BRANCH_PREFIX: &str = "bear/rebase/";
+const PATCH_DIRECTORY_NAME: &str = "patches";
+const MAX_SUMMARY_LINES: usize = 12;
+
+/// 통합 브랜치를 세션을 시작한 브랜치로 전달하는 방법.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeliveryMethod {
+    SquashMerge,
+    RebaseMerge,
+    MergeCommit,
+    FormatPatch,
+    Bundle,
+    Finish,
+}
+
+/// 번호 또는 이름으로 입력한 전달 방법을 해석한다.
+pub fn parse_delivery_method(input: &str) -> Option<DeliveryMethod> {
+    match input.trim().to_lowercase().as_str() {
+        "1" | "squash" => Some(DeliveryMethod::SquashMerge),
+        "2" | "rebase" => Some(DeliveryMethod::RebaseMerge),
+        "3" | "merge" => Some(DeliveryMethod::MergeCommit),
+        "4" | "patch" => Some(DeliveryMethod::FormatPatch),
+        "5" | "bundle" => Some(DeliveryMethod::Bundle),
+        "6" | "done" => Some(DeliveryMethod::Finish),
+        _ => None,
+    }
+}
+
+pub fn format_delivery_menu(integration_branch: &str, base_branch: Option<&str>) -> String {
+    let target = base_branch.unwrap_or("(알 수 없음)");
+    format!(
+        "통합 브랜치 {} 를 전달할 방법을 선택하세요. 기준 브랜치: {}\n\
+         1. squash  - 하나의 커밋으로 합쳐 기준 브랜치에 머지\n\
+         2. rebase  - 임시 브랜치를 기준 브랜치 위로 리베이스한 뒤 fast-forward 머지 (통합 브랜치는 그대로 둡니다)\n\
+         3. merge   - 머지 커밋으로 기준 브랜치에 머지\n\
+         4. patch   - git format-patch로 패치 파일 내보내기\n\
+         5. bundle  - git bundle로 번들 파일 내보내기\n\
+         6. done    - 전달하지 않고 종료 (통합 브랜치는 유지됩니다)",
+        integration_branch, target,
+    )
+}
+
+/// 코드 호스트에 붙여 넣을 PR 제목과 본문.
+#[derive(Debug, PartialEq)]
+pub struct PullRequestText {
+    pub title: String,
+    pub body: String,
+}
+
+impl PullRequestText {
+    pub fn to_markdown(&self) -> String {
+        format!("# {}\n\n{}\n", self.title, self.body)
+    }
+}
+
+/// 스펙의 제목과 개요, 태스크 보고서, 커밋, 변경 파일로 PR 제목과 본문을 만든다.
+pub fn build_pull_request_text(
+    spec: &str,
+    tasks: &[CodingTask],
+    task_reports: &[TaskReport],
+    commit_log: &str,
+    diff_stat: &str,
+) -> PullRequestText {
+    let title = spec_title(spec).unwrap_or_else(|| "Bear 세션 결과".to_string());
+
+    let mut task_lines = Vec::new();
+    for task in tasks {
+        let status = match task_reports.iter().rev().find(|r| r.task_id == task.task_id) {
+            Some(report) if report.status == CodingTaskStatus::ImplementationSuccess => "완료",
+            Some(_) => "차단",
+            None => "미실행",
+        };
+        task_lines.push(format!("- [{}] {} ({})", task.task_id, task.title, status));
+    }
+
+    let body = format!(
+        "## 요약\n\n{}\n\n## 작업\n\n{}\n\n## 커밋\n\n```\n{}\n```\n\n## 변경 파일\n\n```\n{}\n```",
+        spec_overview(spec).unwrap_or_else(|| "(스펙 개요 없음)".to_string()),
+        if task_lines.is_empty() { "(없음)".to_string() } else { task_lines.join("\n") },
+        commit_log.trim(),
+        diff_stat.trim(),
+    );
+
+    PullRequestText { title, body }
+}
+
+fn spec_title(spec: &str) -> Option<String> {
+    spec.lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+}
+
+/// `Overview` 또는 `개요`가 들어간 섹션의 본문. 없으면 첫 번째 문단.
+fn spec_overview(spec: &str) -> Option<String> {
+    let lines: Vec<&str> = spec.lines().collect();
+    let overview_start = lines.iter().position(|line| {
+        line.starts_with('#') && (line.contains("Overview") || line.contains("개요"))
+    });
+
+    let section: Vec<&str> = match overview_start {
+        Some(start) => lines[start + 1..]
+            .iter()
+            .take_while(|line| !line.starts_with('#'))
+            .copied()
+            .collect(),
+        None => lines
+            .iter()
+            .skip_while(|line| line.trim().is_empty() || line.starts_with('#'))
+            .take_while(|line| !line.trim().is_empty())
+            .copied()
+            .collect(),
+    };
+
+    let text = section
+        .iter()
+        .take(MAX_SUMMARY_LINES)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+    Some(text).filter(|text| !text.is_empty())
+}
+
+pub fn save_pull_request_text(dir: &Path, text: &PullRequestText) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(PULL_REQUEST_FILE_NAME);
+    fs::write(&path, text.to_markdown())?;
+    Ok(path)
+}
+
+/// 현재 체크아웃된 브랜치 이름. detached HEAD이면 None을 반환한다.
+pub fn current_branch_name(workspace: &Path) -> Result<Option<String>, GitError> {
+    let name = git::run(workspace, ["rev-parse", "--abbrev-ref", "HEAD"])?;
+    Ok(Some(name).filter(|name| name != "HEAD"))
+}
+
+/// 머지에 쓸 브랜치와 커밋 설정.
+pub struct MergeRequest<'a> {
+    pub base_branch: &'a str,
+    pub integration_branch: &'a str,
+    pub session: &'a str,
+    pub policy: &'a CommitPolicy,
+}
+
+/// 선택한 방법으로 통합 브랜치를 기준 브랜치에 머지한다. 워크스페이스에 진행 중인 git 작업이나
+/// 커밋되지 않은 변경이 있으면 브랜치를 옮기기 전에 거부한다. 머지 커밋과 리베이스한 커밋은 커밋 정책의
+/// 사용자, 서명, 트레일러를 따른다. 성공하든 실패하든 원래 체크아웃된 브랜치로 돌아가고, 실패하면 진행 중인
+/// 머지/리베이스를 되돌린다. 통합 브랜치는 어떤 경우에도 바뀌지 않는다.
+pub fn merge_into_base_branch(
+    workspace: &Path,
+    method: DeliveryMethod,
+    request: &MergeRequest,
+    pull_request: &PullRequestText,
+) -> Result<(), GitError> {
+    let integration_branch = request.integration_branch;
+    if let Some(operation) = git::operation_in_progress(workspace)? {
+        return Err(GitError::OperationInProgress {
+            path: workspace.display().to_string(),
+            operation: operation.label(),
+        });
+    }
+    if !git::run(workspace, ["status", "--porcelain", "--untracked-files=no"])?.is_empty() {
+        return Err(GitError::UncommittedChanges {
+            path: workspace.display().to_string(),
+        });
+    }
+    let original_branch = current_branch_name(workspace)?;
+
+    let result = match method {
+        DeliveryMethod::SquashMerge => squash_merge(workspace, request, pull_request),
+        DeliveryMethod::RebaseMerge => rebase_merge(workspace, request),
+        DeliveryMethod::MergeCommit => merge_commit(workspace, request, pull_request),
+        DeliveryMethod::FormatPatch | DeliveryMethod::Bundle | DeliveryMethod::Finish => return Ok(()),
+    };
+
+    if result.is_ok() {
+        if let Some(branch) = original_branch.filter(|branch| branch != request.base_branch) {
+            git::run(workspace, ["checkout", &branch])?;
+        }
+    } else {
+        let _ = git::run(workspace, ["rebase", "--abort"]);
+        let _ = git::run(workspace, ["merge", "--abort"]);
+        let _ = git::run(workspace, ["reset", "--merge"]);
+        if let Some(branch) = original_branch {
+            let _ = git::run(workspace, ["checkout", &branch]);
+        }
+        if method == DeliveryMethod::RebaseMerge {
+            let _ = git::run(workspace, ["branch", "-D", &rebase_branch_name(integration_branch)]);
+        }
+    }
+    result
+}
+
+fn squash_merge(
+    workspace: &Path,
+    request: &MergeRequest,
+    pull_request: &PullRequestText,
+) -> Result<(), GitError> {
+    git::run(workspace, ["checkout", request.base_branch])?;
+    git::run(workspace, ["merge", "--squash", request.integration_branch])?;
+    commit_delivery(workspace, request, pull_request)
+}
+
+/// 통합 브랜치에서 만든 임시 브랜치를 리베이스하므로, 리베이스가 실패해도 세션의 통합 브랜치는 그대로 남는다.
+fn rebase_merge(workspace: &Path, request: &MergeRequest) -> Result<(), GitError> {
+    let rebase_branch = rebase_branch_name(request.integration_branch);
+    git::run(workspace, ["checkout", "-B", &rebase_branch, request.integration_branch])?;
+    // 리베이스는 커밋을 다시 만들므로 커미터와 서명도 정책을 따라야 한다.
+    let mut rebase_arguments = request.policy.git_config_arguments();
+    rebase_arguments.push("rebase".to_string());
+    if request.policy.sign_off {
+        rebase_arguments.push("--signoff".to_string());
+    }
+    rebase_arguments.push(request.base_branch.to_string());
+    git::run(workspace, rebase_arguments)?;
+    git::run(workspace, ["checkout", request.base_branch])?;
+    git::run(workspace, ["merge", "--ff-only", &rebase_branch])?;
+    git::run(workspace, ["branch", "-D", &rebase_branch])?;
+    Ok(())
+}
+
+fn rebase_branch_name(integration_branch: &str) -> String {
+    format!("{}{}", REBASE_BRANCH_PREFIX, integration_branch)
+}
+
+/// 머지 커밋은 `--no-commit`으로 멈춘 뒤 커밋 정책으로 직접 만든다.
+fn merge_commit(
+    workspace: &Path,
+    request: &MergeRequest,
+    pull_request: &PullRequestText,
+) -> Result<(), GitError> {
+    git::run(workspace, ["checkout", request.base_branch])?;
+    git::run(workspace, ["merge", "--no-ff", "--no-commit", request.integration_branch])?;
+    // 기준 브랜치가 이미 통합 브랜치를 포함하면 머지할 것이 없다.
+    if !git::succeeds(workspace, ["rev-parse", "--verify", "--quiet", "MERGE_HEAD"]) {
+        return Ok(());
+    }
+    commit_delivery(workspace, request, pull_request)
+}
+
+fn commit_delivery(
+    workspace: &Path,
+    request: &MergeRequest,
+    pull_request: &PullRequestText,
+) -> Result<(), GitError> {
+    let message = request
+        .policy
+        .render_delivery_message(&pull_request.title, &pull_request.body, request.session);
+    git::run(workspace, request.policy.commit_arguments(&["-m", &message]))?;
+    Ok(())
+}
+
+/// 기준 커밋 이후의 통합 브랜치 커밋을 저널의 `patches/` 디렉토리에 패치 파일로 내보낸다.
+pub fn export_patches(
+    workspace: &Path,
+    journal_dir: &Path,
+    base_revision: &str,
+    integration_branch: &str,
+) -> Result<PathBuf, GitError> {
+    let patch_dir = journal_dir.join(PATCH_DIRECTORY_NAME);
+    let range = format!("{}..{}", base_revision, integration_branch);
+    git::run(
+        workspace,
+        ["format-patch", "--quiet", "-o", &patch_dir.display().to_string(), &range],
+    )?;
+    Ok(patch_dir)
+}
+
+/// 기준 커밋 이후의 통합 브랜치를 저널의 `<session-id>.bundle` 파일로 내보낸다.
+pub fn export_bundle(
+    workspace: &Path,
+    journal_dir: &Path,
+    base_revision: &str,
+    integration_branch: &str,
+) -> Result<PathBuf, GitError> {
+    let session_id = journal_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("session");
+    let bundle_path = journal_dir.join(format!("{}.bundle", session_id));
+    let range = format!("{}..{}", base_revision, integration_branch);
+    git::run(workspace, ["bundle", "create", &bundle_path.display().to_string(), &range])?;
+    Ok(bundle_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::coding::{CodingTaskKind, TaskTrace};
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        git::run(dir, args).unwrap()
+    }
+
+    /// `repo`는 main 브랜치에 초기 커밋, `integration` 워크트리의 통합 브랜치에 커밋 두 개가 있다.
+    fn setup_repository() -> (TempDir, PathBuf, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("repo");
+        let integration = temp_dir.path().join("integration");
+        fs::create_dir(&workspace).unwrap();
+        git(&workspace, &["init", "-b", "main"]);
+        git(&workspace, &["config", "user.email", "test@test.com"]);
+        git(&workspace, &["config", "user.name", "Test"]);
+        git(&workspace, &["config", "commit.gpgsign", "false"]);
+        fs::write(workspace.join("init.txt"), "init").unwrap();
+        git(&workspace, &["add", "."]);
+        git(&workspace, &["commit", "-m", "initial commit"]);
+        git(
+            &workspace,
+            &["worktree", "add", "-b", "bear/integration/s", &integration.display().to_string()],
+        );
+        for name in ["a.txt", "b.txt"] {
+            fs::write(integration.join(name), name).unwrap();
+            git(&integration, &["add", name]);
+            git(&integration, &["commit", "-m", &format!("Add {}", name)]);
+        }
+        (temp_dir, workspace, integration)
+    }
+
+    fn merge(workspace: &Path, method: DeliveryMethod, base_branch: &str) -> Result<(), GitError> {
+        merge_with_policy(workspace, method, base_branch, &CommitPolicy::default())
+    }
+
+    fn merge_with_policy(
+        workspace: &Path,
+        method: DeliveryMethod,
+        base_branch: &str,
+        policy: &CommitPolicy,
+    ) -> Result<(), GitError> {
+        let request = MergeRequest {
+            base_branch,
+            integration_branch: "bear/integration/s",
+            session: "s",
+            policy,
+        };
+        merge_into_base_branch(workspace, method, &request, &sample_pull_request())
+    }
+
+    fn sample_pull_request() -> PullRequestText {
+        PullRequestText {
+            title: "로그인 기능".to_string(),
+            body: "## 요약\n\n로그인".to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_delivery_method_accepts_numbers_and_names() {
+        assert_eq!(parse_delivery_method("1"), Some(DeliveryMethod::SquashMerge));
+        assert_eq!(parse_delivery_method(" Rebase "), Some(DeliveryMethod::RebaseMerge));
+        assert_eq!(parse_delivery_method("bundle"), Some(DeliveryMethod::Bundle));
+        assert_eq!(parse_delivery_method("7"), None);
+    }
+
+    #[test]
+    fn pull_request_text_uses_spec_title_overview_and_tasks() {
+        let spec = "# 로그인 기능 명세\n\n## 1. Overview\n사용자 로그인을 추가합니다.\n\n## 2. Goals\n- 목표";
+        let tasks = vec![CodingTask {
+            task_id: "TASK-00".to_string(),
+            title: "로그인 화면".to_string(),
+            description: String::new(),
+            dependencies: vec![],
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: Vec::new(),
+        }];
+        let reports = vec![TaskReport {
+            task_id: "TASK-00".to_string(),
+            status: CodingTaskStatus::ImplementationSuccess,
+            report: String::new(),
+            report_file_path: PathBuf::new(),
+            trace: TaskTrace::default(),
+        }];
+
+        let text = build_pull_request_text(spec, &tasks, &reports, "abc Add login", " 1 file changed");
+
+        assert_eq!(text.title, "로그인 기능 명세");
+        assert!(text.body.starts_with("## 요약\n\n사용자 로그인을 추가합니다.\n\n## 작업"));
+        assert!(text.body.contains("- [TASK-00] 로그인 화면 (완료)"));
+        assert!(text.body.contains("abc Add login"));
+    }
+
+    #[test]
+    fn squash_merge_creates_single_commit_on_base_branch() {
+        let (_temp_dir, workspace, _integration) = setup_repository();
+
+        merge(&workspace, DeliveryMethod::SquashMerge, "main").unwrap();
+
+        assert_eq!(current_branch_name(&workspace).unwrap().as_deref(), Some("main"));
+        let log = git(&workspace, &["log", "--format=%s", "main"]);
+        assert_eq!(log.lines().collect::<Vec<_>>(), vec!["로그인 기능", "initial commit"]);
+        assert!(workspace.join("b.txt").is_file());
+    }
+
+    #[test]
+    fn merge_commit_follows_commit_policy_and_returns_to_original_branch() {
+        let (_temp_dir, workspace, _integration) = setup_repository();
+        git(&workspace, &["checkout", "-b", "feature"]);
+        let policy: CommitPolicy = serde_json::from_value(serde_json::json!({
+            "identity": { "name": "Policy", "email": "policy@example.com" },
+            "trailers": true,
+            "sign_off": true
+        }))
+        .unwrap();
+
+        merge_with_policy(&workspace, DeliveryMethod::MergeCommit, "main", &policy).unwrap();
+
+        assert_eq!(current_branch_name(&workspace).unwrap().as_deref(), Some("feature"));
+        assert_eq!(git(&workspace, &["log", "-1", "--format=%an <%ae>", "main"]), "Policy <policy@example.com>");
+        let message = git(&workspace, &["log", "-1", "--format=%B", "main"]);
+        assert!(message.starts_with("로그인 기능\n\n## 요약"));
+        assert!(message.contains("Bear-Session: s"));
+        assert!(message.contains("Signed-off-by: Policy <policy@example.com>"));
+        assert_eq!(git(&workspace, &["rev-list", "--count", "--merges", "main"]), "1");
+    }
+
+    #[test]
+    fn rebase_merge_fast_forwards_base_and_keeps_integration_branch() {
+        let (_temp_dir, workspace, _integration) = setup_repository();
+        fs::write(workspace.join("main.txt"), "main").unwrap();
+        git(&workspace, &["add", "main.txt"]);
+        git(&workspace, &["commit", "-m", "main commit"]);
+        let integration_head = git(&workspace, &["rev-parse", "bear/integration/s"]);
+
+        merge(&workspace, DeliveryMethod::RebaseMerge, "main").unwrap();
+
+        assert_eq!(current_branch_name(&workspace).unwrap().as_deref(), Some("main"));
+        assert_eq!(git(&workspace, &["rev-list", "--count", "main"]), "4");
+        assert_eq!(git(&workspace, &["rev-parse", "bear/integration/s"]), integration_head);
+        assert_eq!(git(&workspace, &["branch", "--list", "bear/rebase/*"]), "");
+    }
+
+    #[test]
+    fn failed_rebase_leaves_integration_branch_untouched() {
+        let (_temp_dir, workspace, _integration) = setup_repository();
+        fs::write(workspace.join("a.txt"), "conflicting").unwrap();
+        git(&workspace, &["add", "a.txt"]);
+        git(&workspace, &["commit", "-m", "conflicting commit"]);
+        let main_head = git(&workspace, &["rev-parse", "main"]);
+        let integration_head = git(&workspace, &["rev-parse", "bear/integration/s"]);
+
+        let result = merge(&workspace, DeliveryMethod::RebaseMerge, "main");
+
+        assert!(result.is_err());
+        assert_eq!(git::operation_in_progress(&workspace).unwrap(), None);
+        assert_eq!(current_branch_name(&workspace).unwrap().as_deref(), Some("main"));
+        assert_eq!(git(&workspace, &["rev-parse", "main"]), main_head);
+        assert_eq!(git(&workspace, &["rev-parse", "bear/integration/s"]), integration_head);
+        assert_eq!(git(&workspace, &["branch", "--list", "bear/rebase/*"]), "");
+    }
+
+    #[test]
+    fn merge_is_refused_while_a_git_operation_is_in_progress() {
+        let (_temp_dir, workspace, _integration) = setup_repository();
+        let head = git(&workspace, &["rev-parse", "HEAD"]);
+        fs::write(git::git_dir(&workspace).unwrap().join("MERGE_HEAD"), head).unwrap();
+
+        let result = merge(&workspace, DeliveryMethod::SquashMerge, "main");
+
+        assert!(matches!(result, Err(GitError::OperationInProgress { operation: "merge", .. })));
+        assert_eq!(git(&workspace, &["rev-list", "--count", "main"]), "1");
+    }
+
+    #[test]
+    fn failed_merge_returns_to_original_branch() {
+        let (_temp_dir, workspace, _integration) = setup_repository();
+
+        let result = merge(&workspace, DeliveryMethod::MergeCommit, "missing-branch");
+
+        assert!(result.is_err());
+        assert_eq!(current_branch_name(&workspace).unwrap().as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn exports_patches_and_bundle_into_journal() {
+        let (_temp_dir, workspace, integration) = setup_repository();
+        let journal_dir = integration.join(".bear").join("20260101").join("session-1");
+        let base = git(&workspace, &["rev-parse", "main"]);
+
+        let patch_dir = export_patches(&integration, &journal_dir, &base, "bear/integration/s").unwrap();
+        let bundle = export_bundle(&integration, &journal_dir, &base, "bear/integration/s").unwrap();
+
+        assert_eq!(fs::read_dir(&patch_dir).unwrap().count(), 2);
+        assert_eq!(bundle, journal_dir.join("session-1.bundle"));
+        git(&workspace, &["bundle", "verify", &bundle.display().to_string()]);
+    }
+}
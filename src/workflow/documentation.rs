@@ -0,0 +1,215 @@
+use std::path::{Path, PathBuf};
+
+use super::acceptance;
+use super::coding::{CodingTask, CodingTaskKind, CodingTaskStatus, TaskReport};
+
+pub fn system_prompt() -> &'static str {
+    r#"# Role
+
+You are the **documentation** assistant. Your job is to turn the results of a completed development session into user-facing documentation that ships together with the code.
+
+The specification MUST be treated as the canonical source of requirements and behavior. The task reports and the code on the integration branch describe what was actually built.
+
+**Core rules:**
+- You MUST only write or update documentation files (for example `README.md` sections, module-level doc comments, `docs/` pages, an architecture overview).
+- You MUST NOT change program behavior. Do NOT modify logic, public interfaces, tests, or build configuration.
+- Documentation MUST describe the behavior that actually exists in the code. If a task report and the code disagree, the code wins.
+- Prefer updating existing documents over creating new ones. Follow the structure, tone, and language of the existing documentation in the repository.
+- Keep the documentation concise and oriented to the reader: what the feature does, how to use it, how it is configured, and how the pieces fit together.
+
+---
+
+# Documentation Process
+
+1. Read the specification, the plan, and every task report listed in the prompt.
+2. Inspect the integration branch diff to learn which modules, interfaces, and configuration were added or changed.
+3. Decide which documents need to be created or updated:
+   - README sections for new user-visible features, commands, options, or configuration.
+   - Module-level doc comments for new modules or significantly changed public interfaces.
+   - An architecture overview (for example `docs/architecture.md`) when the session introduced new components or changed how components interact.
+4. Write the documentation changes.
+5. Run the project's build and tests to confirm that documentation changes (for example doc comments) did not break anything.
+6. Make a single commit with all documentation changes.
+
+---
+
+# Output Language
+
+Follow the language of the existing documentation in the repository. If there is no existing documentation, write in Korean. Code identifiers, file paths, and command names MUST remain unchanged.
+
+---
+
+# Output Format
+
+You MUST return the status marker and a report following the given JSON Schema:
+- `IMPLEMENTATION_SUCCESS`: the documentation was written, the build and tests pass, and the changes are committed.
+- `IMPLEMENTATION_BLOCKED`: the documentation could not be completed (explain why).
+
+The report MUST list every document created or updated, summarize what each one now covers, and include the commit hash and subject line."#
+}
+
+const DOCUMENTATION_PROMPT_TEMPLATE: &str = r#"Write or update the user-facing documentation for the work completed in this session.
+
+Output MUST be valid JSON conforming to the provided JSON Schema.
+
+---
+
+Assigned task:
+<<<
+Task ID: {{TASK_ID}}
+Task Title: {{TASK_TITLE}}
+Task Description:
+{{TASK_DESCRIPTION}}
+>>>
+
+You MUST read following files for context before writing documentation:
+- Specification:
+  - {{SPEC_PATH}}
+- Plan:
+  - {{PLAN_PATH}}
+- Task reports:
+{{TASK_REPORT_PATHS}}
+
+You MUST inspect the code changes of this session on the integration branch:
+- `git log --oneline {{BASE_REVISION}}..{{INTEGRATION_BRANCH}}`
+- `git diff {{BASE_REVISION}}..{{INTEGRATION_BRANCH}}`
+
+---
+
+Worktree context:
+- Integration Branch: {{INTEGRATION_BRANCH}}"#;
+
+pub fn build_documentation_prompt(
+    task: &CodingTask,
+    spec_path: &Path,
+    plan_path: &Path,
+    task_report_paths: &[PathBuf],
+    integration_branch: &str,
+    base_revision: &str,
+) -> String {
+    let report_section = if task_report_paths.is_empty() {
+        "  - N/A".to_string()
+    } else {
+        task_report_paths
+            .iter()
+            .map(|p| format!("  - {}", p.display()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    DOCUMENTATION_PROMPT_TEMPLATE
+        .replace("{{TASK_ID}}", &task.task_id)
+        .replace("{{TASK_TITLE}}", &task.title)
+        .replace("{{TASK_DESCRIPTION}}", &task.description)
+        .replace("{{SPEC_PATH}}", &spec_path.display().to_string())
+        .replace("{{PLAN_PATH}}", &plan_path.display().to_string())
+        .replace("{{TASK_REPORT_PATHS}}", &report_section)
+        .replace("{{BASE_REVISION}}", base_revision)
+        .replace("{{INTEGRATION_BRANCH}}", integration_branch)
+}
+
+/// 코딩 단계가 끝난 뒤 실행할 문서화 태스크를 만든다.
+pub fn build_documentation_task(tasks: &[CodingTask]) -> CodingTask {
+    CodingTask {
+        task_id: acceptance::next_task_id(tasks),
+        title: "세션 결과 문서화".to_string(),
+        description: "스펙, 개발 계획, 작업 리포트와 통합 브랜치의 변경 사항을 바탕으로 \
+                      README 섹션, 모듈 문서, 아키텍처 개요 등 사용자용 문서를 작성하거나 갱신합니다."
+            .to_string(),
+        dependencies: Vec::new(),
+        kind: CodingTaskKind::Documentation,
+        retry: None,
+        requirement_ids: Vec::new(),
+    }
+}
+
+/// 문서화 에이전트에 전달할, 성공적으로 병합된 태스크의 리포트 경로를 모은다.
+pub fn collect_merged_report_paths(task_reports: &[TaskReport]) -> Vec<PathBuf> {
+    task_reports
+        .iter()
+        .filter(|r| r.status == CodingTaskStatus::ImplementationSuccess)
+        .filter(|r| !r.report_file_path.as_os_str().is_empty())
+        .map(|r| r.report_file_path.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::coding::TaskTrace;
+
+    fn make_report(task_id: &str, status: CodingTaskStatus, path: &str) -> TaskReport {
+        TaskReport {
+            task_id: task_id.to_string(),
+            status,
+            report: String::new(),
+            report_file_path: PathBuf::from(path),
+            trace: TaskTrace::default(),
+        }
+    }
+
+    #[test]
+    fn documentation_task_uses_next_id_and_kind() {
+        let tasks = vec![CodingTask {
+            task_id: "TASK-03".to_string(),
+            title: "작업".to_string(),
+            description: "설명".to_string(),
+            dependencies: vec![],
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: Vec::new(),
+        }];
+
+        let task = build_documentation_task(&tasks);
+
+        assert_eq!(task.task_id, "TASK-04");
+        assert_eq!(task.kind, CodingTaskKind::Documentation);
+    }
+
+    #[test]
+    fn collect_merged_report_paths_skips_blocked_and_unsaved() {
+        let reports = vec![
+            make_report("TASK-00", CodingTaskStatus::ImplementationSuccess, "/tmp/TASK-00.md"),
+            make_report("TASK-01", CodingTaskStatus::ImplementationBlocked, "/tmp/TASK-01.md"),
+            make_report("TASK-02", CodingTaskStatus::ImplementationSuccess, ""),
+        ];
+
+        let paths = collect_merged_report_paths(&reports);
+
+        assert_eq!(paths, vec![PathBuf::from("/tmp/TASK-00.md")]);
+    }
+
+    #[test]
+    fn documentation_prompt_contains_all_fields() {
+        let task = build_documentation_task(&[]);
+        let prompt = build_documentation_prompt(
+            &task,
+            Path::new("/ws/.bear/spec.md"),
+            Path::new("/ws/.bear/plan.md"),
+            &[PathBuf::from("/ws/.bear/TASK-00.md")],
+            "bear/integration/session-abc",
+            "abc1234",
+        );
+
+        assert!(prompt.contains("TASK-00"));
+        assert!(prompt.contains("/ws/.bear/spec.md"));
+        assert!(prompt.contains("/ws/.bear/plan.md"));
+        assert!(prompt.contains("/ws/.bear/TASK-00.md"));
+        assert!(prompt.contains("abc1234..bear/integration/session-abc"));
+    }
+
+    #[test]
+    fn documentation_prompt_without_reports() {
+        let task = build_documentation_task(&[]);
+        let prompt = build_documentation_prompt(
+            &task,
+            Path::new("/ws/spec.md"),
+            Path::new("/ws/plan.md"),
+            &[],
+            "bear/integration/x",
+            "HEAD",
+        );
+
+        assert!(prompt.contains("N/A"));
+    }
+}
@@ -0,0 +1,4239 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+use crate::claude_code_client::usage::UsageTotals;
+use crate::claude_code_client::{ClaudeCodeClient, ClaudeCodeClientError, ClaudeCodeRequest};
+use crate::git::{self, GitError};
+use super::acceptance;
+use super::clarification::{
+    self, ClarificationQuestion, ClarificationQuestions, QaRound, QuestionAnswer,
+};
+use super::coding::{
+    self, BuildTestRepairResult,
+    BuildTestRepairStatus, CodingPhaseState, CodingStateSnapshot, CodingTask, CodingTaskResult,
+    CodingTaskKind, CodingTaskStatus, ConflictResolutionResult, ConflictResolutionStatus,
+    RebaseOutcome, ReviewResult, ReviewStatus, TaskExtractionResponse,
+    TaskReport, TaskTrace, TaskWorktreeInfo,
+};
+use super::delivery::{self, DeliveryMethod, MergeRequest, PullRequestText};
+use super::cleanup;
+use super::commit_policy::CommitContext;
+use super::documentation;
+use super::file_validation::{self, FileKind, FileValidationResponse};
+use super::handoff::{self, HandoffDocument};
+use super::integration_review::{self, IntegrationFinding, IntegrationReviewResult};
+use super::journal_storage::{self, JournalStorage};
+use super::planning::{self, PlanResponseType, PlanWritingResponse};
+use super::preflight;
+use super::project_config::{self, ProjectConfig};
+use super::requirements_import::{self, RequirementSource};
+use super::revision::{self, RevisionHistory};
+use super::session_naming;
+use super::session_report::{self, BuildTestRecord, ReviewRecord, SessionActivity, SessionReportInput};
+use super::spec_writing::{self, SpecResponseType, SpecWritingResponse};
+use super::task_review;
+use super::traceability;
+use super::verification::{self, CriterionResult, VerificationResult};
+use super::verification_pipeline::{self, PipelineOutcome, StageFailure, VerificationStage};
+use super::{WorkflowCommand, WorkflowError, WorkflowEvent, WorkflowPhase};
+
+enum AgentOutcome {
+    Clarification(ClarificationQuestions),
+    SpecWriting(SpecWritingResponse),
+    Planning(PlanWritingResponse),
+    TaskExtraction(TaskExtractionResponse),
+    CodingTaskCompleted(CodingTaskResult),
+    ReviewCompleted(ReviewResult),
+    ConflictResolutionCompleted(ConflictResolutionResult),
+    BuildTestCompleted(PipelineOutcome),
+    BuildTestRepairCompleted(BuildTestRepairResult),
+    FileValidation(FileValidationResponse),
+    HandoffCompleted(Result<HandoffDocument, String>),
+    VerificationCompleted(Result<VerificationResult, String>),
+    IntegrationReviewCompleted(Result<IntegrationReviewResult, String>),
+}
+
+struct AgentThreadResult {
+    client: ClaudeCodeClient,
+    outcome: Result<AgentOutcome, String>,
+}
+
+enum AgentStreamMessage {
+    /// 새 세션 저널 디렉토리에 요구사항 사본이 준비되었다.
+    JournalCreated,
+    StreamLine(String),
+    Completed(Box<AgentThreadResult>),
+}
+
+/// 요구사항 정리부터 스펙, 플랜, 코딩, 최종 승인, 문서화까지의 세션 파이프라인을 구동한다.
+/// 터미널에 의존하지 않으므로 TUI 외의 호스트도 [`WorkflowCommand`]를 보내고
+/// [`WorkflowEvent`]를 받아 같은 워크플로를 실행할 수 있다.
+pub struct Engine {
+    api_key: String,
+    /// 에이전트 CLI 실행 파일. 없으면 PATH와 알려진 설치 위치에서 찾는다.
+    agent_binary: Option<PathBuf>,
+    workspace: PathBuf,
+    project_config: ProjectConfig,
+    /// 현재 세션의 저널 보관 방식. 이어서 진행하는 세션은 저널이 놓인 위치를 따른다.
+    journal_storage: JournalStorage,
+    phase: WorkflowPhase,
+    events: Vec<WorkflowEvent>,
+    requirements: Option<String>,
+    requirement_sources: Vec<RequirementSource>,
+    last_user_input: Option<String>,
+    claude_client: Option<ClaudeCodeClient>,
+    agent_result_receiver: Option<mpsc::Receiver<AgentStreamMessage>>,
+    qa_log: Vec<QaRound>,
+    current_round_questions: Vec<ClarificationQuestion>,
+    current_round_answers: Vec<QuestionAnswer>,
+    last_spec_draft: Option<String>,
+    spec_clarification_questions: Vec<String>,
+    last_plan_draft: Option<String>,
+    plan_clarification_questions: Vec<String>,
+    spec_revisions: RevisionHistory,
+    plan_revisions: RevisionHistory,
+    revision_feedback: Option<String>,
+    pending_rollback_note: Option<String>,
+    approved_spec: Option<String>,
+    spec_revision_instructions_sent: bool,
+    session_name: Option<String>,
+    session_date_dir: Option<String>,
+    base_journal_dir: Option<PathBuf>,
+    integration_branch: Option<String>,
+    integration_base_commit: Option<String>,
+    integration_base_branch: Option<String>,
+    integration_worktree: Option<PathBuf>,
+    reviewed_tasks: Vec<CodingTask>,
+    coding_state: Option<CodingPhaseState>,
+    pending_coding_report: Option<String>,
+    review_state: Option<ReviewState>,
+    pending_build_test: Option<PendingBuildTest>,
+    pending_handoff: Option<PendingHandoff>,
+    coding_hold: Option<CodingHold>,
+    build_test_command_phase: BuildTestCommandPhase,
+    fatal_error: Option<String>,
+    resumed_session_dir: Option<PathBuf>,
+    resumed_has_plan: bool,
+    pending_validation_kind: Option<FileKind>,
+    activity: SessionActivity,
+    /// 이 세션에서 실행한 에이전트 쿼리의 누적 사용량. 코딩 상태와 함께 저장해 이어서 진행할 때 복원한다.
+    usage: UsageTotals,
+    current_task_trace: TaskTrace,
+    failed_criteria: Vec<CriterionResult>,
+    integration_findings: Vec<IntegrationFinding>,
+    pull_request: Option<PullRequestText>,
+}
+
+struct PendingBuildTest {
+    task_id: String,
+    report: String,
+    is_retry: bool,
+}
+
+struct NextCodingTask {
+    task: CodingTask,
+    total: usize,
+    index: usize,
+    upstream_report_paths: Vec<PathBuf>,
+    upstream_handoff_paths: Vec<PathBuf>,
+}
+
+struct PendingHandoff {
+    task: CodingTask,
+    report: String,
+    report_file_path: PathBuf,
+    files_touched: Vec<String>,
+}
+
+struct ReviewState {
+    task_id: String,
+    report: String,
+    iteration_count: usize,
+    reviewer_client: Option<ClaudeCodeClient>,
+    coding_client: Option<ClaudeCodeClient>,
+    /// 커밋 확인에 실패해 코딩 에이전트에게 되돌려 보낸 횟수.
+    commit_check_count: usize,
+}
+
+const MAX_REVIEW_ITERATIONS: usize = 3;
+const MAX_COMMIT_CHECK_RETRIES: usize = 2;
+
+/// 현재 작업이 끝난 뒤 다음 작업으로 넘어가기 전에 적용할 사용자 요청.
+#[derive(Clone, Copy)]
+enum CodingHold {
+    Pause,
+    Stop,
+}
+
+/// 검증 파이프라인을 감지하지 못했을 때 빌드와 테스트 명령어를 차례로 묻는다.
+enum BuildTestCommandPhase {
+    BuildCommand,
+    TestCommand { build_command: String },
+}
+
+const DRAFT_FEEDBACK_HELP: &str =
+    "피드백을 입력하거나, Ctrl+A를 눌러 승인하세요. 이전 리비전으로 돌아가려면 /rollback <번호>를 입력하세요.";
+
+impl Engine {
+    pub fn new(api_key: String, workspace: PathBuf, project_config: ProjectConfig) -> Self {
+        Self {
+            api_key,
+            agent_binary: None,
+            workspace,
+            journal_storage: project_config.journal_storage,
+            project_config,
+            phase: WorkflowPhase::Idle,
+            events: Vec::new(),
+            requirements: None,
+            requirement_sources: Vec::new(),
+            last_user_input: None,
+            claude_client: None,
+            agent_result_receiver: None,
+            qa_log: Vec::new(),
+            current_round_questions: Vec::new(),
+            current_round_answers: Vec::new(),
+            last_spec_draft: None,
+            spec_clarification_questions: Vec::new(),
+            last_plan_draft: None,
+            plan_clarification_questions: Vec::new(),
+            spec_revisions: RevisionHistory::new("spec"),
+            plan_revisions: RevisionHistory::new("plan"),
+            revision_feedback: None,
+            pending_rollback_note: None,
+            approved_spec: None,
+            spec_revision_instructions_sent: false,
+            session_name: None,
+            session_date_dir: None,
+            base_journal_dir: None,
+            integration_branch: None,
+            integration_base_commit: None,
+            integration_base_branch: None,
+            integration_worktree: None,
+            reviewed_tasks: Vec::new(),
+            coding_state: None,
+            pending_coding_report: None,
+            review_state: None,
+            pending_build_test: None,
+            pending_handoff: None,
+            coding_hold: None,
+            build_test_command_phase: BuildTestCommandPhase::BuildCommand,
+            fatal_error: None,
+            resumed_session_dir: None,
+            resumed_has_plan: false,
+            pending_validation_kind: None,
+            activity: SessionActivity::default(),
+            usage: UsageTotals::default(),
+            current_task_trace: TaskTrace::default(),
+            failed_criteria: Vec::new(),
+            integration_findings: Vec::new(),
+            pull_request: None,
+        }
+    }
+
+    pub fn phase(&self) -> WorkflowPhase {
+        self.phase
+    }
+
+    pub fn workspace(&self) -> &Path {
+        &self.workspace
+    }
+
+    pub fn fatal_error(&self) -> Option<&str> {
+        self.fatal_error.as_deref()
+    }
+
+    /// [`WorkflowPhase::TaskReview`]에서 사용자가 수정 중인 작업 목록.
+    pub fn reviewed_tasks(&self) -> &[CodingTask] {
+        &self.reviewed_tasks
+    }
+
+    /// 에이전트 스레드의 결과를 확인하고, 그 사이 쌓인 이벤트를 모두 돌려준다.
+    pub fn poll_events(&mut self) -> Vec<WorkflowEvent> {
+        self.tick_agent_result();
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn handle_command(&mut self, command: WorkflowCommand) -> Result<(), WorkflowError> {
+        match (self.phase, command) {
+            (_, WorkflowCommand::Cancel) => {
+                self.cancel();
+                Ok(())
+            }
+            (WorkflowPhase::Idle, WorkflowCommand::StartSession { requirements }) => {
+                let requirements = non_empty_input(requirements)?;
+                self.submit_requirements(requirements)
+            }
+            (WorkflowPhase::Idle, WorkflowCommand::ResumeSession { session_dir }) => {
+                let session_dir = non_empty_input(session_dir)?;
+                self.submit_session_dir_path(session_dir)
+            }
+            (WorkflowPhase::ClarificationAnswer, WorkflowCommand::SubmitAnswer(answer)) => {
+                self.submit_clarification_answer(non_empty_input(answer)?);
+                Ok(())
+            }
+            (WorkflowPhase::SpecClarificationAnswer, WorkflowCommand::SubmitAnswer(answer)) => {
+                self.submit_spec_clarification_answer(non_empty_input(answer)?);
+                Ok(())
+            }
+            (WorkflowPhase::PlanClarificationAnswer, WorkflowCommand::SubmitAnswer(answer)) => {
+                self.submit_plan_clarification_answer(non_empty_input(answer)?);
+                Ok(())
+            }
+            (WorkflowPhase::BuildTestCommandInput, WorkflowCommand::SubmitAnswer(command)) => {
+                self.submit_build_test_command(non_empty_input(command)?);
+                Ok(())
+            }
+            (WorkflowPhase::SpecFeedback, WorkflowCommand::Feedback(feedback)) => {
+                self.submit_spec_feedback(non_empty_input(feedback)?);
+                Ok(())
+            }
+            (WorkflowPhase::PlanFeedback, WorkflowCommand::Feedback(feedback)) => {
+                self.submit_plan_feedback(non_empty_input(feedback)?);
+                Ok(())
+            }
+            (WorkflowPhase::TaskReview, WorkflowCommand::Feedback(command)) => {
+                self.submit_task_review_command(non_empty_input(command)?);
+                Ok(())
+            }
+            (WorkflowPhase::AcceptanceReview, WorkflowCommand::Feedback(change_request)) => {
+                self.submit_change_request(non_empty_input(change_request)?);
+                Ok(())
+            }
+            (WorkflowPhase::SpecFeedback, WorkflowCommand::Approve) => {
+                self.approve_spec();
+                Ok(())
+            }
+            (WorkflowPhase::PlanFeedback, WorkflowCommand::Approve) => {
+                self.approve_plan();
+                Ok(())
+            }
+            (WorkflowPhase::TaskReview, WorkflowCommand::Approve) => {
+                self.confirm_task_list();
+                Ok(())
+            }
+            (WorkflowPhase::AcceptanceReview, WorkflowCommand::Approve) => {
+                self.approve_final_code();
+                Ok(())
+            }
+            (WorkflowPhase::Coding, WorkflowCommand::Pause) => {
+                self.request_coding_hold(CodingHold::Pause);
+                Ok(())
+            }
+            (WorkflowPhase::Coding, WorkflowCommand::Stop) => {
+                self.request_coding_hold(CodingHold::Stop);
+                Ok(())
+            }
+            (WorkflowPhase::Coding, WorkflowCommand::Resume) => {
+                self.cancel_coding_hold();
+                Ok(())
+            }
+            (WorkflowPhase::Paused, WorkflowCommand::Resume) => {
+                self.resume_coding();
+                Ok(())
+            }
+            (WorkflowPhase::Paused, WorkflowCommand::Stop) => {
+                self.stop_coding();
+                Ok(())
+            }
+            (WorkflowPhase::Delivery, WorkflowCommand::SubmitAnswer(answer)) => {
+                self.deliver_integration_branch(&answer);
+                Ok(())
+            }
+            (WorkflowPhase::Done, WorkflowCommand::ExportReportBundle) => {
+                self.export_report_bundle();
+                Ok(())
+            }
+            (phase, _) => Err(WorkflowError::UnexpectedCommand { phase }),
+        }
+    }
+
+    fn cancel(&mut self) {
+        // 수신 채널을 버리면 실행 중인 에이전트 스레드의 전송은 조용히 실패한다.
+        self.agent_result_receiver = None;
+        self.set_phase(WorkflowPhase::Done);
+    }
+
+    fn set_phase(&mut self, phase: WorkflowPhase) {
+        self.phase = phase;
+        self.events.push(WorkflowEvent::PhaseChanged(phase));
+    }
+
+    fn add_system_message(&mut self, content: &str) {
+        self.events.push(WorkflowEvent::Message(content.to_string()));
+    }
+
+    fn tick_agent_result(&mut self) {
+        let receiver = match self.agent_result_receiver.take() {
+            Some(r) => r,
+            None => return,
+        };
+
+        loop {
+            match receiver.try_recv() {
+                Ok(AgentStreamMessage::JournalCreated) => {
+                    self.commit_user_request();
+                }
+                Ok(AgentStreamMessage::StreamLine(line)) => {
+                    self.events.push(WorkflowEvent::AgentOutput(line));
+                }
+                Ok(AgentStreamMessage::Completed(mut result)) => {
+                    self.usage.add(result.client.take_usage());
+                    self.claude_client = Some(result.client);
+                    match result.outcome {
+                        Ok(AgentOutcome::Clarification(response)) => {
+                            self.handle_clarification_response(response);
+                        }
+                        Ok(AgentOutcome::SpecWriting(response)) => {
+                            self.handle_spec_response(response);
+                        }
+                        Ok(AgentOutcome::Planning(response)) => {
+                            self.handle_plan_response(response);
+                        }
+                        Ok(AgentOutcome::TaskExtraction(response)) => {
+                            self.handle_task_extraction_response(response);
+                        }
+                        Ok(AgentOutcome::CodingTaskCompleted(result)) => {
+                            self.handle_coding_task_result(result);
+                        }
+                        Ok(AgentOutcome::ReviewCompleted(result)) => {
+                            self.handle_review_result(result);
+                        }
+                        Ok(AgentOutcome::ConflictResolutionCompleted(result)) => {
+                            self.handle_conflict_resolution_result(result);
+                        }
+                        Ok(AgentOutcome::BuildTestCompleted(outcome)) => {
+                            self.handle_build_test_result(outcome);
+                        }
+                        Ok(AgentOutcome::BuildTestRepairCompleted(result)) => {
+                            self.handle_build_test_repair_result(result);
+                        }
+                        Ok(AgentOutcome::FileValidation(result)) => {
+                            self.handle_file_validation_result(result);
+                        }
+                        Ok(AgentOutcome::HandoffCompleted(result)) => {
+                            self.handle_handoff_result(result);
+                        }
+                        Ok(AgentOutcome::VerificationCompleted(result)) => {
+                            self.handle_verification_result(result);
+                        }
+                        Ok(AgentOutcome::IntegrationReviewCompleted(result)) => {
+                            self.handle_integration_review_result(result);
+                        }
+                        Err(error_message) => {
+                            if self.phase == WorkflowPhase::Coding {
+                                self.handle_coding_task_error(error_message);
+                            } else {
+                                self.handle_agent_error(error_message);
+                            }
+                        }
+                    }
+                    return;
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    self.agent_result_receiver = Some(receiver);
+                    return;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.handle_agent_error("에이전트 통신이 중단되었습니다.".to_string());
+                    return;
+                }
+            }
+        }
+    }
+
+    fn journal_dir(&self) -> PathBuf {
+        if let Some(coding_state) = &self.coding_state
+            && let Some(worktree_info) = &coding_state.current_task_worktree
+            && let (Some(date), Some(name)) = (&self.session_date_dir, &self.session_name)
+        {
+            return worktree_info.worktree_path.join(".bear").join(date).join(name);
+        }
+        self.workspace_journal_dir()
+    }
+
+    fn workspace_journal_dir(&self) -> PathBuf {
+        if let Some(dir) = &self.base_journal_dir {
+            return dir.clone();
+        }
+        match (&self.session_date_dir, &self.session_name) {
+            (Some(date), Some(name)) => self.journal_root().join(".bear").join(date).join(name),
+            _ => PathBuf::new(),
+        }
+    }
+
+    /// `.bear` 저널 디렉토리를 담는 곳. 저널을 코드와 함께 두면 통합 워크트리, 아니면 코드 밖 저널 루트다.
+    fn journal_root(&self) -> PathBuf {
+        if self.journal_storage.is_in_tree() {
+            self.integration_workspace()
+        } else {
+            journal_storage::external_journal_root(&self.workspace)
+        }
+    }
+
+    /// 통합 브랜치가 체크아웃된 Bear 전용 워크트리. 만들지 못했으면 사용자의 워크스페이스를 쓴다.
+    /// 통합 브랜치로의 머지와 저널 커밋은 모두 여기서 일어나므로 사용자의 체크아웃은 바뀌지 않는다.
+    fn integration_workspace(&self) -> PathBuf {
+        self.integration_worktree
+            .clone()
+            .unwrap_or_else(|| self.workspace.clone())
+    }
+
+    /// 세션 ID를 정하고 통합 워크트리를 만든 뒤, 그 안의 새 저널 디렉토리 경로를 반환한다.
+    fn begin_session_journal(&mut self) -> PathBuf {
+        let name = session_naming::generate_session_id();
+        self.session_date_dir = Some(session_naming::today_date_string());
+        self.session_name = Some(name.clone());
+        if self.integration_branch.is_none() {
+            self.create_integration_worktree(&name);
+        }
+        if let Err(err) = journal_storage::prepare_journal_root(
+            &self.workspace,
+            self.journal_storage,
+            &self.project_config.commit_policy,
+        ) {
+            self.add_system_message(&format!(
+                "저널 브랜치 준비 실패: {}. 저널을 커밋하지 않고 코드 밖 디렉토리에 보관합니다.",
+                err,
+            ));
+            self.journal_storage = JournalStorage::External;
+        }
+        let journal_dir = self.workspace_journal_dir();
+        self.base_journal_dir = Some(journal_dir.clone());
+        journal_dir
+    }
+
+    /// 현재 HEAD에서 통합 브랜치를 만들어 전용 워크트리에 체크아웃한다.
+    fn create_integration_worktree(&mut self, session_name: &str) -> bool {
+        let workspace = self.workspace.clone();
+        self.record_integration_base_commit(&workspace);
+        match coding::create_integration_worktree(&workspace, session_name, &self.project_config.commit_policy) {
+            Ok(integration) => {
+                self.add_system_message(&format!(
+                    "통합 브랜치 생성: {}\n통합 워크트리: {}",
+                    integration.branch,
+                    integration.path.display(),
+                ));
+                self.integration_branch = Some(integration.branch);
+                self.integration_worktree = Some(integration.path);
+                true
+            }
+            Err(err) => {
+                self.add_system_message(&format!("통합 브랜치 생성 실패: {}", err));
+                false
+            }
+        }
+    }
+
+    fn commit_journal_file(&self, file_path: &Path, summary: &str, task_id: Option<&str>) -> Result<(), GitError> {
+        self.commit_journal_files(&[file_path.to_path_buf()], summary, task_id)
+    }
+
+    /// 저널 파일을 커밋 정책의 저널 메시지 템플릿으로 완성해 보관 방식에 맞는 곳에 커밋한다.
+    /// 코드 밖 디렉토리에 보관하는 저널은 커밋하지 않는다.
+    fn commit_journal_files(
+        &self,
+        file_paths: &[PathBuf],
+        summary: &str,
+        task_id: Option<&str>,
+    ) -> Result<(), GitError> {
+        let policy = &self.project_config.commit_policy;
+        let context = CommitContext {
+            session: self.session_name.as_deref().unwrap_or_default(),
+            task_id,
+        };
+        let message = policy.render_journal_message(summary, context);
+        match self.journal_storage {
+            JournalStorage::InTree | JournalStorage::OrphanBranch => {
+                coding::commit_files_in_workspace(&self.journal_root(), file_paths, &message, policy)
+            }
+            JournalStorage::External => Ok(()),
+        }
+    }
+
+    /// 새 세션의 사용자 요청과 요구사항 사본을 저널에 저장하고 통합 브랜치에 커밋한다.
+    fn commit_user_request(&mut self) {
+        let journal_dir = self.journal_dir();
+        if let Some(user_request) = &self.requirements
+            && let Err(err) = spec_writing::save_user_request(&journal_dir, user_request)
+        {
+            self.add_system_message(&format!("사용자 요청 파일 저장 실패: {}", err));
+        }
+        if self.integration_branch.is_none() {
+            return;
+        }
+
+        let user_request_path = journal_dir.join("user-request.md");
+        let mut request_files = vec![user_request_path.clone()];
+        request_files.extend(
+            self.requirement_sources
+                .iter()
+                .enumerate()
+                .map(|(index, source)| {
+                    requirements_import::source_copy_path(&journal_dir, index, source)
+                }),
+        );
+        if user_request_path.exists()
+            && let Err(err) = self.commit_journal_files(&request_files, "Add user request", None)
+        {
+            self.add_system_message(&format!("사용자 요청 파일 커밋 실패: {}", err));
+        }
+    }
+
+    fn submit_session_dir_path(&mut self, raw_path: String) -> Result<(), WorkflowError> {
+        self.activity.started_at = Some(session_naming::now_kst());
+        let resolved_dir = file_validation::validate_directory_locally(&raw_path, &self.workspace)
+            .map_err(|message| WorkflowError::InvalidSessionDirectory { message })?;
+
+        let spec_path = resolved_dir.join("spec.md");
+        if !spec_path.is_file() {
+            return Err(WorkflowError::InvalidSessionDirectory {
+                message: format!("디렉토리에 spec.md 파일이 없습니다: {}", resolved_dir.display()),
+            });
+        }
+
+        if resolved_dir.join(coding::CODING_STATE_FILE_NAME).is_file() {
+            return self.resume_coding_session(resolved_dir);
+        }
+
+        let has_plan = resolved_dir.join("plan.md").is_file();
+
+        self.resumed_session_dir = Some(resolved_dir);
+        self.resumed_has_plan = has_plan;
+        self.pending_validation_kind = Some(FileKind::Spec);
+        self.add_system_message("스펙 파일을 검증 중입니다...");
+        self.start_file_content_validation(spec_path);
+        Ok(())
+    }
+
+    /// coding-state.json이 있는 세션은 스펙/플랜 검증 없이 저장된 코딩 단계부터 이어간다.
+    /// 남은 작업이 있으면 계속 실행하고, 없으면 차단된 작업을 재실행할 수 있는 최종 검토로 간다.
+    fn resume_coding_session(&mut self, session_dir: PathBuf) -> Result<(), WorkflowError> {
+        let invalid = |message: String| WorkflowError::InvalidSessionDirectory { message };
+
+        let snapshot = coding::load_coding_state(&session_dir)
+            .map_err(|err| invalid(format!("진행 상태 파일 읽기 실패: {}", err)))?;
+        let (Some(date_dir), Some(session_name)) = (
+            session_dir.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str()),
+            session_dir.file_name().and_then(|n| n.to_str()),
+        ) else {
+            return Err(invalid(format!(
+                "세션 디렉토리 경로를 해석할 수 없습니다: {}",
+                session_dir.display()
+            )));
+        };
+        let integration_worktree = match cleanup::find_branch_worktree(&self.workspace, &snapshot.integration_branch) {
+            Some(path) => path,
+            None => coding::add_integration_worktree(&self.workspace, &snapshot.integration_branch)
+                .map_err(|err| invalid(format!("통합 워크트리 생성 실패: {}", err)))?,
+        };
+        // 사용자의 워크스페이스에서 고른 저널은 워크트리 안의 같은 위치에서 이어 간다.
+        let journal_in_worktree = session_dir
+            .strip_prefix(&self.workspace)
+            .map(|relative| integration_worktree.join(relative))
+            .ok()
+            .filter(|dir| dir.join(coding::CODING_STATE_FILE_NAME).is_file());
+        self.journal_storage = journal_storage::storage_of_session(&self.workspace, &session_dir);
+
+        self.session_date_dir = Some(date_dir.to_string());
+        self.session_name = Some(session_name.to_string());
+        self.base_journal_dir = Some(journal_in_worktree.unwrap_or(session_dir));
+        self.integration_worktree = Some(integration_worktree);
+        self.integration_branch = Some(snapshot.integration_branch.clone());
+        self.integration_base_commit = snapshot.integration_base_commit;
+        self.integration_base_branch = snapshot.integration_base_branch;
+        self.usage = snapshot.usage;
+        self.coding_state = Some(CodingPhaseState {
+            tasks: snapshot.tasks,
+            current_task_index: snapshot.next_task_index,
+            task_reports: snapshot.task_reports,
+            integration_branch: snapshot.integration_branch.clone(),
+            current_task_worktree: None,
+            verification_pipeline: None,
+        });
+
+        self.add_system_message(&format!(
+            "저장된 코딩 단계를 이어서 진행합니다.\n통합 브랜치: {}",
+            snapshot.integration_branch,
+        ));
+        if snapshot.next_task_index >= self.coding_state.as_ref().unwrap().tasks.len() {
+            // 남은 작업이 없으므로 다시 검증하지 않고 바로 최종 검토로 간다.
+            self.enter_acceptance_review(&snapshot.integration_branch);
+            return Ok(());
+        }
+        self.set_phase(WorkflowPhase::Coding);
+        self.start_next_coding_task();
+        Ok(())
+    }
+
+    fn start_file_content_validation(&mut self, path: PathBuf) {
+        if let Err(error_message) = self.ensure_claude_client() {
+            self.add_system_message(&format!("클라이언트 생성 실패: {}", error_message));
+            self.set_phase(WorkflowPhase::Done);
+            return;
+        }
+
+        let mut client = self.claude_client.take().expect("client must be available");
+        client.reset_session();
+        client.set_system_prompt(Some(file_validation::system_prompt().to_string()));
+
+        let kind = self.pending_validation_kind.unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::AgentWorking);
+
+        std::thread::spawn(move || {
+            let request = ClaudeCodeRequest {
+                user_prompt: file_validation::build_validation_prompt(&path, kind),
+                output_schema: file_validation::validation_schema(),
+            };
+
+            let outcome = client
+                .query::<FileValidationResponse>(&request)
+                .map(AgentOutcome::FileValidation)
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client,
+                outcome,
+            })));
+        });
+    }
+
+    fn handle_file_validation_result(&mut self, result: FileValidationResponse) {
+        let kind = self.pending_validation_kind.take().unwrap_or(FileKind::Spec);
+
+        if !result.valid {
+            self.add_system_message(&format!("파일 검증 실패: {}", result.reason));
+            self.resumed_session_dir = None;
+            self.resumed_has_plan = false;
+            self.set_phase(WorkflowPhase::Idle);
+            return;
+        }
+
+        match kind {
+            FileKind::Spec => {
+                let session_dir = self.resumed_session_dir.clone().unwrap();
+                let spec_path = session_dir.join("spec.md");
+                match std::fs::read_to_string(&spec_path) {
+                    Ok(content) => {
+                        self.approved_spec = Some(content);
+                        self.add_system_message("스펙 파일이 검증되었습니다.");
+
+                        if self.resumed_has_plan {
+                            let plan_path = session_dir.join("plan.md");
+                            self.pending_validation_kind = Some(FileKind::Plan);
+                            self.add_system_message("플랜 파일을 검증 중입니다...");
+                            self.start_file_content_validation(plan_path);
+                        } else {
+                            self.start_resumed_session_workflow();
+                        }
+                    }
+                    Err(err) => {
+                        self.add_system_message(&format!("스펙 파일 읽기 실패: {}", err));
+                        self.resumed_session_dir = None;
+                        self.set_phase(WorkflowPhase::Idle);
+                    }
+                }
+            }
+            FileKind::Plan => {
+                let session_dir = self.resumed_session_dir.clone().unwrap();
+                let plan_path = session_dir.join("plan.md");
+                match std::fs::read_to_string(&plan_path) {
+                    Ok(content) => {
+                        self.last_plan_draft = Some(content);
+                        self.add_system_message("플랜 파일이 검증되었습니다.");
+                        self.start_resumed_session_workflow();
+                    }
+                    Err(err) => {
+                        self.add_system_message(&format!("플랜 파일 읽기 실패: {}", err));
+                        self.resumed_session_dir = None;
+                        self.resumed_has_plan = false;
+                        self.set_phase(WorkflowPhase::Idle);
+                    }
+                }
+            }
+        }
+    }
+
+    fn start_resumed_session_workflow(&mut self) {
+        if let Err(error_message) = self.ensure_claude_client() {
+            self.add_system_message(&format!("클라이언트 생성 실패: {}", error_message));
+            self.set_phase(WorkflowPhase::Done);
+            return;
+        }
+
+        let mut client = self.claude_client.take().expect("client must be available");
+        client.reset_session();
+
+        let has_plan = self.resumed_has_plan;
+        let resumed_dir = self.resumed_session_dir.clone().unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::AgentWorking);
+
+        if has_plan {
+            self.add_system_message(
+                "세션을 초기화하고 코드 구현을 시작합니다...",
+            );
+        } else {
+            self.add_system_message(
+                "세션을 초기화하고 개발 계획을 작성합니다...",
+            );
+        }
+        let new_journal_dir = self.begin_session_journal();
+
+        std::thread::spawn(move || {
+            if let Err(err) = std::fs::create_dir_all(&new_journal_dir) {
+                let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                    client,
+                    outcome: Err(format!("세션 디렉토리 생성 실패: {}", err)),
+                })));
+                return;
+            }
+
+            let _ = sender.send(AgentStreamMessage::JournalCreated);
+
+            // spec.md를 이전 세션에서 새 세션 디렉토리로 복사
+            let source_spec = resumed_dir.join("spec.md");
+            let dest_spec = new_journal_dir.join("spec.md");
+            if let Err(err) = std::fs::copy(&source_spec, &dest_spec) {
+                let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                    client,
+                    outcome: Err(format!("스펙 파일 복사 실패: {}", err)),
+                })));
+                return;
+            }
+
+            // user-request.md 생성
+            let user_request_content = format!(
+                "{} 세션으로부터 재시작 되었음.",
+                resumed_dir.display()
+            );
+            let user_request_path = new_journal_dir.join("user-request.md");
+            let _ = std::fs::write(&user_request_path, &user_request_content);
+
+            if has_plan {
+                // plan.md를 이전 세션에서 새 세션 디렉토리로 복사
+                let source_plan = resumed_dir.join("plan.md");
+                let dest_plan = new_journal_dir.join("plan.md");
+                if let Err(err) = std::fs::copy(&source_plan, &dest_plan) {
+                    let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                        client,
+                        outcome: Err(format!("플랜 파일 복사 실패: {}", err)),
+                    })));
+                    return;
+                }
+
+                // 태스크 추출 시작
+                client.set_system_prompt(
+                    Some(coding::task_extraction_system_prompt().to_string()),
+                );
+
+                let request = ClaudeCodeRequest {
+                    user_prompt: coding::build_task_extraction_prompt(&dest_plan),
+                    output_schema: coding::task_extraction_schema(),
+                };
+
+                let stream_sender = sender.clone();
+                let outcome = client
+                    .query_streaming::<TaskExtractionResponse, _>(&request, |line| {
+                        let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                    })
+                    .map(AgentOutcome::TaskExtraction)
+                    .map_err(|err| err.to_string());
+
+                let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                    client,
+                    outcome,
+                })));
+            } else {
+                // 플랜 작성 시작
+                client.set_system_prompt(Some(planning::system_prompt().to_string()));
+
+                let request = ClaudeCodeRequest {
+                    user_prompt: planning::build_initial_plan_prompt(
+                        &user_request_path,
+                        &dest_spec,
+                    ),
+                    output_schema: planning::plan_writing_schema(),
+                };
+
+                let stream_sender = sender.clone();
+                let outcome = client
+                    .query_streaming::<PlanWritingResponse, _>(&request, |line| {
+                        let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                    })
+                    .map(AgentOutcome::Planning)
+                    .map_err(|err| err.to_string());
+
+                let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                    client,
+                    outcome,
+                })));
+            }
+        });
+    }
+
+    /// `@<경로>` 줄로 지정한 파일, 디렉토리, 이슈 JSON은 읽어서 입력한 텍스트와 함께 하나의 요청 문서로 합친다.
+    fn submit_requirements(&mut self, input: String) -> Result<(), WorkflowError> {
+        self.activity.started_at = Some(session_naming::now_kst());
+        let (typed_text, references) = requirements_import::split_import_references(&input);
+        let sources = requirements_import::load_sources(&references, &self.workspace)
+            .map_err(|message| WorkflowError::InvalidRequirementsSource { message })?;
+        if !sources.is_empty() {
+            self.add_system_message(&format!("요구사항 파일 {}개를 가져왔습니다.", sources.len()));
+        }
+
+        let requirements = requirements_import::build_user_request_document(&typed_text, &sources);
+        self.last_user_input = Some(requirements.clone());
+        self.requirements = Some(requirements);
+        self.requirement_sources = sources;
+
+        if let Err(error_message) = self.ensure_claude_client() {
+            self.add_system_message(&format!("클라이언트 생성 실패: {}", error_message));
+            self.set_phase(WorkflowPhase::Done);
+            return Ok(());
+        }
+
+        self.add_system_message("요구사항을 분석 중입니다. 잠시만 기다려 주세요.");
+        self.start_clarification_query();
+        Ok(())
+    }
+
+    /// 현재 질문에 대한 답변을 기록하고, 남은 질문이 있으면 다음 질문을 보여준다.
+    /// 모든 질문에 답하면 질문별 답변을 한 라운드로 Q&A 로그에 남기고 다음 분석을 시작한다.
+    fn submit_clarification_answer(&mut self, input: String) {
+        self.last_user_input = Some(input.clone());
+
+        let position = self.current_round_answers.len();
+        let Some(question) = self.current_round_questions.get(position).cloned() else {
+            return;
+        };
+        self.current_round_answers.push(QuestionAnswer {
+            answer: clarification::parse_question_answer(&input, &question),
+            question: question.question,
+        });
+
+        if self.current_round_answers.len() < self.current_round_questions.len() {
+            self.show_current_clarification_question();
+            return;
+        }
+
+        self.current_round_questions.clear();
+        let answers = std::mem::take(&mut self.current_round_answers);
+        self.qa_log.push(QaRound { answers });
+
+        self.add_system_message("답변을 분석 중입니다. 잠시만 기다려 주세요.");
+        self.start_clarification_query();
+    }
+
+    fn show_current_clarification_question(&mut self) {
+        let position = self.current_round_answers.len();
+        let total = self.current_round_questions.len();
+        if let Some(question) = self.current_round_questions.get(position) {
+            let message = clarification::format_question_prompt(question, position, total);
+            self.add_system_message(&message);
+        }
+    }
+
+    fn new_agent_client(
+        &self,
+        working_directory: PathBuf,
+        system_prompt: Option<String>,
+    ) -> Result<ClaudeCodeClient, ClaudeCodeClientError> {
+        match &self.agent_binary {
+            Some(binary_path) => ClaudeCodeClient::with_binary(
+                binary_path.clone(),
+                self.api_key.clone(),
+                working_directory,
+                system_prompt,
+            ),
+            None => ClaudeCodeClient::new(self.api_key.clone(), working_directory, system_prompt),
+        }
+    }
+
+    fn ensure_claude_client(&mut self) -> Result<(), String> {
+        if self.claude_client.is_some() {
+            return Ok(());
+        }
+
+        let workspace = self.workspace.clone();
+        let client = self.new_agent_client(
+            workspace,
+            Some(clarification::system_prompt().to_string()),
+        )
+            .map_err(|err| err.to_string())?;
+
+        self.claude_client = Some(client);
+        Ok(())
+    }
+
+    fn start_clarification_query(&mut self) {
+        let mut client = self.claude_client.take().expect("client must be available");
+        let original_request = self.requirements.clone().unwrap();
+        let qa_log = self.qa_log.clone();
+        let sources = self.requirement_sources.clone();
+        let is_new_session = self.session_name.is_none();
+        let journal_dir = if is_new_session {
+            self.begin_session_journal()
+        } else {
+            self.journal_dir()
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::AgentWorking);
+
+        std::thread::spawn(move || {
+            if is_new_session {
+                // 에이전트가 첫 질문부터 사본을 읽을 수 있도록 쿼리 전에 복사한다.
+                if let Err(err) = requirements_import::copy_sources_to_journal(&journal_dir, &sources) {
+                    let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                        client,
+                        outcome: Err(format!("요구사항 파일 복사 실패: {}", err)),
+                    })));
+                    return;
+                }
+                let _ = sender.send(AgentStreamMessage::JournalCreated);
+            }
+            let attached_files: Vec<PathBuf> = sources
+                .iter()
+                .enumerate()
+                .map(|(index, source)| requirements_import::source_copy_path(&journal_dir, index, source))
+                .collect();
+
+            let request = ClaudeCodeRequest {
+                user_prompt: clarification::build_user_prompt(
+                    &original_request,
+                    &attached_files,
+                    &qa_log,
+                ),
+                output_schema: clarification::clarification_schema(),
+            };
+
+            let stream_sender = sender.clone();
+            let outcome = client
+                .query_streaming::<ClarificationQuestions, _>(&request, |line| {
+                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                })
+                .map(AgentOutcome::Clarification)
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult { client, outcome })));
+        });
+    }
+
+    fn handle_clarification_response(&mut self, response: ClarificationQuestions) {
+        if response.questions.is_empty() {
+            self.add_system_message("요구사항 분석이 완료되었습니다. 스펙 문서를 작성합니다.");
+            self.start_spec_writing_query(true);
+            return;
+        }
+
+        self.add_system_message(&format!(
+            "스펙 작성을 위해 {}개의 질문에 하나씩 답변해 주세요.",
+            response.questions.len(),
+        ));
+        self.current_round_questions = response.questions;
+        self.current_round_answers.clear();
+        self.show_current_clarification_question();
+        self.set_phase(WorkflowPhase::ClarificationAnswer);
+    }
+
+    fn handle_agent_error(&mut self, error_message: String) {
+        self.add_system_message(&format!("에이전트 오류: {}", error_message));
+        self.fatal_error = Some(error_message);
+        self.set_phase(WorkflowPhase::Failed);
+    }
+
+    fn start_spec_writing_query(&mut self, is_initial: bool) {
+        let mut client = self.claude_client.take().expect("client must be available");
+
+        let qa_log = self.qa_log.clone();
+        let user_request_path = self.journal_dir().join("user-request.md");
+        let user_feedback = if is_initial {
+            None
+        } else {
+            self.last_user_input.clone()
+        };
+        let send_full_revision_instructions = if is_initial {
+            false
+        } else {
+            let should_send = !self.spec_revision_instructions_sent;
+            self.spec_revision_instructions_sent = true;
+            should_send
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::AgentWorking);
+
+        std::thread::spawn(move || {
+            let user_prompt = if is_initial {
+                spec_writing::build_initial_spec_prompt(&user_request_path, &qa_log)
+            } else {
+                let feedback = user_feedback.unwrap_or_default();
+                if send_full_revision_instructions {
+                    spec_writing::build_revision_prompt(&feedback)
+                } else {
+                    spec_writing::build_followup_revision_prompt(&feedback)
+                }
+            };
+
+            let request = ClaudeCodeRequest {
+                user_prompt,
+                output_schema: spec_writing::spec_writing_schema(),
+            };
+
+            let stream_sender = sender.clone();
+            let outcome = client
+                .query_streaming::<SpecWritingResponse, _>(&request, |line| {
+                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                })
+                .map(AgentOutcome::SpecWriting)
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client,
+                outcome,
+            })));
+        });
+    }
+
+    fn handle_spec_response(&mut self, response: SpecWritingResponse) {
+        match response.response_type {
+            SpecResponseType::SpecDraft => {
+                let draft = response.spec_draft.unwrap_or_default();
+
+                self.record_draft_revision(FileKind::Spec, draft.clone());
+                self.add_system_message(&format!(
+                    "스펙 드래프트가 작성되었습니다. {}\n\n{}",
+                    self.spec_revisions.describe_latest("스펙"),
+                    DRAFT_FEEDBACK_HELP,
+                ));
+                self.last_spec_draft = Some(draft);
+                self.set_phase(WorkflowPhase::SpecFeedback);
+            }
+            SpecResponseType::ClarifyingQuestions => {
+                let questions = response.clarifying_questions.unwrap_or_default();
+
+                let mut message = String::from("스펙 작성을 위해 추가 정보가 필요합니다.\n");
+                for (i, question) in questions.iter().enumerate() {
+                    message.push_str(&format!("\n{}. {}", i + 1, question));
+                }
+
+                self.spec_clarification_questions = questions;
+                self.add_system_message(&message);
+                self.set_phase(WorkflowPhase::SpecClarificationAnswer);
+            }
+            SpecResponseType::Approved => {
+                self.approve_spec();
+            }
+        }
+    }
+
+    fn submit_spec_clarification_answer(&mut self, answer: String) {
+        self.set_revision_input(answer);
+
+        self.add_system_message("답변을 반영하여 스펙을 작성합니다.");
+        self.start_spec_writing_query(false);
+    }
+
+    fn submit_spec_feedback(&mut self, feedback: String) {
+        if let Some(version) = revision::parse_rollback_command(&feedback) {
+            self.rollback_draft(FileKind::Spec, version);
+            return;
+        }
+        self.set_revision_input(feedback);
+
+        self.add_system_message("피드백을 반영하여 스펙을 수정합니다.");
+        self.start_spec_writing_query(false);
+    }
+
+    fn approve_spec(&mut self) {
+        let spec = match &self.last_spec_draft {
+            Some(spec) => spec.clone(),
+            None => {
+                self.add_system_message("승인할 스펙이 없습니다.");
+                return;
+            }
+        };
+
+        self.approved_spec = Some(spec.clone());
+
+        let journal_dir = self.journal_dir();
+        if let Err(err) = spec_writing::save_approved_spec(&journal_dir, &spec) {
+            self.add_system_message(&format!("스펙 파일 저장 실패: {}", err));
+        }
+
+        let mut spec_files = vec![journal_dir.join("spec.md")];
+        spec_files.extend(self.spec_revisions.saved_file_paths(&journal_dir));
+        self.pending_rollback_note = None;
+        if let Err(err) = self.commit_journal_files(&spec_files, "Add approved specification", None) {
+            self.add_system_message(&format!("스펙 파일 커밋 실패: {}", err));
+        }
+
+        self.add_system_message("스펙이 승인되었습니다. 개발 계획을 작성합니다.");
+        self.start_plan_writing_query(true);
+    }
+
+    fn start_plan_writing_query(&mut self, is_initial: bool) {
+        let mut client = self.claude_client.take().expect("client must be available");
+
+        if is_initial {
+            client.reset_session();
+            client.set_system_prompt(Some(planning::system_prompt().to_string()));
+        }
+
+        let journal_dir = self.journal_dir();
+        let user_request_path = journal_dir.join("user-request.md");
+        let spec_path = journal_dir.join("spec.md");
+        let user_feedback = if is_initial {
+            None
+        } else {
+            self.last_user_input.clone()
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::AgentWorking);
+
+        std::thread::spawn(move || {
+            let user_prompt = if is_initial {
+                planning::build_initial_plan_prompt(&user_request_path, &spec_path)
+            } else {
+                let feedback = user_feedback.unwrap_or_default();
+                planning::build_plan_revision_prompt(&feedback)
+            };
+
+            let request = ClaudeCodeRequest {
+                user_prompt,
+                output_schema: planning::plan_writing_schema(),
+            };
+
+            let stream_sender = sender.clone();
+            let outcome = client
+                .query_streaming::<PlanWritingResponse, _>(&request, |line| {
+                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                })
+                .map(AgentOutcome::Planning)
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client,
+                outcome,
+            })));
+        });
+    }
+
+    fn handle_plan_response(&mut self, response: PlanWritingResponse) {
+        match response.response_type {
+            PlanResponseType::PlanDraft => {
+                let draft = response.plan_draft.unwrap_or_default();
+
+                self.record_draft_revision(FileKind::Plan, draft.clone());
+                self.add_system_message(&format!(
+                    "개발 계획 드래프트가 작성되었습니다. {}\n\n{}",
+                    self.plan_revisions.describe_latest("개발 계획"),
+                    DRAFT_FEEDBACK_HELP,
+                ));
+                self.last_plan_draft = Some(draft);
+                self.set_phase(WorkflowPhase::PlanFeedback);
+            }
+            PlanResponseType::ClarifyingQuestions => {
+                let questions = response.clarifying_questions.unwrap_or_default();
+
+                let mut message = String::from("개발 계획 작성을 위해 추가 정보가 필요합니다.\n");
+                for (i, question) in questions.iter().enumerate() {
+                    message.push_str(&format!("\n{}. {}", i + 1, question));
+                }
+
+                self.plan_clarification_questions = questions;
+                self.add_system_message(&message);
+                self.set_phase(WorkflowPhase::PlanClarificationAnswer);
+            }
+            PlanResponseType::Approved => {
+                self.approve_plan();
+            }
+        }
+    }
+
+    fn submit_plan_clarification_answer(&mut self, answer: String) {
+        self.set_revision_input(answer);
+
+        self.add_system_message("답변을 반영하여 개발 계획을 작성합니다.");
+        self.start_plan_writing_query(false);
+    }
+
+    fn submit_plan_feedback(&mut self, feedback: String) {
+        if let Some(version) = revision::parse_rollback_command(&feedback) {
+            self.rollback_draft(FileKind::Plan, version);
+            return;
+        }
+        self.set_revision_input(feedback);
+
+        self.add_system_message("피드백을 반영하여 개발 계획을 수정합니다.");
+        self.start_plan_writing_query(false);
+    }
+
+    fn revisions_mut(&mut self, kind: FileKind) -> &mut RevisionHistory {
+        match kind {
+            FileKind::Spec => &mut self.spec_revisions,
+            FileKind::Plan => &mut self.plan_revisions,
+        }
+    }
+
+    /// 다음 리비전을 만들 사용자 입력을 기록한다. 직전에 롤백했다면 에이전트가 롤백된 드래프트를
+    /// 기준으로 수정하도록 안내문을 앞에 붙여 보낸다.
+    fn set_revision_input(&mut self, input: String) {
+        let agent_input = match self.pending_rollback_note.take() {
+            Some(note) => format!("{}\n\n{}", note, input),
+            None => input.clone(),
+        };
+        self.revision_feedback = Some(input);
+        self.last_user_input = Some(agent_input);
+    }
+
+    /// 새 드래프트를 다음 리비전으로 추가하고 저널에 저장한다.
+    fn record_draft_revision(&mut self, kind: FileKind, draft: String) -> Option<PathBuf> {
+        let journal_dir = self.journal_dir();
+        let feedback = self.revision_feedback.take();
+        let history = self.revisions_mut(kind);
+        let version = history.push(draft);
+
+        match history.save(&journal_dir, version, feedback.as_deref()) {
+            Ok(path) => Some(path),
+            Err(err) => {
+                self.add_system_message(&format!("리비전 파일 저장 실패: {}", err));
+                None
+            }
+        }
+    }
+
+    /// 이전 리비전의 내용을 새 리비전으로 복원한다. 기록을 지우지 않으므로 롤백 자체도 되돌릴 수 있다.
+    fn rollback_draft(&mut self, kind: FileKind, version: Result<usize, String>) {
+        let version = match version {
+            Ok(version) => version,
+            Err(message) => {
+                self.add_system_message(&message);
+                return;
+            }
+        };
+        let Some(draft) = self.revisions_mut(kind).get(version).cloned() else {
+            let latest = self.revisions_mut(kind).latest_version();
+            self.add_system_message(&format!(
+                "v{} 리비전이 없습니다. 1부터 {} 사이의 번호를 입력하세요.",
+                version, latest,
+            ));
+            return;
+        };
+
+        self.revision_feedback = Some(format!("/rollback {}", version));
+        let Some(revision_path) = self.record_draft_revision(kind, draft.clone()) else {
+            return;
+        };
+        self.pending_rollback_note = Some(revision::build_rollback_note(version, &revision_path));
+
+        let description = match kind {
+            FileKind::Spec => {
+                self.last_spec_draft = Some(draft);
+                self.spec_revisions.describe_latest("스펙")
+            }
+            FileKind::Plan => {
+                self.last_plan_draft = Some(draft);
+                self.plan_revisions.describe_latest("개발 계획")
+            }
+        };
+        self.add_system_message(&format!(
+            "v{}로 롤백했습니다. {}\n\n{}",
+            version, description, DRAFT_FEEDBACK_HELP,
+        ));
+    }
+
+    fn approve_plan(&mut self) {
+        let plan = match &self.last_plan_draft {
+            Some(plan) => plan.clone(),
+            None => {
+                self.add_system_message("승인할 개발 계획이 없습니다.");
+                return;
+            }
+        };
+
+        let journal_dir = self.journal_dir();
+        if let Err(err) = planning::save_approved_plan(&journal_dir, &plan) {
+            self.add_system_message(&format!("플랜 파일 저장 실패: {}", err));
+        }
+
+        let mut plan_files = vec![journal_dir.join("plan.md")];
+        plan_files.extend(self.plan_revisions.saved_file_paths(&journal_dir));
+        self.pending_rollback_note = None;
+        if let Err(err) = self.commit_journal_files(&plan_files, "Add approved development plan", None) {
+            self.add_system_message(&format!("플랜 파일 커밋 실패: {}", err));
+        }
+
+        self.add_system_message("개발 계획이 승인되었습니다. 작업 목록을 추출합니다.");
+        self.start_task_extraction();
+    }
+
+    fn start_task_extraction(&mut self) {
+        let mut client = self.claude_client.take().expect("client must be available");
+        client.reset_session();
+        client.set_system_prompt(Some(coding::task_extraction_system_prompt().to_string()));
+
+        let plan_path = self.journal_dir().join("plan.md");
+
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::AgentWorking);
+
+        std::thread::spawn(move || {
+            let request = ClaudeCodeRequest {
+                user_prompt: coding::build_task_extraction_prompt(&plan_path),
+                output_schema: coding::task_extraction_schema(),
+            };
+
+            let stream_sender = sender.clone();
+            let outcome = client
+                .query_streaming::<TaskExtractionResponse, _>(&request, |line| {
+                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                })
+                .map(AgentOutcome::TaskExtraction)
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client,
+                outcome,
+            })));
+        });
+    }
+
+    fn handle_task_extraction_response(&mut self, response: TaskExtractionResponse) {
+        if response.tasks.is_empty() {
+            self.add_system_message("추출된 작업이 없습니다.");
+            self.set_phase(WorkflowPhase::Done);
+            return;
+        }
+
+        self.add_system_message(&format!(
+            "{}개 작업이 추출되었습니다.\n\n{}",
+            response.tasks.len(),
+            task_review::format_task_list(&response.tasks),
+        ));
+        self.add_system_message(task_review::TASK_REVIEW_HELP);
+        self.reviewed_tasks = response.tasks;
+        self.set_phase(WorkflowPhase::TaskReview);
+    }
+
+    fn submit_task_review_command(&mut self, input: String) {
+        let result = task_review::parse_task_review_command(&input).and_then(|command| {
+            task_review::apply_task_review_command(&mut self.reviewed_tasks, command)
+        });
+
+        match result {
+            Ok(()) => {
+                let task_list = task_review::format_task_list(&self.reviewed_tasks);
+                self.add_system_message(&task_list);
+            }
+            Err(err) => self.add_system_message(&err),
+        }
+    }
+
+    fn confirm_task_list(&mut self) {
+        if let Err(err) = task_review::validate_task_graph(&self.reviewed_tasks) {
+            self.add_system_message(&err);
+            return;
+        }
+
+        let tasks = std::mem::take(&mut self.reviewed_tasks);
+        self.start_coding_phase(tasks);
+    }
+
+    fn start_coding_phase(&mut self, tasks: Vec<CodingTask>) {
+        let integration_branch = match &self.integration_branch {
+            Some(branch) => branch.clone(),
+            None => {
+                let session_name = self
+                    .session_name
+                    .clone()
+                    .unwrap_or_else(|| "unnamed".to_string());
+                if !self.create_integration_worktree(&session_name) {
+                    self.set_phase(WorkflowPhase::Done);
+                    return;
+                }
+                self.integration_branch.clone().unwrap()
+            }
+        };
+
+        self.save_and_commit_task_list(&tasks);
+
+        self.add_system_message(&format!(
+            "코딩 워크스페이스 준비 완료.\n통합 브랜치: {}",
+            integration_branch,
+        ));
+
+        self.coding_state = Some(CodingPhaseState {
+            tasks,
+            current_task_index: 0,
+            task_reports: Vec::new(),
+            integration_branch,
+            current_task_worktree: None,
+            verification_pipeline: None,
+        });
+
+        self.start_next_coding_task();
+    }
+
+    fn save_and_commit_task_list(&mut self, tasks: &[CodingTask]) {
+        let journal_dir = self.workspace_journal_dir();
+        let tasks_path = match task_review::save_tasks(&journal_dir, tasks) {
+            Ok(path) => path,
+            Err(err) => {
+                self.add_system_message(&format!("작업 목록 파일 저장 실패: {}", err));
+                return;
+            }
+        };
+
+        if let Err(err) =
+            self.commit_journal_file(&tasks_path, "Add reviewed task list", None)
+        {
+            self.add_system_message(&format!("작업 목록 파일 커밋 실패: {}", err));
+        }
+    }
+
+    /// 다음 코딩 태스크에 필요한 데이터를 추출한다.
+    /// 남은 태스크가 없으면 None을 반환한다.
+    fn extract_next_coding_task_data(&self) -> Option<NextCodingTask> {
+        let coding_state = self.coding_state.as_ref()?;
+        if coding_state.current_task_index >= coding_state.tasks.len() {
+            return None;
+        }
+
+        let task = coding_state.tasks[coding_state.current_task_index].clone();
+        let total = coding_state.tasks.len();
+        let index = coding_state.current_task_index;
+        let upstream_report_paths = match task.kind {
+            CodingTaskKind::Implementation => {
+                coding::collect_upstream_report_paths(&task, &coding_state.task_reports)
+            }
+            CodingTaskKind::Documentation => {
+                documentation::collect_merged_report_paths(&coding_state.task_reports)
+            }
+        };
+        let upstream_handoff_paths = handoff::collect_upstream_handoff_paths(
+            &task,
+            &coding_state.tasks,
+            &self.workspace_journal_dir(),
+        );
+
+        Some(NextCodingTask {
+            task,
+            total,
+            index,
+            upstream_report_paths,
+            upstream_handoff_paths,
+        })
+    }
+
+    fn start_next_coding_task(&mut self) {
+        let extracted = self.extract_next_coding_task_data();
+        let NextCodingTask {
+            task,
+            total,
+            index,
+            upstream_report_paths,
+            upstream_handoff_paths,
+        } = match extracted {
+            Some(data) => data,
+            None => {
+                self.finish_coding_phase();
+                return;
+            }
+        };
+
+        self.add_system_message(&format!(
+            "작업 {}/{} 시작: [{}] {}",
+            index + 1,
+            total,
+            task.task_id,
+            task.title,
+        ));
+        self.activity.start_task(&task.task_id, session_naming::now_kst());
+        self.current_task_trace = TaskTrace::default();
+
+        let workspace = self.workspace.clone();
+        let integration_branch = self
+            .coding_state
+            .as_ref()
+            .unwrap()
+            .integration_branch
+            .clone();
+
+        let task_branch =
+            match coding::create_task_branch(
+                &workspace,
+                &integration_branch,
+                self.session_name.as_deref().unwrap_or_default(),
+                &task.task_id,
+                &self.project_config.commit_policy,
+            ) {
+                Ok(branch) => branch,
+                Err(err) => {
+                    self.add_system_message(&format!("태스크 브랜치 생성 실패: {}", err));
+                    self.save_and_advance_task(
+                        task.task_id.clone(),
+                        CodingTaskStatus::ImplementationBlocked,
+                        format!("태스크 브랜치 생성 실패: {}", err),
+                    );
+                    return;
+                }
+            };
+
+        let worktree = coding::snapshot_refs(&workspace)
+            .and_then(|ref_snapshot| Ok((coding::create_worktree(&workspace, &task_branch)?, ref_snapshot)));
+        let (worktree_path, ref_snapshot) = match worktree {
+            Ok(worktree) => worktree,
+            Err(err) => {
+                self.add_system_message(&format!("워크트리 생성 실패: {}", err));
+                let _ = coding::delete_branch(&workspace, &task_branch);
+                self.save_and_advance_task(
+                    task.task_id.clone(),
+                    CodingTaskStatus::ImplementationBlocked,
+                    format!("워크트리 생성 실패: {}", err),
+                );
+                return;
+            }
+        };
+
+        self.add_system_message(&format!(
+            "태스크 워크트리 생성: {}\n브랜치: {}",
+            worktree_path.display(),
+            task_branch,
+        ));
+
+        let coding_state = self.coding_state.as_mut().unwrap();
+        coding_state.current_task_worktree = Some(TaskWorktreeInfo {
+            worktree_path: worktree_path.clone(),
+            task_branch,
+            ref_snapshot,
+        });
+
+        let worktree_journal = self.journal_dir();
+        let workspace_journal = self.workspace_journal_dir();
+        for err in coding::copy_artifacts_to_worktree(
+            &workspace_journal,
+            &worktree_journal,
+            &["spec.md", "plan.md"],
+        ) {
+            self.add_system_message(&err);
+        }
+        if !self.journal_storage.is_in_tree()
+            && let Err(err) = journal_storage::hide_from_git(&worktree_journal)
+        {
+            self.add_system_message(&format!("워크트리 저널 사본 제외 설정 실패: {}", err));
+        }
+
+        let journal_dir = self.journal_dir();
+        let spec_path = journal_dir.join("spec.md");
+        let plan_path = journal_dir.join("plan.md");
+
+        let system_prompt = match task.kind {
+            CodingTaskKind::Implementation => coding::coding_agent_system_prompt(),
+            CodingTaskKind::Documentation => documentation::system_prompt(),
+        };
+        let base_revision = self
+            .integration_base_commit
+            .clone()
+            .unwrap_or_else(|| "HEAD".to_string());
+        let commit_instructions = self.project_config.commit_policy.agent_commit_instructions(CommitContext {
+            session: self.session_name.as_deref().unwrap_or_default(),
+            task_id: Some(&task.task_id),
+        });
+
+        let mut client = match self.new_agent_client(
+            worktree_path,
+            Some(format!("{}{}", system_prompt, commit_instructions)),
+        ) {
+            Ok(c) => c,
+            Err(err) => {
+                self.add_system_message(&format!(
+                    "코딩 에이전트 클라이언트 생성 실패: {}",
+                    err,
+                ));
+                self.cleanup_current_task_worktree();
+                self.save_and_advance_task(
+                    task.task_id.clone(),
+                    CodingTaskStatus::ImplementationBlocked,
+                    format!("코딩 에이전트 클라이언트 생성 실패: {}", err),
+                );
+                return;
+            }
+        };
+        client.set_environment(self.project_config.commit_policy.agent_environment());
+
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::Coding);
+
+        std::thread::spawn(move || {
+            let user_prompt = match task.kind {
+                CodingTaskKind::Implementation => coding::build_coding_task_prompt(
+                    &task,
+                    &spec_path,
+                    &plan_path,
+                    &upstream_report_paths,
+                    &upstream_handoff_paths,
+                    &integration_branch,
+                ),
+                CodingTaskKind::Documentation => documentation::build_documentation_prompt(
+                    &task,
+                    &spec_path,
+                    &plan_path,
+                    &upstream_report_paths,
+                    &integration_branch,
+                    &base_revision,
+                ),
+            };
+
+            let request = ClaudeCodeRequest {
+                user_prompt,
+                output_schema: coding::coding_task_result_schema(),
+            };
+
+            let stream_sender = sender.clone();
+            let outcome = client
+                .query_streaming::<CodingTaskResult, _>(&request, |line| {
+                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                })
+                .map(AgentOutcome::CodingTaskCompleted)
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client,
+                outcome,
+            })));
+        });
+    }
+
+    fn handle_coding_task_result(&mut self, result: CodingTaskResult) {
+        let task_id = {
+            let coding_state = self.coding_state.as_ref().unwrap();
+            coding_state.tasks[coding_state.current_task_index]
+                .task_id
+                .clone()
+        };
+
+        let status_label = match &result.status {
+            CodingTaskStatus::ImplementationSuccess => "SUCCESS",
+            CodingTaskStatus::ImplementationBlocked => "BLOCKED",
+        };
+        self.add_system_message(&format!(
+            "작업 [{}] 완료: {}",
+            task_id, status_label,
+        ));
+        self.current_task_trace.requirement_tests = result.requirement_tests;
+
+        if result.status == CodingTaskStatus::ImplementationBlocked {
+            self.review_state = None;
+            self.cleanup_current_task_worktree();
+            self.save_and_advance_task(task_id, result.status, result.report);
+            return;
+        }
+
+        let coding_client = self.claude_client.take();
+
+        match self.review_state.as_mut() {
+            None => {
+                self.review_state = Some(ReviewState {
+                    task_id: task_id.clone(),
+                    report: result.report.clone(),
+                    iteration_count: 0,
+                    reviewer_client: None,
+                    coding_client,
+                    commit_check_count: 0,
+                });
+            }
+            Some(rs) => {
+                rs.report = result.report.clone();
+                rs.coding_client = coding_client;
+            }
+        }
+
+        if self.verify_task_commits(&task_id) {
+            self.start_review();
+        }
+    }
+
+    /// 코딩 에이전트 턴이 끝나면 남은 변경을 자동 커밋하고, 태스크 브랜치에 새 커밋이 없거나
+    /// 다른 ref에 커밋했으면 에이전트에게 되돌려 보낸다. 리뷰로 넘어가도 되면 true를 반환한다.
+    fn verify_task_commits(&mut self, task_id: &str) -> bool {
+        self.commit_remaining_changes(task_id);
+
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let worktree_info = coding_state.current_task_worktree.as_ref().unwrap();
+        let integration_branch = coding_state.integration_branch.clone();
+        let task_branch = worktree_info.task_branch.clone();
+        let problems = match coding::check_task_commits(worktree_info, &integration_branch, &self.journal_dir()) {
+            Ok(check) => check.problems(&task_branch, &integration_branch),
+            Err(err) => {
+                self.block_reviewed_task(task_id, &format!("커밋 확인 실패: {}", err));
+                return false;
+            }
+        };
+        if problems.is_empty() {
+            return true;
+        }
+
+        let review_state = self.review_state.as_mut().unwrap();
+        review_state.commit_check_count += 1;
+        let attempt = review_state.commit_check_count;
+        let problem_list = problems.join("\n");
+        if attempt > MAX_COMMIT_CHECK_RETRIES {
+            self.block_reviewed_task(
+                task_id,
+                &format!("커밋 확인 {}회 실패:\n{}", MAX_COMMIT_CHECK_RETRIES, problem_list),
+            );
+            return false;
+        }
+
+        self.add_system_message(&format!(
+            "[{}] 커밋 확인 실패 ({}/{}). 코딩 에이전트에게 되돌려 보냅니다:\n{}",
+            task_id, attempt, MAX_COMMIT_CHECK_RETRIES, problem_list,
+        ));
+        let task = {
+            let coding_state = self.coding_state.as_ref().unwrap();
+            coding_state.tasks[coding_state.current_task_index].clone()
+        };
+        let user_prompt = coding::build_commit_fix_prompt(&task, &problems, &task_branch, &integration_branch);
+        self.start_coding_agent_turn(user_prompt);
+        false
+    }
+
+    /// 리뷰 단계에 들어온 태스크를 이유와 함께 차단하고 다음 태스크로 넘어간다.
+    fn block_reviewed_task(&mut self, task_id: &str, reason: &str) {
+        self.add_system_message(&format!("[{}] {}", task_id, reason));
+        let review_state = self.review_state.take().unwrap();
+        self.claude_client = review_state.coding_client;
+        self.cleanup_current_task_worktree();
+        self.save_and_advance_task(
+            task_id.to_string(),
+            CodingTaskStatus::ImplementationBlocked,
+            format!("{}\n\n---\n{}", review_state.report, reason),
+        );
+    }
+
+    /// 에이전트가 커밋하지 않고 남긴 변경을 태스크 커밋으로 남긴다. 워크트리를 지우면 사라지기 때문이다.
+    fn commit_remaining_changes(&mut self, task_id: &str) {
+        let worktree_path = self
+            .coding_state
+            .as_ref()
+            .unwrap()
+            .current_task_worktree
+            .as_ref()
+            .unwrap()
+            .worktree_path
+            .clone();
+        let policy = &self.project_config.commit_policy;
+        let context = CommitContext {
+            session: self.session_name.as_deref().unwrap_or_default(),
+            task_id: Some(task_id),
+        };
+        let message = policy.render_task_message(&format!("Commit remaining changes for {}", task_id), context);
+        match coding::commit_remaining_changes(&worktree_path, &self.journal_dir(), &message, policy) {
+            Ok(0) => {}
+            Ok(file_count) => self.add_system_message(&format!(
+                "[{}] 에이전트가 커밋하지 않은 변경 {}개 파일을 자동 커밋했습니다.",
+                task_id, file_count,
+            )),
+            Err(err) => self.add_system_message(&format!("[{}] 남은 변경 자동 커밋 실패: {}", task_id, err)),
+        }
+    }
+
+    fn start_review(&mut self) {
+        let review_state = self.review_state.as_ref().unwrap();
+        let is_followup = review_state.iteration_count > 0;
+        let task_id = review_state.task_id.clone();
+        let report = review_state.report.clone();
+
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let worktree_info = coding_state.current_task_worktree.as_ref().unwrap();
+        let worktree_path = worktree_info.worktree_path.clone();
+
+        let commit_range = match coding::task_commit_range(&worktree_path, &coding_state.integration_branch) {
+            Ok(range) => range,
+            Err(err) => {
+                self.add_system_message(&format!(
+                    "[{}] 태스크 커밋 범위 조회 실패: {}. 리뷰 건너뜀.",
+                    task_id, err,
+                ));
+                self.finalize_review_and_proceed();
+                return;
+            }
+        };
+
+        let journal_dir = self.journal_dir();
+
+        let report_path = match coding::save_task_report(
+            &journal_dir, &task_id, &report,
+        ) {
+            Ok(path) => path,
+            Err(err) => {
+                self.add_system_message(&format!(
+                    "[{}] 리포트 저장 실패: {}. 리뷰 건너뜀.",
+                    task_id, err,
+                ));
+                self.finalize_review_and_proceed();
+                return;
+            }
+        };
+
+        let spec_path = journal_dir.join("spec.md");
+        let plan_path = journal_dir.join("plan.md");
+        let upstream_handoff_paths = {
+            let coding_state = self.coding_state.as_ref().unwrap();
+            handoff::collect_upstream_handoff_paths(
+                &coding_state.tasks[coding_state.current_task_index],
+                &coding_state.tasks,
+                &self.workspace_journal_dir(),
+            )
+        };
+
+        let user_prompt = if is_followup {
+            coding::build_followup_review_prompt(
+                &spec_path, &plan_path, &report_path, &upstream_handoff_paths,
+                &commit_range,
+            )
+        } else {
+            coding::build_initial_review_prompt(
+                &spec_path, &plan_path, &report_path, &upstream_handoff_paths,
+                &commit_range,
+            )
+        };
+
+        let mut reviewer_client = match self.review_state.as_mut().unwrap().reviewer_client.take() {
+            Some(client) => client,
+            None => {
+                match self.new_agent_client(
+                    worktree_path.clone(),
+                    Some(coding::review_agent_system_prompt().to_string()),
+                ) {
+                    Ok(c) => c,
+                    Err(err) => {
+                        self.add_system_message(&format!(
+                            "[{}] 리뷰 에이전트 클라이언트 생성 실패: {}. 리뷰 건너뜀.",
+                            task_id, err,
+                        ));
+                        self.finalize_review_and_proceed();
+                        return;
+                    }
+                }
+            }
+        };
+        reviewer_client.set_working_directory(worktree_path);
+
+        let iteration_label = self.review_state.as_ref().unwrap().iteration_count + 1;
+        self.add_system_message(&format!(
+            "[{}] 코드 리뷰 시작 (iteration {})...",
+            task_id, iteration_label,
+        ));
+
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::Coding);
+
+        std::thread::spawn(move || {
+            let request = ClaudeCodeRequest {
+                user_prompt,
+                output_schema: coding::review_result_schema(),
+            };
+
+            let stream_sender = sender.clone();
+            let outcome = reviewer_client
+                .query_streaming::<ReviewResult, _>(&request, |line| {
+                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                })
+                .map(AgentOutcome::ReviewCompleted)
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client: reviewer_client,
+                outcome,
+            })));
+        });
+    }
+
+    fn handle_review_result(&mut self, result: ReviewResult) {
+        let reviewer_client = self.claude_client.take();
+        let review_state = self.review_state.as_mut().unwrap();
+        review_state.reviewer_client = reviewer_client;
+        review_state.iteration_count += 1;
+
+        let task_id = review_state.task_id.clone();
+        self.activity.reviews.push(ReviewRecord {
+            task_id: task_id.clone(),
+            iteration: review_state.iteration_count,
+            approved: result.review_result == ReviewStatus::Approved,
+            comment: result.review_comment.clone(),
+        });
+
+        match result.review_result {
+            ReviewStatus::Approved => {
+                self.add_system_message(&format!("[{}] 코드 리뷰 승인.", task_id));
+                self.finalize_review_and_proceed();
+            }
+            ReviewStatus::RequestChanges => {
+                let iteration_count = self.review_state.as_ref().unwrap().iteration_count;
+
+                if iteration_count >= MAX_REVIEW_ITERATIONS {
+                    self.add_system_message(&format!(
+                        "[{}] 리뷰 최대 반복 횟수({}) 도달. 자동 승인 처리.",
+                        task_id, MAX_REVIEW_ITERATIONS,
+                    ));
+                    self.finalize_review_and_proceed();
+                    return;
+                }
+
+                self.add_system_message(&format!(
+                    "[{}] 리뷰어 변경 요청 (iteration {}/{}): {}",
+                    task_id, iteration_count, MAX_REVIEW_ITERATIONS,
+                    result.review_comment,
+                ));
+
+                self.start_coding_revision(result.review_comment);
+            }
+        }
+    }
+
+    fn finalize_review_and_proceed(&mut self) {
+        let review_state = self.review_state.take().unwrap();
+        let task_id = review_state.task_id;
+        let report = review_state.report;
+
+        self.claude_client = review_state.coding_client;
+
+        self.rebase_and_merge_task(task_id, report);
+    }
+
+    fn start_coding_revision(&mut self, review_comment: String) {
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let task = coding_state.tasks[coding_state.current_task_index].clone();
+        let task_id = task.task_id.clone();
+
+        let journal_dir = self.journal_dir();
+        let spec_path = journal_dir.join("spec.md");
+        let plan_path = journal_dir.join("plan.md");
+
+        let integration_branch = coding_state.integration_branch.clone();
+        let user_prompt = coding::build_coding_revision_prompt(
+            &task, &spec_path, &plan_path, &review_comment, &integration_branch,
+        );
+
+        if self.review_state.as_ref().unwrap().coding_client.is_none() {
+            self.add_system_message(&format!(
+                "[{}] 코딩 에이전트 세션을 찾을 수 없습니다. 리뷰 자동 승인 처리.",
+                task_id,
+            ));
+            self.finalize_review_and_proceed();
+            return;
+        }
+
+        self.add_system_message(&format!(
+            "[{}] 리뷰 피드백 반영을 위한 코딩 에이전트 재시작...",
+            task_id,
+        ));
+        self.start_coding_agent_turn(user_prompt);
+    }
+
+    /// 리뷰 중인 태스크의 코딩 에이전트 세션에 다음 턴을 보낸다.
+    /// 세션이 없으면 작업을 이어갈 수 없으므로 차단 처리한다.
+    fn start_coding_agent_turn(&mut self, user_prompt: String) {
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let worktree_path = coding_state.current_task_worktree.as_ref().unwrap().worktree_path.clone();
+        let task_id = coding_state.tasks[coding_state.current_task_index].task_id.clone();
+
+        let Some(mut client) = self.review_state.as_mut().unwrap().coding_client.take() else {
+            self.block_reviewed_task(&task_id, "코딩 에이전트 세션을 찾을 수 없습니다.");
+            return;
+        };
+        client.set_working_directory(worktree_path);
+
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::Coding);
+
+        std::thread::spawn(move || {
+            let request = ClaudeCodeRequest {
+                user_prompt,
+                output_schema: coding::coding_task_result_schema(),
+            };
+
+            let stream_sender = sender.clone();
+            let outcome = client
+                .query_streaming::<CodingTaskResult, _>(&request, |line| {
+                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                })
+                .map(AgentOutcome::CodingTaskCompleted)
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client,
+                outcome,
+            })));
+        });
+    }
+
+    fn rebase_and_merge_task(
+        &mut self,
+        task_id: String,
+        report: String,
+    ) {
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let worktree_info = coding_state.current_task_worktree.as_ref().unwrap();
+        let worktree_path = worktree_info.worktree_path.clone();
+        let integration_branch = coding_state.integration_branch.clone();
+
+        self.add_system_message(&format!(
+            "[{}] 통합 브랜치로 리베이스 시작...",
+            task_id,
+        ));
+
+        match coding::rebase_onto_integration(&worktree_path, &integration_branch) {
+            Ok(RebaseOutcome::Success) => {
+                self.add_system_message(&format!("[{}] 리베이스 성공.", task_id));
+                self.verify_build_and_test(task_id, report);
+            }
+            Ok(RebaseOutcome::Conflict { conflicted_files }) => {
+                self.add_system_message(&format!(
+                    "[{}] 리베이스 충돌 발생 ({}개 파일). 충돌 해결 에이전트 시작...",
+                    task_id,
+                    conflicted_files.len(),
+                ));
+                self.start_conflict_resolution(
+                    task_id,
+                    conflicted_files,
+                    report,
+                );
+            }
+            Err(err) => {
+                self.add_system_message(&format!("[{}] 리베이스 실패: {}", task_id, err));
+                self.cleanup_current_task_worktree();
+                self.save_and_advance_task(
+                    task_id,
+                    CodingTaskStatus::ImplementationBlocked,
+                    format!("{}\n\n---\n리베이스 실패: {}", report, err),
+                );
+            }
+        }
+    }
+
+    fn handle_coding_task_error(&mut self, error_message: String) {
+        let task_id = {
+            let coding_state = self.coding_state.as_ref().unwrap();
+            coding_state.tasks[coding_state.current_task_index]
+                .task_id
+                .clone()
+        };
+
+        self.add_system_message(&format!(
+            "Task [{}] error: {}",
+            task_id, error_message,
+        ));
+
+        self.review_state = None;
+        self.cleanup_current_task_worktree();
+
+        let report = format!(
+            "IMPLEMENTATION_BLOCKED\n---\nAgent error: {}",
+            error_message,
+        );
+        let message = format!("Task [{}] error: {}", task_id, error_message);
+        self.save_and_advance_task(
+            task_id,
+            CodingTaskStatus::ImplementationBlocked,
+            report,
+        );
+        self.fatal_error = Some(message);
+        self.set_phase(WorkflowPhase::Failed);
+    }
+
+    fn cleanup_current_task_worktree(&mut self) {
+        let workspace = self.workspace.clone();
+        let coding_state = self.coding_state.as_mut().unwrap();
+        if let Some(info) = coding_state.current_task_worktree.take() {
+            if let Err(err) = coding::remove_worktree(&workspace, &info.worktree_path) {
+                self.add_system_message(&format!("워크트리 제거 실패: {}", err));
+            }
+            if let Err(err) = coding::delete_branch(&workspace, &info.task_branch) {
+                self.add_system_message(&format!("태스크 브랜치 삭제 실패: {}", err));
+            }
+        }
+    }
+
+    fn verify_build_and_test(
+        &mut self,
+        task_id: String,
+        report: String,
+    ) {
+        let worktree_path = self
+            .coding_state
+            .as_ref()
+            .unwrap()
+            .current_task_worktree
+            .as_ref()
+            .unwrap()
+            .worktree_path
+            .clone();
+
+        let already_chosen = self
+            .coding_state
+            .as_ref()
+            .unwrap()
+            .verification_pipeline
+            .is_some();
+
+        if !already_chosen {
+            match self.choose_verification_pipeline(&task_id, &worktree_path) {
+                Some(pipeline) => {
+                    self.coding_state.as_mut().unwrap().verification_pipeline = Some(pipeline);
+                }
+                None => {
+                    self.add_system_message(
+                        "검증 파이프라인을 자동 감지할 수 없습니다. 빌드 명령어를 입력해주세요:",
+                    );
+                    self.ask_build_command(task_id, report);
+                    return;
+                }
+            }
+        }
+
+        self.start_build_test_execution(task_id, report, false);
+    }
+
+    /// 프로젝트 설정에 저장된 파이프라인을 먼저 쓰고, 없으면 워크트리의 빌드 시스템에서 감지한다.
+    fn choose_verification_pipeline(
+        &mut self,
+        task_id: &str,
+        worktree_path: &Path,
+    ) -> Option<Vec<VerificationStage>> {
+        let (pipeline, source) = if !self.project_config.verification_pipeline.is_empty() {
+            (self.project_config.verification_pipeline.clone(), "프로젝트 설정")
+        } else {
+            (verification_pipeline::detect_pipeline(worktree_path)?, "빌드 시스템 감지")
+        };
+
+        let stage_lines: Vec<String> = pipeline
+            .iter()
+            .map(|stage| format!("- {}", stage.describe()))
+            .collect();
+        self.add_system_message(&format!(
+            "[{}] 검증 파이프라인 ({}):\n{}",
+            task_id,
+            source,
+            stage_lines.join("\n"),
+        ));
+        Some(pipeline)
+    }
+
+    fn ask_build_command(
+        &mut self,
+        task_id: String,
+        report: String,
+    ) {
+        self.pending_build_test = Some(PendingBuildTest {
+            task_id,
+            report,
+            is_retry: false,
+        });
+        self.build_test_command_phase = BuildTestCommandPhase::BuildCommand;
+        self.set_phase(WorkflowPhase::BuildTestCommandInput);
+    }
+
+    fn submit_build_test_command(&mut self, command: String) {
+        let phase = std::mem::replace(
+            &mut self.build_test_command_phase,
+            BuildTestCommandPhase::BuildCommand,
+        );
+
+        match phase {
+            BuildTestCommandPhase::BuildCommand => {
+                self.build_test_command_phase = BuildTestCommandPhase::TestCommand {
+                    build_command: command,
+                };
+                self.add_system_message("테스트 명령어를 입력해주세요 (예: make test):");
+            }
+            BuildTestCommandPhase::TestCommand { build_command } => {
+                let pipeline = verification_pipeline::build_and_test_stages(&build_command, &command);
+                self.save_verification_pipeline(&pipeline);
+                self.coding_state.as_mut().unwrap().verification_pipeline = Some(pipeline);
+
+                let pending = self.pending_build_test.take().unwrap();
+                self.start_build_test_execution(
+                    pending.task_id,
+                    pending.report,
+                    pending.is_retry,
+                );
+            }
+        }
+    }
+
+    /// 입력받은 파이프라인을 통합 워크트리의 프로젝트 설정에 저장하고 통합 브랜치에 커밋한다.
+    /// 사용자의 체크아웃은 건드리지 않고, 세션을 전달하면 기준 브랜치에 함께 들어가 다음 세션에서 다시 묻지 않는다.
+    fn save_verification_pipeline(&mut self, pipeline: &[VerificationStage]) {
+        let integration_workspace = self.integration_workspace();
+        let result = project_config::save_verification_pipeline(&integration_workspace, pipeline)
+            .map_err(|err| err.to_string())
+            .and_then(|path| {
+                let policy = &self.project_config.commit_policy;
+                let context = CommitContext {
+                    session: self.session_name.as_deref().unwrap_or_default(),
+                    task_id: None,
+                };
+                let message = policy.render_journal_message("Save verification pipeline", context);
+                coding::commit_files_in_workspace(&integration_workspace, std::slice::from_ref(&path), &message, policy)
+                    .map(|()| path)
+                    .map_err(|err| err.to_string())
+            });
+        match result {
+            Ok(path) => {
+                self.project_config.verification_pipeline = pipeline.to_vec();
+                self.add_system_message(&format!(
+                    "검증 파이프라인을 통합 브랜치의 {}에 저장했습니다. 세션을 전달하면 기준 브랜치에도 들어갑니다. \
+                     단계를 추가하거나 수정하려면 이 파일을 편집하세요.",
+                    path.display(),
+                ));
+            }
+            Err(err) => {
+                self.add_system_message(&format!("검증 파이프라인 저장 실패: {}", err));
+            }
+        }
+    }
+
+    fn start_build_test_execution(
+        &mut self,
+        task_id: String,
+        report: String,
+        is_retry: bool,
+    ) {
+        let pipeline = self
+            .coding_state
+            .as_ref()
+            .unwrap()
+            .verification_pipeline
+            .clone()
+            .unwrap();
+        let worktree_path = self
+            .coding_state
+            .as_ref()
+            .unwrap()
+            .current_task_worktree
+            .as_ref()
+            .unwrap()
+            .worktree_path
+            .clone();
+
+        self.add_system_message(&format!(
+            "[{}] 검증 파이프라인 실행 시작 ({}단계)...",
+            task_id,
+            pipeline.len(),
+        ));
+
+        self.pending_build_test = Some(PendingBuildTest {
+            task_id,
+            report,
+            is_retry,
+        });
+
+        let client = self.claude_client.take().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::Coding);
+
+        std::thread::spawn(move || {
+            let outcome = verification_pipeline::run_pipeline(&worktree_path, &pipeline)
+                .map(AgentOutcome::BuildTestCompleted);
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client,
+                outcome,
+            })));
+        });
+    }
+
+    fn handle_build_test_result(&mut self, outcome: PipelineOutcome) {
+        let pending = self.pending_build_test.take().unwrap();
+        self.record_build_test(&pending.task_id, &outcome);
+
+        for failure in outcome.optional_failures() {
+            self.add_system_message(&format!(
+                "[{}] 선택 단계 '{}' 실패. 머지를 막지 않습니다.",
+                pending.task_id, failure.stage.name,
+            ));
+        }
+
+        if outcome.blocking_failure().is_some() {
+            self.handle_build_test_failure(pending, outcome.failures);
+            return;
+        }
+
+        self.add_system_message(&format!(
+            "[{}] 검증 파이프라인 통과.",
+            pending.task_id,
+        ));
+        self.ff_merge_and_advance(
+            pending.task_id,
+            pending.report,
+        );
+    }
+
+    fn record_build_test(&mut self, task_id: &str, outcome: &PipelineOutcome) {
+        let blocking_failure = outcome.blocking_failure();
+        self.activity.build_tests.push(BuildTestRecord {
+            task_id: task_id.to_string(),
+            passed: blocking_failure.is_none(),
+            failed_stage: blocking_failure.map(|failure| failure.stage.name.clone()),
+            output: blocking_failure
+                .map(|failure| failure.output.clone())
+                .unwrap_or_default(),
+        });
+    }
+
+    /// 필수 단계가 실패했을 때 처음이면 선택 단계 실패까지 모두 수리 에이전트에게 넘기고, 수리 후 재실패면 차단한다.
+    fn handle_build_test_failure(
+        &mut self,
+        pending: PendingBuildTest,
+        failures: Vec<StageFailure>,
+    ) {
+        let blocking_stage_name = failures
+            .iter()
+            .find(|failure| failure.stage.required)
+            .map(|failure| failure.stage.name.clone())
+            .unwrap_or_default();
+
+        if pending.is_retry {
+            self.add_system_message(&format!(
+                "[{}] 수리 후 '{}' 단계 재실패. 태스크 차단 처리.",
+                pending.task_id, blocking_stage_name,
+            ));
+            self.cleanup_current_task_worktree();
+            self.save_and_advance_task(
+                pending.task_id,
+                CodingTaskStatus::ImplementationBlocked,
+                format!("{}\n\n---\n{}", pending.report, describe_stage_failures(&failures)),
+            );
+        } else {
+            self.add_system_message(&format!(
+                "[{}] '{}' 단계 실패. 수리 에이전트 시작...",
+                pending.task_id, blocking_stage_name,
+            ));
+            self.start_build_test_repair(
+                pending.task_id,
+                pending.report,
+                failures,
+            );
+        }
+    }
+
+    fn start_build_test_repair(
+        &mut self,
+        task_id: String,
+        report: String,
+        failures: Vec<StageFailure>,
+    ) {
+        self.pending_build_test = Some(PendingBuildTest {
+            task_id: task_id.clone(),
+            report,
+            is_retry: true,
+        });
+
+        let pipeline = self
+            .coding_state
+            .as_ref()
+            .unwrap()
+            .verification_pipeline
+            .as_ref()
+            .unwrap();
+        let user_prompt = coding::build_build_test_repair_prompt(
+            &task_id,
+            pipeline,
+            &failures,
+        );
+
+        let mut client = match self.claude_client.take() {
+            Some(c) => c,
+            None => {
+                self.add_system_message("수리 에이전트를 위한 세션을 찾을 수 없습니다.");
+                let pending = self.pending_build_test.take().unwrap();
+                self.cleanup_current_task_worktree();
+                self.save_and_advance_task(
+                    pending.task_id,
+                    CodingTaskStatus::ImplementationBlocked,
+                    format!(
+                        "{}\n\n---\n(수리 불가)\n{}",
+                        pending.report,
+                        describe_stage_failures(&failures),
+                    ),
+                );
+                return;
+            }
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::Coding);
+
+        std::thread::spawn(move || {
+            let request = ClaudeCodeRequest {
+                user_prompt,
+                output_schema: coding::build_test_repair_result_schema(),
+            };
+
+            let stream_sender = sender.clone();
+            let outcome = client
+                .query_streaming::<BuildTestRepairResult, _>(&request, |line| {
+                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                })
+                .map(AgentOutcome::BuildTestRepairCompleted)
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client,
+                outcome,
+            })));
+        });
+    }
+
+    fn handle_build_test_repair_result(&mut self, result: BuildTestRepairResult) {
+        let pending = self.pending_build_test.take().unwrap();
+
+        match result.status {
+            BuildTestRepairStatus::Fixed => {
+                self.commit_remaining_changes(&pending.task_id);
+                self.add_system_message(&format!(
+                    "[{}] 수리 에이전트 완료. 검증 파이프라인 재실행...",
+                    pending.task_id,
+                ));
+                self.start_build_test_execution(
+                    pending.task_id,
+                    pending.report,
+                    true,
+                );
+            }
+            BuildTestRepairStatus::FixFailed => {
+                self.add_system_message(&format!(
+                    "[{}] 수리 실패: {}",
+                    pending.task_id, result.report,
+                ));
+                self.cleanup_current_task_worktree();
+                self.save_and_advance_task(
+                    pending.task_id,
+                    CodingTaskStatus::ImplementationBlocked,
+                    format!(
+                        "{}\n\n---\n검증 파이프라인 수리 실패: {}",
+                        pending.report, result.report,
+                    ),
+                );
+            }
+        }
+    }
+
+    fn ff_merge_and_advance(
+        &mut self,
+        task_id: String,
+        report: String,
+    ) {
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let worktree_info = coding_state.current_task_worktree.as_ref().unwrap();
+        let worktree_path = worktree_info.worktree_path.clone();
+        let task_branch = worktree_info.task_branch.clone();
+
+        let date_dir = self.session_date_dir.clone().unwrap_or_default();
+        let session_name = self.session_name.clone().unwrap_or_default();
+
+        if self.project_config.commit_policy.squash_task_commits {
+            self.squash_current_task_commits(&task_id, &report);
+        }
+        if self.journal_storage.is_in_tree() {
+            if let Err(err) = coding::save_and_commit_task_report_in_worktree(
+                &worktree_path, &date_dir, &session_name, &task_id, &report,
+                &self.project_config.commit_policy,
+            ) {
+                self.add_system_message(&format!(
+                    "[{}] 워크트리 리포트 커밋 실패: {}. 리포트 없이 진행.",
+                    task_id, err,
+                ));
+            }
+        } else {
+            self.save_task_report_outside_code(&task_id, &report);
+        }
+
+        self.add_system_message(&format!(
+            "[{}] 통합 브랜치로 fast-forward 머지 시작...",
+            task_id,
+        ));
+
+        let report_file_path = self.workspace_journal_dir().join(format!("{}.md", task_id));
+
+        let workspace = self.integration_workspace();
+        let pre_merge_revision = coding::get_latest_commit_revision(&workspace);
+        match coding::fast_forward_merge_task_branch(
+            &workspace,
+            &task_branch,
+        ) {
+            Ok(()) => {
+                self.add_system_message(&format!("[{}] fast-forward 머지 완료.", task_id));
+                if let Ok(revision) = &pre_merge_revision {
+                    self.record_merged_commits(&workspace, revision, &task_branch);
+                }
+                match pre_merge_revision {
+                    Ok(revision) => {
+                        self.start_handoff_generation(report, report_file_path, revision);
+                    }
+                    Err(err) => {
+                        self.add_system_message(&format!(
+                            "[{}] 머지 전 커밋 조회 실패: {}. 핸드오프 문서 생성 건너뜀.",
+                            task_id, err,
+                        ));
+                        self.cleanup_current_task_worktree();
+                        self.advance_task(
+                            task_id,
+                            CodingTaskStatus::ImplementationSuccess,
+                            report,
+                            report_file_path,
+                        );
+                    }
+                }
+            }
+            Err(err) => {
+                self.add_system_message(&format!(
+                    "[{}] fast-forward 머지 실패: {}",
+                    task_id, err
+                ));
+                self.cleanup_current_task_worktree();
+                self.save_and_advance_task(
+                    task_id,
+                    CodingTaskStatus::ImplementationBlocked,
+                    format!("{}\n\n---\nfast-forward 머지 실패: {}", report, err),
+                );
+            }
+        }
+    }
+
+    /// 태스크 커밋을 보고서 요약과 요구사항/태스크 ID를 담은 커밋 하나로 합친다.
+    /// 실패하면 커밋을 그대로 둔 채 머지를 이어간다.
+    fn squash_current_task_commits(&mut self, task_id: &str, report: &str) {
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let task = &coding_state.tasks[coding_state.current_task_index];
+        let worktree_path = coding_state.current_task_worktree.as_ref().unwrap().worktree_path.clone();
+        let integration_branch = coding_state.integration_branch.clone();
+
+        let mut requirement_ids = task.requirement_ids.clone();
+        for tests in &self.current_task_trace.requirement_tests {
+            if !requirement_ids.contains(&tests.requirement_id) {
+                requirement_ids.push(tests.requirement_id.clone());
+            }
+        }
+        let policy = &self.project_config.commit_policy;
+        let context = CommitContext {
+            session: self.session_name.as_deref().unwrap_or_default(),
+            task_id: Some(task_id),
+        };
+        let message = policy.render_task_message(&coding::build_squash_summary(task, report, &requirement_ids), context);
+
+        match coding::squash_task_commits(&worktree_path, &integration_branch, &message, policy) {
+            Ok(commit_count) => self.add_system_message(&format!(
+                "[{}] 태스크 커밋 {}개를 하나로 합쳤습니다.",
+                task_id, commit_count,
+            )),
+            Err(err) => self.add_system_message(&format!(
+                "[{}] 태스크 커밋 합치기 실패: {}. 커밋을 그대로 머지합니다.",
+                task_id, err,
+            )),
+        }
+    }
+
+    /// 저널을 코드 밖에 보관하면 태스크 리포트를 태스크 커밋 대신 저널에 저장하고 저널 보관소에 커밋한다.
+    fn save_task_report_outside_code(&mut self, task_id: &str, report: &str) {
+        let saved = coding::save_task_report(&self.workspace_journal_dir(), task_id, report)
+            .map_err(|err| err.to_string())
+            .and_then(|path| {
+                let summary = format!("Add implementation report for {}", task_id);
+                self.commit_journal_file(&path, &summary, Some(task_id))
+                    .map_err(|err| err.to_string())
+            });
+        if let Err(err) = saved {
+            self.add_system_message(&format!("[{}] 저널 리포트 저장 실패: {}", task_id, err));
+        }
+    }
+
+    /// 병합된 태스크의 코딩 에이전트 세션에서 하위 태스크용 핸드오프 문서를 생성한다.
+    fn start_handoff_generation(
+        &mut self,
+        report: String,
+        report_file_path: PathBuf,
+        pre_merge_revision: String,
+    ) {
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let task = coding_state.tasks[coding_state.current_task_index].clone();
+        let integration_branch = coding_state.integration_branch.clone();
+        let workspace = self.workspace.clone();
+
+        let mut client = match (task.kind, self.claude_client.take()) {
+            (CodingTaskKind::Implementation, Some(client)) => client,
+            (_, client) => {
+                self.claude_client = client;
+                self.cleanup_current_task_worktree();
+                self.advance_task(
+                    task.task_id,
+                    CodingTaskStatus::ImplementationSuccess,
+                    report,
+                    report_file_path,
+                );
+                return;
+            }
+        };
+
+        let files_touched = match coding::list_changed_files_in_range(
+            &workspace,
+            &pre_merge_revision,
+            &integration_branch,
+        ) {
+            Ok(files) => files,
+            Err(err) => {
+                self.add_system_message(&format!(
+                    "[{}] 변경 파일 목록 조회 실패: {}",
+                    task.task_id, err,
+                ));
+                Vec::new()
+            }
+        };
+        let merge_range = format!("{}..{}", pre_merge_revision, integration_branch);
+        let user_prompt = handoff::build_handoff_prompt(&task, &merge_range, &files_touched);
+
+        self.add_system_message(&format!("[{}] 핸드오프 문서 생성 중...", task.task_id));
+        self.pending_handoff = Some(PendingHandoff {
+            task,
+            report,
+            report_file_path,
+            files_touched,
+        });
+
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::Coding);
+
+        std::thread::spawn(move || {
+            let request = ClaudeCodeRequest {
+                user_prompt,
+                output_schema: handoff::handoff_schema(),
+            };
+
+            let stream_sender = sender.clone();
+            let result = client
+                .query_streaming::<HandoffDocument, _>(&request, |line| {
+                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                })
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client,
+                outcome: Ok(AgentOutcome::HandoffCompleted(result)),
+            })));
+        });
+    }
+
+    fn handle_handoff_result(&mut self, result: Result<HandoffDocument, String>) {
+        let pending = self.pending_handoff.take().unwrap();
+        let task_id = pending.task.task_id.clone();
+
+        match result {
+            Ok(document) => {
+                self.save_and_commit_handoff(&pending, &document);
+            }
+            Err(err) => {
+                self.add_system_message(&format!(
+                    "[{}] 핸드오프 문서 생성 실패: {}. 리포트만 전달합니다.",
+                    task_id, err,
+                ));
+            }
+        }
+
+        self.cleanup_current_task_worktree();
+        self.advance_task(
+            task_id,
+            CodingTaskStatus::ImplementationSuccess,
+            pending.report,
+            pending.report_file_path,
+        );
+    }
+
+    fn save_and_commit_handoff(&mut self, pending: &PendingHandoff, document: &HandoffDocument) {
+        let task_id = &pending.task.task_id;
+        let markdown =
+            handoff::render_handoff_markdown(&pending.task, document, &pending.files_touched);
+
+        let handoff_path =
+            match handoff::save_handoff(&self.workspace_journal_dir(), task_id, &markdown) {
+                Ok(path) => path,
+                Err(err) => {
+                    self.add_system_message(&format!(
+                        "[{}] 핸드오프 문서 저장 실패: {}",
+                        task_id, err,
+                    ));
+                    return;
+                }
+            };
+
+        if let Err(err) = self.commit_journal_file(
+            &handoff_path,
+            &format!("Add handoff document for {}", task_id),
+            Some(task_id),
+        ) {
+            self.add_system_message(&format!(
+                "[{}] 핸드오프 문서 커밋 실패: {}",
+                task_id, err,
+            ));
+            return;
+        }
+
+        self.add_system_message(&format!(
+            "[{}] 핸드오프 문서 생성: {}",
+            task_id,
+            handoff_path.display(),
+        ));
+    }
+
+    fn start_conflict_resolution(
+        &mut self,
+        task_id: String,
+        conflicted_files: Vec<String>,
+        original_report: String,
+    ) {
+        self.pending_coding_report = Some(original_report);
+
+        let mut client = match self.claude_client.take() {
+            Some(c) => c,
+            None => {
+                self.add_system_message("충돌 해결을 위한 에이전트 세션을 찾을 수 없습니다.");
+                self.pending_coding_report = None;
+                let _ = coding::abort_rebase(
+                    &self
+                        .coding_state
+                        .as_ref()
+                        .unwrap()
+                        .current_task_worktree
+                        .as_ref()
+                        .unwrap()
+                        .worktree_path,
+                );
+                self.cleanup_current_task_worktree();
+                self.save_and_advance_task(
+                    task_id,
+                    CodingTaskStatus::ImplementationBlocked,
+                    "충돌 해결 세션을 찾을 수 없음".to_string(),
+                );
+                return;
+            }
+        };
+
+        let integration_branch = self
+            .coding_state
+            .as_ref()
+            .unwrap()
+            .integration_branch
+            .clone();
+
+        let user_prompt = coding::build_conflict_resolution_prompt(
+            &task_id,
+            &integration_branch,
+            &conflicted_files,
+        );
+
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::Coding);
+
+        std::thread::spawn(move || {
+            let request = ClaudeCodeRequest {
+                user_prompt,
+                output_schema: coding::conflict_resolution_result_schema(),
+            };
+
+            let stream_sender = sender.clone();
+            let outcome = client
+                .query_streaming::<ConflictResolutionResult, _>(&request, |line| {
+                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                })
+                .map(AgentOutcome::ConflictResolutionCompleted)
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client,
+                outcome,
+            })));
+        });
+    }
+
+    fn handle_conflict_resolution_result(&mut self, mut result: ConflictResolutionResult) {
+        let task_id = {
+            let coding_state = self.coding_state.as_ref().unwrap();
+            coding_state.tasks[coding_state.current_task_index]
+                .task_id
+                .clone()
+        };
+
+        let worktree_path = self
+            .coding_state
+            .as_ref()
+            .unwrap()
+            .current_task_worktree
+            .as_ref()
+            .unwrap()
+            .worktree_path
+            .clone();
+        // 리베이스는 에이전트가 끝내야 한다. 중간에 멈춘 리베이스는 머지할 수 없다.
+        if result.status == ConflictResolutionStatus::ConflictResolved
+            && let Ok(Some(operation)) = git::operation_in_progress(&worktree_path)
+        {
+            result.status = ConflictResolutionStatus::ConflictResolutionFailed;
+            result.report = format!("{}\n\n---\n{} 작업이 끝나지 않았습니다.", result.report, operation.label());
+        }
+
+        match result.status {
+            ConflictResolutionStatus::ConflictResolved => {
+                self.add_system_message(&format!("[{}] 충돌 해결 완료.", task_id));
+                self.commit_remaining_changes(&task_id);
+                let report = self
+                    .pending_coding_report
+                    .take()
+                    .unwrap_or(result.report);
+                self.verify_build_and_test(task_id, report);
+            }
+            ConflictResolutionStatus::ConflictResolutionFailed => {
+                self.add_system_message(&format!(
+                    "[{}] 충돌 해결 실패: {}",
+                    task_id, result.report,
+                ));
+                let _ = coding::abort_rebase(&worktree_path);
+                self.pending_coding_report = None;
+                self.cleanup_current_task_worktree();
+                self.save_and_advance_task(
+                    task_id,
+                    CodingTaskStatus::ImplementationBlocked,
+                    format!("충돌 해결 실패: {}", result.report),
+                );
+            }
+        }
+    }
+
+    fn save_and_advance_task(
+        &mut self,
+        task_id: String,
+        status: CodingTaskStatus,
+        report: String,
+    ) {
+        self.advance_task(task_id, status, report, PathBuf::new());
+    }
+
+    fn record_merged_commits(&mut self, workspace: &Path, pre_merge_revision: &str, task_branch: &str) {
+        match coding::list_commits_in_range(workspace, pre_merge_revision, task_branch) {
+            Ok(commit_log) => {
+                self.current_task_trace.commits = commit_log.lines().map(str::to_string).collect();
+            }
+            Err(err) => {
+                self.add_system_message(&format!("머지된 커밋 조회 실패: {}", err));
+            }
+        }
+    }
+
+    fn advance_task(
+        &mut self,
+        task_id: String,
+        status: CodingTaskStatus,
+        report: String,
+        report_file_path: PathBuf,
+    ) {
+        self.activity.finish_task(&task_id, session_naming::now_kst());
+        let trace = std::mem::take(&mut self.current_task_trace);
+        let coding_state = self.coding_state.as_mut().unwrap();
+        coding_state.task_reports.push(TaskReport {
+            task_id,
+            status,
+            report,
+            report_file_path,
+            trace,
+        });
+        coding_state.current_task_index += 1;
+
+        match self.coding_hold.take() {
+            Some(CodingHold::Pause) => self.pause_coding(),
+            Some(CodingHold::Stop) => self.stop_coding(),
+            None => self.start_next_coding_task(),
+        }
+    }
+
+    fn request_coding_hold(&mut self, hold: CodingHold) {
+        self.coding_hold = Some(hold);
+        let message = match hold {
+            CodingHold::Pause => "현재 작업이 끝나면 일시 정지합니다.",
+            CodingHold::Stop => "현재 작업이 끝나면 진행 상태를 저장하고 중지합니다.",
+        };
+        self.add_system_message(message);
+    }
+
+    fn cancel_coding_hold(&mut self) {
+        if self.coding_hold.take().is_some() {
+            self.add_system_message("일시 정지/중지 예약을 취소했습니다.");
+        }
+    }
+
+    fn pause_coding(&mut self) {
+        self.add_system_message("코딩 단계를 일시 정지했습니다.");
+        self.set_phase(WorkflowPhase::Paused);
+    }
+
+    fn resume_coding(&mut self) {
+        self.add_system_message("코딩 단계를 재개합니다.");
+        self.set_phase(WorkflowPhase::Coding);
+        self.start_next_coding_task();
+    }
+
+    /// 남은 작업과 지금까지의 보고서를 coding-state.json에 저장하고 통합 브랜치에 커밋한 뒤 중지한다.
+    fn stop_coding(&mut self) {
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let snapshot = CodingStateSnapshot {
+            integration_branch: coding_state.integration_branch.clone(),
+            integration_base_commit: self.integration_base_commit.clone(),
+            integration_base_branch: self.integration_base_branch.clone(),
+            tasks: coding_state.tasks.clone(),
+            next_task_index: coding_state.current_task_index,
+            task_reports: coding_state.task_reports.clone(),
+            usage: self.usage,
+        };
+
+        let journal_dir = self.workspace_journal_dir();
+        match coding::save_coding_state(&journal_dir, &snapshot) {
+            Ok(path) => {
+                if let Err(err) =
+                    self.commit_journal_file(&path, "Save coding state", None)
+                {
+                    self.add_system_message(&format!("진행 상태 파일 커밋 실패: {}", err));
+                }
+                self.add_system_message(&format!(
+                    "코딩 단계를 중지했습니다. 진행 상태: {}",
+                    path.display(),
+                ));
+            }
+            Err(err) => {
+                self.add_system_message(&format!("진행 상태 파일 저장 실패: {}", err));
+            }
+        }
+
+        self.write_traceability_matrix();
+        self.write_session_report();
+        self.set_phase(WorkflowPhase::Stopped);
+    }
+
+    fn finish_coding_phase(&mut self) {
+        self.write_traceability_matrix();
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let integration_branch = coding_state.integration_branch.clone();
+
+        let success_count = coding_state
+            .task_reports
+            .iter()
+            .filter(|r| r.status == CodingTaskStatus::ImplementationSuccess)
+            .count();
+        let blocked_count = coding_state
+            .task_reports
+            .iter()
+            .filter(|r| r.status == CodingTaskStatus::ImplementationBlocked)
+            .count();
+
+        let documentation_finished = coding_state
+            .tasks
+            .last()
+            .is_some_and(|task| task.kind == CodingTaskKind::Documentation);
+
+        if documentation_finished {
+            self.finish_documentation_phase(&integration_branch);
+            return;
+        }
+
+        self.add_system_message(&format!(
+            "코딩 단계 완료. 성공: {}, 차단: {}",
+            success_count, blocked_count,
+        ));
+
+        self.start_acceptance_verification(integration_branch);
+    }
+
+    /// 마지막 태스크가 머지된 통합 브랜치 전체를 스펙의 인수 조건과 대조하는 검증 에이전트를 실행한다.
+    fn start_acceptance_verification(&mut self, integration_branch: String) {
+        let workspace = self.integration_workspace();
+        let journal_dir = self.workspace_journal_dir();
+        let mut client = match self.new_agent_client(
+            workspace,
+            Some(verification::system_prompt().to_string()),
+        ) {
+            Ok(client) => client,
+            Err(err) => {
+                self.add_system_message(&format!(
+                    "검증 에이전트 클라이언트 생성 실패: {}. 인수 조건 검증을 건너뜁니다.",
+                    err,
+                ));
+                self.enter_acceptance_review(&integration_branch);
+                return;
+            }
+        };
+
+        let base_revision = self
+            .integration_base_commit
+            .clone()
+            .unwrap_or_else(|| "HEAD".to_string());
+        let user_prompt = verification::build_verification_prompt(
+            &journal_dir.join("spec.md"),
+            &journal_dir.join("plan.md"),
+            &integration_branch,
+            &base_revision,
+        );
+
+        self.add_system_message("통합 브랜치를 스펙의 인수 조건과 대조하여 검증 중입니다...");
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::AgentWorking);
+
+        std::thread::spawn(move || {
+            let request = ClaudeCodeRequest {
+                user_prompt,
+                output_schema: verification::verification_schema(),
+            };
+
+            let stream_sender = sender.clone();
+            let result = client
+                .query_streaming::<VerificationResult, _>(&request, |line| {
+                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                })
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client,
+                outcome: Ok(AgentOutcome::VerificationCompleted(result)),
+            })));
+        });
+    }
+
+    /// 검증 결과를 저널에 커밋하고 실패한 인수 조건을 기억한 뒤 통합 리뷰로 넘어간다.
+    /// 검증에 실패해도 사용자가 직접 검토할 수 있도록 다음 단계는 계속 진행한다.
+    fn handle_verification_result(&mut self, result: Result<VerificationResult, String>) {
+        let integration_branch = self.coding_state.as_ref().unwrap().integration_branch.clone();
+
+        match result {
+            Ok(result) => {
+                match verification::save_verification_report(&self.workspace_journal_dir(), &result) {
+                    Ok(path) => {
+                        if let Err(err) =
+                            self.commit_journal_file(&path, "Add acceptance verification report", None)
+                        {
+                            self.add_system_message(&format!("검증 보고서 커밋 실패: {}", err));
+                        }
+                    }
+                    Err(err) => {
+                        self.add_system_message(&format!("검증 보고서 저장 실패: {}", err));
+                    }
+                }
+                self.failed_criteria = verification::failed_criteria(&result);
+                self.add_system_message(&verification::format_verification_summary(&result));
+            }
+            Err(err) => {
+                self.failed_criteria.clear();
+                self.add_system_message(&format!("인수 조건 검증 실패: {}. 검증 없이 진행합니다.", err));
+            }
+        }
+
+        self.start_integration_review(integration_branch);
+    }
+
+    /// 태스크별 리뷰에서 보이지 않는, 여러 태스크가 합쳐져 생긴 문제를
+    /// 기준 커밋부터 통합 브랜치까지의 전체 변경에서 찾는 리뷰 에이전트를 실행한다.
+    fn start_integration_review(&mut self, integration_branch: String) {
+        let workspace = self.integration_workspace();
+        let journal_dir = self.workspace_journal_dir();
+        let mut client = match self.new_agent_client(
+            workspace,
+            Some(integration_review::system_prompt().to_string()),
+        ) {
+            Ok(client) => client,
+            Err(err) => {
+                self.add_system_message(&format!(
+                    "통합 리뷰 에이전트 클라이언트 생성 실패: {}. 통합 리뷰를 건너뜁니다.",
+                    err,
+                ));
+                self.enter_acceptance_review(&integration_branch);
+                return;
+            }
+        };
+
+        let base_revision = self
+            .integration_base_commit
+            .clone()
+            .unwrap_or_else(|| "HEAD".to_string());
+        let report_paths = documentation::collect_merged_report_paths(
+            &self.coding_state.as_ref().unwrap().task_reports,
+        );
+        let user_prompt = integration_review::build_integration_review_prompt(
+            &journal_dir.join("spec.md"),
+            &journal_dir.join("plan.md"),
+            &report_paths,
+            &format!("{}..{}", base_revision, integration_branch),
+        );
+
+        self.add_system_message("전체 작업이 합쳐진 통합 브랜치를 리뷰 중입니다...");
+        let (sender, receiver) = mpsc::channel();
+        self.agent_result_receiver = Some(receiver);
+        self.set_phase(WorkflowPhase::AgentWorking);
+
+        std::thread::spawn(move || {
+            let request = ClaudeCodeRequest {
+                user_prompt,
+                output_schema: integration_review::integration_review_schema(),
+            };
+
+            let stream_sender = sender.clone();
+            let result = client
+                .query_streaming::<IntegrationReviewResult, _>(&request, |line| {
+                    let _ = stream_sender.send(AgentStreamMessage::StreamLine(line));
+                })
+                .map_err(|err| err.to_string());
+
+            let _ = sender.send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client,
+                outcome: Ok(AgentOutcome::IntegrationReviewCompleted(result)),
+            })));
+        });
+    }
+
+    fn handle_integration_review_result(&mut self, result: Result<IntegrationReviewResult, String>) {
+        let integration_branch = self.coding_state.as_ref().unwrap().integration_branch.clone();
+
+        match result {
+            Ok(result) => {
+                match integration_review::save_integration_review(&self.workspace_journal_dir(), &result) {
+                    Ok(path) => {
+                        if let Err(err) =
+                            self.commit_journal_file(&path, "Add integration review report", None)
+                        {
+                            self.add_system_message(&format!("통합 리뷰 보고서 커밋 실패: {}", err));
+                        }
+                    }
+                    Err(err) => {
+                        self.add_system_message(&format!("통합 리뷰 보고서 저장 실패: {}", err));
+                    }
+                }
+                self.add_system_message(&integration_review::format_integration_review_summary(&result));
+                self.integration_findings = result.findings;
+            }
+            Err(err) => {
+                self.integration_findings.clear();
+                self.add_system_message(&format!("통합 리뷰 실패: {}. 통합 리뷰 없이 진행합니다.", err));
+            }
+        }
+
+        self.enter_acceptance_review(&integration_branch);
+    }
+
+    fn start_documentation_phase(&mut self) {
+        let task = documentation::build_documentation_task(
+            &self.coding_state.as_ref().unwrap().tasks,
+        );
+        self.add_system_message(&format!(
+            "문서화 단계를 시작합니다. 작업 [{}]: {}",
+            task.task_id, task.title,
+        ));
+        self.coding_state.as_mut().unwrap().tasks.push(task);
+
+        self.start_next_coding_task();
+    }
+
+    fn finish_documentation_phase(&mut self, integration_branch: &str) {
+        let documentation_status = self
+            .coding_state
+            .as_ref()
+            .unwrap()
+            .task_reports
+            .last()
+            .map(|report| report.status.clone());
+
+        match documentation_status {
+            Some(CodingTaskStatus::ImplementationSuccess) => {
+                self.add_system_message("문서화 단계 완료. 문서가 통합 브랜치에 커밋되었습니다.");
+            }
+            _ => {
+                self.add_system_message("문서화 작업이 차단되었습니다. 문서 없이 세션을 종료합니다.");
+            }
+        }
+
+        self.write_session_report();
+        self.write_pull_request_text(integration_branch);
+        self.add_system_message(&delivery::format_delivery_menu(
+            integration_branch,
+            self.integration_base_branch.as_deref(),
+        ));
+        self.set_phase(WorkflowPhase::Delivery);
+    }
+
+    /// 스펙과 태스크 보고서로 PR 제목과 본문을 만들어 저널에 커밋한다. 머지 커밋 메시지에도 쓴다.
+    fn write_pull_request_text(&mut self, integration_branch: &str) {
+        let journal_dir = self.workspace_journal_dir();
+        let spec = std::fs::read_to_string(journal_dir.join("spec.md"))
+            .ok()
+            .or_else(|| self.approved_spec.clone())
+            .unwrap_or_default();
+        let base_revision = self
+            .integration_base_commit
+            .clone()
+            .unwrap_or_else(|| "HEAD".to_string());
+        let commit_log =
+            coding::list_commits_in_range(&self.workspace, &base_revision, integration_branch)
+                .unwrap_or_else(|err| format!("커밋 목록 조회 실패: {}", err));
+        let diff_stat =
+            coding::diff_stat_in_range(&self.workspace, &base_revision, integration_branch)
+                .unwrap_or_else(|err| format!("변경 파일 조회 실패: {}", err));
+
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let pull_request = delivery::build_pull_request_text(
+            &spec,
+            &coding_state.tasks,
+            &coding_state.task_reports,
+            &commit_log,
+            &diff_stat,
+        );
+
+        match delivery::save_pull_request_text(&journal_dir, &pull_request) {
+            Ok(path) => {
+                if let Err(err) = self.commit_journal_file(&path, "Add pull request description", None) {
+                    self.add_system_message(&format!("PR 설명 커밋 실패: {}", err));
+                }
+                self.add_system_message(&format!("PR 제목과 설명을 저장했습니다: {}", path.display()));
+            }
+            Err(err) => {
+                self.add_system_message(&format!("PR 설명 저장 실패: {}", err));
+            }
+        }
+        self.pull_request = Some(pull_request);
+    }
+
+    /// 사용자가 고른 방법으로 통합 브랜치를 전달한다. 기준 브랜치에 머지하거나 전달 없이 끝내면
+    /// 세션을 마치고, 패치/번들 내보내기 뒤에는 다른 방법을 더 고를 수 있다.
+    fn deliver_integration_branch(&mut self, answer: &str) {
+        let Some(method) = delivery::parse_delivery_method(answer) else {
+            self.add_system_message("1-6 사이의 번호나 squash, rebase, merge, patch, bundle, done 중 하나를 입력하세요.");
+            return;
+        };
+        let integration_branch = self.coding_state.as_ref().unwrap().integration_branch.clone();
+        let base_revision = self
+            .integration_base_commit
+            .clone()
+            .unwrap_or_else(|| "HEAD".to_string());
+        let journal_dir = self.workspace_journal_dir();
+
+        match method {
+            DeliveryMethod::FormatPatch => {
+                match delivery::export_patches(&self.workspace, &journal_dir, &base_revision, &integration_branch) {
+                    Ok(path) => self.add_system_message(&format!("패치 파일을 내보냈습니다: {}", path.display())),
+                    Err(err) => self.add_system_message(&format!("패치 내보내기 실패: {}", err)),
+                }
+            }
+            DeliveryMethod::Bundle => {
+                match delivery::export_bundle(&self.workspace, &journal_dir, &base_revision, &integration_branch) {
+                    Ok(path) => self.add_system_message(&format!("번들 파일을 내보냈습니다: {}", path.display())),
+                    Err(err) => self.add_system_message(&format!("번들 내보내기 실패: {}", err)),
+                }
+            }
+            DeliveryMethod::Finish => {
+                self.add_system_message(&format!(
+                    "통합 브랜치가 유지됩니다: {}\n통합 워크트리: {}",
+                    integration_branch,
+                    self.integration_workspace().display(),
+                ));
+                self.set_phase(WorkflowPhase::Done);
+            }
+            method => self.merge_into_base_branch(method, &integration_branch),
+        }
+    }
+
+    fn merge_into_base_branch(&mut self, method: DeliveryMethod, integration_branch: &str) {
+        let Some(base_branch) = self.integration_base_branch.clone() else {
+            self.add_system_message("세션을 시작한 브랜치를 알 수 없어 머지할 수 없습니다. 패치나 번들로 내보내세요.");
+            return;
+        };
+        let workspace_issues = preflight::run_preflight_checks(&self.workspace);
+        if !workspace_issues.is_empty() {
+            let descriptions: Vec<String> = workspace_issues
+                .iter()
+                .map(|issue| format!("- {}", issue.description()))
+                .collect();
+            self.add_system_message(&format!(
+                "브랜치를 옮기기 전에 워크스페이스를 정리해야 합니다:\n{}\n정리한 뒤 다시 전달 방법을 선택하세요.",
+                descriptions.join("\n"),
+            ));
+            return;
+        }
+        let pull_request = self.pull_request.get_or_insert_with(|| PullRequestText {
+            title: format!("Merge {}", integration_branch),
+            body: String::new(),
+        });
+
+        let request = MergeRequest {
+            base_branch: &base_branch,
+            integration_branch,
+            session: self.session_name.as_deref().unwrap_or_default(),
+            policy: &self.project_config.commit_policy,
+        };
+
+        match delivery::merge_into_base_branch(&self.workspace, method, &request, pull_request) {
+            Ok(()) => {
+                self.add_system_message(&format!(
+                    "통합 브랜치 {} 를 {} 에 머지했습니다.",
+                    integration_branch, base_branch,
+                ));
+                self.remove_integration_worktree();
+                self.set_phase(WorkflowPhase::Done);
+            }
+            Err(err) => {
+                self.add_system_message(&format!(
+                    "머지 실패: {}\n저장소를 원래 상태로 되돌렸습니다. 다른 방법을 선택하세요.",
+                    err,
+                ));
+            }
+        }
+    }
+
+    /// 스펙의 요구사항 ID를 태스크, 통합된 커밋, 테스트와 연결한 추적성 매트릭스를 저널에 커밋하고
+    /// 누락된 연결을 알린다.
+    fn write_traceability_matrix(&mut self) {
+        let journal_dir = self.workspace_journal_dir();
+        let spec = std::fs::read_to_string(journal_dir.join("spec.md"))
+            .ok()
+            .or_else(|| self.approved_spec.clone())
+            .unwrap_or_default();
+        let requirements = traceability::extract_requirements(&spec);
+        if requirements.is_empty() {
+            self.add_system_message("스펙에 요구사항 ID(REQ-xxx)가 없어 추적성 매트릭스를 만들지 않습니다.");
+            return;
+        }
+
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let rows = traceability::build_traceability_matrix(
+            &requirements,
+            &coding_state.tasks,
+            &coding_state.task_reports,
+        );
+        let unknown_ids = traceability::unknown_requirement_ids(&requirements, &coding_state.tasks);
+        let document = traceability::render_traceability_matrix(&rows, &unknown_ids);
+
+        match traceability::save_traceability_matrix(&journal_dir, &document) {
+            Ok(path) => {
+                if let Err(err) = self.commit_journal_file(&path, "Update traceability matrix", None) {
+                    self.add_system_message(&format!("추적성 매트릭스 커밋 실패: {}", err));
+                }
+                self.add_system_message(&format!(
+                    "추적성 매트릭스: {}\n{}",
+                    path.display(),
+                    traceability::format_gaps(&rows),
+                ));
+            }
+            Err(err) => {
+                self.add_system_message(&format!("추적성 매트릭스 저장 실패: {}", err));
+            }
+        }
+    }
+
+    /// 저널의 요구사항/스펙/플랜과 이번 실행의 활동 기록으로 세션 보고서를 만들어 저널에 커밋한다.
+    /// 파일이 없으면 메모리에 있는 최신 내용을 쓴다.
+    fn write_session_report(&mut self) {
+        let journal_dir = self.workspace_journal_dir();
+        let read_journal_file = |file_name: &str, fallback: Option<&String>| {
+            std::fs::read_to_string(journal_dir.join(file_name))
+                .ok()
+                .or_else(|| fallback.cloned())
+                .unwrap_or_default()
+        };
+        let user_request = read_journal_file("user-request.md", self.requirements.as_ref());
+        let spec = read_journal_file("spec.md", self.approved_spec.as_ref());
+        let plan = read_journal_file("plan.md", self.last_plan_draft.as_ref());
+
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let base_revision = self
+            .integration_base_commit
+            .clone()
+            .unwrap_or_else(|| "HEAD".to_string());
+        let commit_log = coding::list_commits_in_range(
+            &self.workspace,
+            &base_revision,
+            &coding_state.integration_branch,
+        )
+        .unwrap_or_else(|err| format!("커밋 목록 조회 실패: {}", err));
+
+        let input = SessionReportInput {
+            session_name: self.session_name.as_deref().unwrap_or_default(),
+            user_request: &user_request,
+            qa_log: &self.qa_log,
+            spec: &spec,
+            plan: &plan,
+            tasks: &coding_state.tasks,
+            task_reports: &coding_state.task_reports,
+            activity: &self.activity,
+            integration_branch: &coding_state.integration_branch,
+            commit_log: &commit_log,
+            usage: self.usage,
+            generated_at: session_naming::now_kst(),
+        };
+
+        let paths = match session_report::save_session_report(&journal_dir, &input) {
+            Ok(paths) => paths,
+            Err(err) => {
+                self.add_system_message(&format!("세션 보고서 저장 실패: {}", err));
+                return;
+            }
+        };
+        if let Err(err) =
+            self.commit_journal_files(&paths, "Add session report", None)
+        {
+            self.add_system_message(&format!("세션 보고서 커밋 실패: {}", err));
+        }
+        self.add_system_message(&format!(
+            "세션 보고서를 저장했습니다: {}",
+            paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>().join(", "),
+        ));
+    }
+
+    fn export_report_bundle(&mut self) {
+        if self.session_name.is_none() {
+            self.add_system_message("묶을 세션 저널이 없습니다.");
+            return;
+        }
+        match session_report::create_session_bundle(&self.workspace_journal_dir()) {
+            Ok(path) => {
+                self.add_system_message(&format!("보고서와 산출물을 묶었습니다: {}", path.display()));
+            }
+            Err(err) => {
+                self.add_system_message(&format!("산출물 묶음 생성 실패: {}", err));
+            }
+        }
+    }
+
+    /// 머지가 끝나 저널까지 기준 브랜치에 들어갔으므로 통합 워크트리를 지운다. 통합 브랜치는 남긴다.
+    /// 워크트리 안의 저널에는 커밋하지 않은 내보내기 결과도 있으므로, 먼저 코드 밖 저널 루트로 복사해
+    /// 종료 단계의 보고서 묶기가 계속 동작하게 한다. 복사하지 못하면 워크트리를 남긴다.
+    fn remove_integration_worktree(&mut self) {
+        let Some(worktree_path) = self.integration_worktree.clone() else {
+            return;
+        };
+        if worktree_path == self.workspace {
+            return;
+        }
+        let journal_dir = self.workspace_journal_dir();
+        if journal_dir.starts_with(&worktree_path) {
+            match journal_storage::copy_journal_out_of_worktree(&self.workspace, &worktree_path, &journal_dir) {
+                Ok(copied_dir) => {
+                    self.add_system_message(&format!("세션 저널을 {} 로 옮겼습니다.", copied_dir.display()));
+                    self.base_journal_dir = Some(copied_dir);
+                    self.journal_storage = JournalStorage::External;
+                }
+                Err(err) => {
+                    self.add_system_message(&format!(
+                        "저널 복사 실패: {}. 통합 워크트리 {} 를 지우지 않고 남깁니다.",
+                        err,
+                        worktree_path.display(),
+                    ));
+                    return;
+                }
+            }
+        }
+        self.integration_worktree = None;
+        if let Err(err) = coding::remove_worktree(&self.workspace, &worktree_path) {
+            self.add_system_message(&format!("통합 워크트리 삭제 실패: {}", err));
+        }
+    }
+
+    fn record_integration_base_commit(&mut self, workspace: &Path) {
+        match coding::get_latest_commit_revision(workspace) {
+            Ok(revision) => self.integration_base_commit = Some(revision),
+            Err(err) => {
+                self.add_system_message(&format!("기준 커밋 조회 실패: {}", err));
+            }
+        }
+        match delivery::current_branch_name(workspace) {
+            Ok(branch) => self.integration_base_branch = branch,
+            Err(err) => {
+                self.add_system_message(&format!("기준 브랜치 조회 실패: {}", err));
+            }
+        }
+    }
+
+    fn enter_acceptance_review(&mut self, integration_branch: &str) {
+        let workspace = self.workspace.clone();
+        let base_revision = self
+            .integration_base_commit
+            .clone()
+            .unwrap_or_else(|| "HEAD".to_string());
+
+        let commit_log =
+            coding::list_commits_in_range(&workspace, &base_revision, integration_branch)
+                .unwrap_or_else(|err| format!("커밋 목록 조회 실패: {}", err));
+        let diff_stat =
+            coding::diff_stat_in_range(&workspace, &base_revision, integration_branch)
+                .unwrap_or_else(|err| format!("변경 파일 조회 실패: {}", err));
+
+        let summary = acceptance::build_acceptance_summary(
+            &self.coding_state.as_ref().unwrap().task_reports,
+            integration_branch,
+            &commit_log,
+            &diff_stat,
+        );
+        self.add_system_message(&summary);
+        self.set_phase(WorkflowPhase::AcceptanceReview);
+    }
+
+    fn submit_change_request(&mut self, change_request: String) {
+        self.last_user_input = Some(change_request.clone());
+
+        if let Some(request) = acceptance::parse_rerun_request(&change_request) {
+            self.rerun_blocked_tasks(request);
+            return;
+        }
+
+        if let Some(criterion_ids) = verification::parse_fix_criteria_request(&change_request) {
+            self.add_criterion_fix_tasks(criterion_ids);
+            return;
+        }
+
+        if let Some(finding_ids) = integration_review::parse_cleanup_request(&change_request) {
+            self.add_cleanup_tasks(finding_ids);
+            return;
+        }
+
+        let task = acceptance::build_change_request_task(
+            &self.coding_state.as_ref().unwrap().tasks,
+            &change_request,
+        );
+        self.add_system_message(&format!(
+            "변경 요청을 새 작업 [{}]으로 추가했습니다. 코딩 에이전트에 전달합니다.",
+            task.task_id,
+        ));
+        self.coding_state.as_mut().unwrap().tasks.push(task);
+
+        self.start_next_coding_task();
+    }
+
+    /// 선택한 실패 인수 조건(비어 있으면 전부)마다 수정 태스크를 추가하고 코딩 파이프라인으로 실행한다.
+    fn add_criterion_fix_tasks(&mut self, criterion_ids: Vec<String>) {
+        let selected: Vec<CriterionResult> = self
+            .failed_criteria
+            .iter()
+            .filter(|c| criterion_ids.is_empty() || criterion_ids.contains(&c.criterion_id))
+            .cloned()
+            .collect();
+        if selected.is_empty() {
+            self.add_system_message("작업으로 만들 실패한 인수 조건이 없습니다.");
+            return;
+        }
+
+        for criterion in &selected {
+            let task = verification::build_criterion_fix_task(
+                &self.coding_state.as_ref().unwrap().tasks,
+                criterion,
+            );
+            self.add_system_message(&format!(
+                "실패한 인수 조건 {}을(를) 새 작업 [{}]으로 추가했습니다.",
+                criterion.criterion_id, task.task_id,
+            ));
+            self.coding_state.as_mut().unwrap().tasks.push(task);
+        }
+        self.failed_criteria.retain(|c| !selected.contains(c));
+
+        self.start_next_coding_task();
+    }
+
+    /// 선택한 통합 리뷰 발견 사항(비어 있으면 전부)마다 정리 태스크를 추가하고 코딩 파이프라인으로 실행한다.
+    fn add_cleanup_tasks(&mut self, finding_ids: Vec<String>) {
+        let selected: Vec<IntegrationFinding> = self
+            .integration_findings
+            .iter()
+            .filter(|f| finding_ids.is_empty() || finding_ids.contains(&f.finding_id))
+            .cloned()
+            .collect();
+        if selected.is_empty() {
+            self.add_system_message("작업으로 만들 통합 리뷰 발견 사항이 없습니다.");
+            return;
+        }
+
+        for finding in &selected {
+            let task = integration_review::build_cleanup_task(
+                &self.coding_state.as_ref().unwrap().tasks,
+                finding,
+            );
+            self.add_system_message(&format!(
+                "통합 리뷰 발견 사항 {}을(를) 새 작업 [{}]으로 추가했습니다.",
+                finding.finding_id, task.task_id,
+            ));
+            self.coding_state.as_mut().unwrap().tasks.push(task);
+        }
+        self.integration_findings.retain(|f| !selected.contains(f));
+
+        self.start_next_coding_task();
+    }
+
+    /// 선택한 차단 태스크마다 이전 보고서를 저널에 보존하고, 보고서 경로와 사용자 지침을 담은
+    /// 재실행 태스크를 추가한 뒤 일반 코딩 파이프라인으로 실행한다.
+    fn rerun_blocked_tasks(&mut self, request: acceptance::RerunRequest) {
+        let coding_state = self.coding_state.as_ref().unwrap();
+        let blocked = acceptance::blocked_task_ids(&coding_state.task_reports);
+
+        if request.task_ids.is_empty() {
+            self.add_system_message(&format!(
+                "재실행할 작업 ID를 지정하세요. 차단된 작업: {}",
+                if blocked.is_empty() { "없음".to_string() } else { blocked.join(", ") },
+            ));
+            return;
+        }
+        if let Some(task_id) = request.task_ids.iter().find(|id| !blocked.contains(id)) {
+            self.add_system_message(&format!("차단된 작업이 아닙니다: {}", task_id));
+            return;
+        }
+
+        let mut rerun_tasks = Vec::new();
+        for task_id in &request.task_ids {
+            match self.preserve_blocked_report(task_id) {
+                Ok(report_path) => {
+                    let task = self.find_coding_task(task_id);
+                    rerun_tasks.push(acceptance::build_rerun_task(
+                        &task,
+                        report_path,
+                        request.guidance.clone(),
+                    ));
+                }
+                Err(err) => {
+                    self.add_system_message(&format!("[{}] 차단 보고서 보존 실패: {}", task_id, err));
+                    return;
+                }
+            }
+        }
+
+        self.add_system_message(&format!(
+            "차단된 작업을 다시 실행합니다: {}",
+            request.task_ids.join(", "),
+        ));
+        self.coding_state.as_mut().unwrap().tasks.extend(rerun_tasks);
+        self.start_next_coding_task();
+    }
+
+    fn find_coding_task(&self, task_id: &str) -> CodingTask {
+        self.coding_state
+            .as_ref()
+            .unwrap()
+            .tasks
+            .iter()
+            .rev()
+            .find(|task| task.task_id == task_id)
+            .cloned()
+            .expect("blocked task must exist in the task list")
+    }
+
+    fn preserve_blocked_report(&mut self, task_id: &str) -> Result<PathBuf, String> {
+        let task_reports = &self.coding_state.as_ref().unwrap().task_reports;
+        let attempt = task_reports
+            .iter()
+            .filter(|r| r.task_id == task_id && r.status == CodingTaskStatus::ImplementationBlocked)
+            .count();
+        let report = task_reports
+            .iter()
+            .rev()
+            .find(|r| r.task_id == task_id)
+            .map(|r| r.report.clone())
+            .unwrap_or_default();
+
+        let journal_dir = self.workspace_journal_dir();
+        let report_path = acceptance::blocked_report_file_path(&journal_dir, task_id, attempt);
+        std::fs::create_dir_all(&journal_dir).map_err(|e| e.to_string())?;
+        std::fs::write(&report_path, report).map_err(|e| e.to_string())?;
+
+        self.commit_journal_file(
+            &report_path,
+            &format!("Preserve blocked report for {}", task_id),
+            Some(task_id),
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(report_path)
+    }
+
+    fn approve_final_code(&mut self) {
+        self.add_system_message("최종 코드가 승인되었습니다.");
+
+        self.start_documentation_phase();
+    }
+}
+
+/// 차단된 태스크 보고서에 덧붙일 실패 단계별 출력.
+fn describe_stage_failures(failures: &[StageFailure]) -> String {
+    failures
+        .iter()
+        .map(|failure| format!("검증 단계 '{}' 실패:\n{}", failure.stage.name, failure.output))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn non_empty_input(input: String) -> Result<String, WorkflowError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(WorkflowError::EmptyInput);
+    }
+    Ok(trimmed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// 설치된 에이전트와 무관하게 동작하도록 없는 실행 파일을 지정해 클라이언트 생성이 항상 실패하게 한다.
+    fn new_engine(workspace: &Path) -> Engine {
+        let mut engine = Engine::new("test-api-key".to_string(), workspace.to_path_buf(), ProjectConfig::default());
+        engine.agent_binary = Some(workspace.join("missing-agent"));
+        engine
+    }
+
+    #[test]
+    fn rejects_commands_not_accepted_in_current_phase() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut engine = new_engine(temp_dir.path());
+
+        let result = engine.handle_command(WorkflowCommand::Approve);
+
+        assert!(matches!(
+            result,
+            Err(WorkflowError::UnexpectedCommand { phase: WorkflowPhase::Idle })
+        ));
+        assert_eq!(engine.phase(), WorkflowPhase::Idle);
+    }
+
+    #[test]
+    fn rejects_blank_requirements() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut engine = new_engine(temp_dir.path());
+
+        let result = engine.handle_command(WorkflowCommand::StartSession {
+            requirements: "  \n ".to_string(),
+        });
+
+        assert!(matches!(result, Err(WorkflowError::EmptyInput)));
+        assert!(engine.poll_events().is_empty());
+    }
+
+    #[test]
+    fn resume_rejects_directory_without_spec() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("session")).unwrap();
+        let mut engine = new_engine(temp_dir.path());
+
+        let result = engine.handle_command(WorkflowCommand::ResumeSession {
+            session_dir: "session".to_string(),
+        });
+
+        assert!(matches!(result, Err(WorkflowError::InvalidSessionDirectory { .. })));
+        assert_eq!(engine.phase(), WorkflowPhase::Idle);
+    }
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        std::process::Command::new("git").current_dir(dir).args(args).output().unwrap();
+    }
+
+    fn messages(events: &[WorkflowEvent]) -> Vec<&str> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                WorkflowEvent::Message(message) => Some(message.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resume_from_coding_state_enters_acceptance_review_with_blocked_tasks() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path();
+        run_git(workspace, &["init"]);
+        run_git(workspace, &["config", "user.email", "test@test.com"]);
+        run_git(workspace, &["config", "user.name", "Test"]);
+        run_git(workspace, &["config", "commit.gpgsign", "false"]);
+        std::fs::write(workspace.join("init.txt"), "init").unwrap();
+        run_git(workspace, &["add", "init.txt"]);
+        run_git(workspace, &["commit", "-m", "initial commit"]);
+        let integration_worktree = workspace.join("integration");
+        run_git(
+            workspace,
+            &["worktree", "add", "-b", "bear/integration/session", &integration_worktree.display().to_string()],
+        );
+
+        let session_dir = workspace.join(".bear").join("20260101").join("session");
+        let snapshot = CodingStateSnapshot {
+            integration_branch: "bear/integration/session".to_string(),
+            integration_base_commit: None,
+            integration_base_branch: None,
+            tasks: vec![CodingTask {
+                task_id: "TASK-00".to_string(),
+                title: "작업".to_string(),
+                description: "설명".to_string(),
+                dependencies: vec![],
+                kind: CodingTaskKind::Implementation,
+                retry: None,
+                requirement_ids: Vec::new(),
+            }],
+            next_task_index: 1,
+            task_reports: vec![TaskReport {
+                task_id: "TASK-00".to_string(),
+                status: CodingTaskStatus::ImplementationBlocked,
+                report: "차단됨".to_string(),
+                report_file_path: PathBuf::new(),
+                trace: TaskTrace::default(),
+            }],
+            usage: UsageTotals { query_count: 4, total_cost_usd: 1.5, agent_duration_ms: 3000 },
+        };
+        coding::save_coding_state(&session_dir, &snapshot).unwrap();
+        std::fs::write(session_dir.join("spec.md"), "# Spec").unwrap();
+        let mut engine = new_engine(workspace);
+
+        engine
+            .handle_command(WorkflowCommand::ResumeSession {
+                session_dir: session_dir.display().to_string(),
+            })
+            .unwrap();
+
+        assert_eq!(engine.usage, UsageTotals { query_count: 4, total_cost_usd: 1.5, agent_duration_ms: 3000 });
+        assert_eq!(engine.phase(), WorkflowPhase::AcceptanceReview);
+        let events = engine.poll_events();
+        assert!(messages(&events).iter().any(|m| m.contains("차단된 작업: TASK-00")));
+
+        engine
+            .handle_command(WorkflowCommand::Feedback("/rerun TASK-09".to_string()))
+            .unwrap();
+        let events = engine.poll_events();
+        assert_eq!(messages(&events), vec!["차단된 작업이 아닙니다: TASK-09"]);
+        assert_eq!(engine.phase(), WorkflowPhase::AcceptanceReview);
+    }
+
+    #[test]
+    fn cancel_finishes_the_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut engine = new_engine(temp_dir.path());
+
+        engine.handle_command(WorkflowCommand::Cancel).unwrap();
+
+        assert_eq!(engine.phase(), WorkflowPhase::Done);
+        assert_eq!(
+            engine.poll_events(),
+            vec![WorkflowEvent::PhaseChanged(WorkflowPhase::Done)]
+        );
+    }
+
+    fn implementation_task(task_id: &str) -> CodingTask {
+        CodingTask {
+            task_id: task_id.to_string(),
+            title: format!("{} 작업", task_id),
+            description: "설명".to_string(),
+            dependencies: vec![],
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: Vec::new(),
+        }
+    }
+
+    fn task_report(task_id: &str, status: CodingTaskStatus) -> TaskReport {
+        TaskReport {
+            task_id: task_id.to_string(),
+            status,
+            report: format!("{} 보고서", task_id),
+            report_file_path: PathBuf::new(),
+            trace: TaskTrace::default(),
+        }
+    }
+
+    /// `<temp>/repo`의 main 브랜치에 초기 커밋이 있는 워크스페이스.
+    fn init_workspace(temp_dir: &TempDir) -> PathBuf {
+        let workspace = temp_dir.path().join("repo");
+        std::fs::create_dir(&workspace).unwrap();
+        run_git(&workspace, &["init", "-b", "main"]);
+        run_git(&workspace, &["config", "user.email", "test@test.com"]);
+        run_git(&workspace, &["config", "user.name", "Test"]);
+        run_git(&workspace, &["config", "commit.gpgsign", "false"]);
+        std::fs::write(workspace.join("init.txt"), "init").unwrap();
+        run_git(&workspace, &["add", "init.txt"]);
+        run_git(&workspace, &["commit", "-m", "initial commit"]);
+        workspace
+    }
+
+    /// 통합 워크트리와 저널을 만든 뒤 주어진 작업 목록으로 코딩 단계에 들어간 엔진.
+    fn engine_in_coding_phase(workspace: &Path, tasks: Vec<CodingTask>, task_reports: Vec<TaskReport>) -> Engine {
+        let mut engine = new_engine(workspace);
+        engine.begin_session_journal();
+        let integration_branch = engine.integration_branch.clone().unwrap();
+        engine.coding_state = Some(CodingPhaseState {
+            current_task_index: task_reports.len(),
+            tasks,
+            task_reports,
+            integration_branch,
+            current_task_worktree: None,
+            verification_pipeline: None,
+        });
+        engine.set_phase(WorkflowPhase::Coding);
+        engine.poll_events();
+        engine
+    }
+
+    /// 에이전트 없이 현재 작업의 태스크 브랜치와 워크트리를 만든다.
+    fn open_current_task_worktree(engine: &mut Engine) -> PathBuf {
+        let workspace = engine.workspace.clone();
+        let coding_state = engine.coding_state.as_mut().unwrap();
+        let task_id = coding_state.tasks[coding_state.current_task_index].task_id.clone();
+        let task_branch = coding::create_task_branch(
+            &workspace,
+            &coding_state.integration_branch,
+            engine.session_name.as_deref().unwrap(),
+            &task_id,
+            &engine.project_config.commit_policy,
+        )
+        .unwrap();
+        let ref_snapshot = coding::snapshot_refs(&workspace).unwrap();
+        let worktree_path = coding::create_worktree(&workspace, &task_branch).unwrap();
+        coding_state.current_task_worktree = Some(TaskWorktreeInfo {
+            worktree_path: worktree_path.clone(),
+            task_branch,
+            ref_snapshot,
+        });
+        worktree_path
+    }
+
+    /// 에이전트 스레드가 끝난 것처럼 결과를 넣고 그 사이 쌓인 이벤트를 돌려준다.
+    fn finish_agent_turn(engine: &mut Engine, outcome: AgentOutcome) -> Vec<WorkflowEvent> {
+        let (sender, receiver) = mpsc::channel();
+        sender
+            .send(AgentStreamMessage::Completed(Box::new(AgentThreadResult {
+                client: ClaudeCodeClient::for_tests(engine.workspace.clone()),
+                outcome: Ok(outcome),
+            })))
+            .unwrap();
+        engine.agent_result_receiver = Some(receiver);
+        engine.poll_events()
+    }
+
+    fn coding_result(status: CodingTaskStatus) -> AgentOutcome {
+        AgentOutcome::CodingTaskCompleted(CodingTaskResult {
+            status,
+            report: "에이전트 보고서".to_string(),
+            requirement_tests: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn pause_waits_for_current_task_and_stop_saves_remaining_tasks() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = init_workspace(&temp_dir);
+        let mut engine = engine_in_coding_phase(
+            &workspace,
+            vec![implementation_task("TASK-00"), implementation_task("TASK-01")],
+            Vec::new(),
+        );
+        let worktree_path = open_current_task_worktree(&mut engine);
+
+        engine.handle_command(WorkflowCommand::Pause).unwrap();
+        engine.handle_command(WorkflowCommand::Resume).unwrap();
+        assert_eq!(
+            messages(&engine.poll_events()),
+            vec!["현재 작업이 끝나면 일시 정지합니다.", "일시 정지/중지 예약을 취소했습니다."],
+        );
+        engine.handle_command(WorkflowCommand::Pause).unwrap();
+        assert_eq!(engine.phase(), WorkflowPhase::Coding);
+
+        finish_agent_turn(&mut engine, coding_result(CodingTaskStatus::ImplementationBlocked));
+
+        assert_eq!(engine.phase(), WorkflowPhase::Paused);
+        assert!(!worktree_path.exists());
+        assert_eq!(engine.coding_state.as_ref().unwrap().current_task_index, 1);
+
+        engine.handle_command(WorkflowCommand::Stop).unwrap();
+
+        assert_eq!(engine.phase(), WorkflowPhase::Stopped);
+        let snapshot = coding::load_coding_state(&engine.workspace_journal_dir()).unwrap();
+        assert_eq!(snapshot.next_task_index, 1);
+        assert_eq!(snapshot.tasks.len(), 2);
+        assert_eq!(snapshot.task_reports[0].status, CodingTaskStatus::ImplementationBlocked);
+    }
+
+    #[test]
+    fn agent_turn_without_task_commits_is_sent_back_then_blocked() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = init_workspace(&temp_dir);
+        let mut engine = engine_in_coding_phase(&workspace, vec![implementation_task("TASK-00")], Vec::new());
+        open_current_task_worktree(&mut engine);
+
+        for attempt in 1..=MAX_COMMIT_CHECK_RETRIES {
+            let events = finish_agent_turn(&mut engine, coding_result(CodingTaskStatus::ImplementationSuccess));
+            let expected = format!("[TASK-00] 커밋 확인 실패 ({}/{})", attempt, MAX_COMMIT_CHECK_RETRIES);
+            assert!(messages(&events).iter().any(|m| m.starts_with(&expected)), "{:?}", events);
+            assert_eq!(engine.phase(), WorkflowPhase::Coding);
+            assert_eq!(engine.review_state.as_ref().unwrap().commit_check_count, attempt);
+        }
+
+        let events = finish_agent_turn(&mut engine, coding_result(CodingTaskStatus::ImplementationSuccess));
+
+        assert!(messages(&events).iter().any(|m| m.starts_with("[TASK-00] 커밋 확인 2회 실패")));
+        assert!(engine.review_state.is_none());
+        let report = &engine.coding_state.as_ref().unwrap().task_reports[0];
+        assert_eq!(report.status, CodingTaskStatus::ImplementationBlocked);
+        assert!(report.report.contains("커밋 확인 2회 실패"));
+        // 에이전트 실행 파일이 없으므로 인수 검증은 건너뛴다.
+        assert_eq!(engine.phase(), WorkflowPhase::AcceptanceReview);
+    }
+
+    #[test]
+    fn uncommitted_agent_work_is_committed_before_verification() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = init_workspace(&temp_dir);
+        let mut engine = engine_in_coding_phase(&workspace, vec![implementation_task("TASK-00")], Vec::new());
+        let worktree_path = open_current_task_worktree(&mut engine);
+        std::fs::write(worktree_path.join("feature.txt"), "feature").unwrap();
+
+        let events = finish_agent_turn(&mut engine, coding_result(CodingTaskStatus::ImplementationSuccess));
+
+        let messages = messages(&events);
+        assert!(messages.iter().any(|m| m.contains("변경 1개 파일을 자동 커밋했습니다")), "{:?}", messages);
+        assert!(!messages.iter().any(|m| m.contains("커밋 확인 실패")));
+        assert_eq!(git::run(&worktree_path, ["ls-files", "feature.txt"]).unwrap(), "feature.txt");
+        assert_eq!(git::run(&worktree_path, ["status", "--porcelain", "feature.txt"]).unwrap(), "");
+        // 에이전트 실행 파일이 없으므로 리뷰를 건너뛰고, 검증은 빌드/테스트 명령을 묻는다.
+        assert_eq!(engine.phase(), WorkflowPhase::BuildTestCommandInput);
+    }
+
+    #[test]
+    fn rerun_preserves_blocked_report_and_returns_to_acceptance_review() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = init_workspace(&temp_dir);
+        let mut engine = engine_in_coding_phase(
+            &workspace,
+            vec![implementation_task("TASK-00")],
+            vec![task_report("TASK-00", CodingTaskStatus::ImplementationBlocked)],
+        );
+        engine.set_phase(WorkflowPhase::AcceptanceReview);
+
+        engine
+            .handle_command(WorkflowCommand::Feedback("/rerun TASK-00\n캐시를 먼저 확인하세요".to_string()))
+            .unwrap();
+
+        let events = engine.poll_events();
+        assert!(messages(&events).contains(&"차단된 작업을 다시 실행합니다: TASK-00"));
+        let preserved = acceptance::blocked_report_file_path(&engine.workspace_journal_dir(), "TASK-00", 1);
+        assert_eq!(std::fs::read_to_string(&preserved).unwrap(), "TASK-00 보고서");
+        let coding_state = engine.coding_state.as_ref().unwrap();
+        assert_eq!(coding_state.tasks.len(), 2);
+        let retry = coding_state.tasks[1].retry.as_ref().unwrap();
+        assert_eq!(retry.previous_report_path, preserved);
+        assert_eq!(retry.guidance.as_deref(), Some("캐시를 먼저 확인하세요"));
+        assert_eq!(coding_state.task_reports.len(), 2);
+        assert!(coding_state.current_task_worktree.is_none());
+        assert_eq!(engine.phase(), WorkflowPhase::AcceptanceReview);
+    }
+
+    #[test]
+    fn approval_runs_documentation_and_delivery_rechecks_workspace() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = init_workspace(&temp_dir);
+        let mut engine = engine_in_coding_phase(
+            &workspace,
+            vec![implementation_task("TASK-00")],
+            vec![task_report("TASK-00", CodingTaskStatus::ImplementationSuccess)],
+        );
+        engine.set_phase(WorkflowPhase::AcceptanceReview);
+
+        engine.handle_command(WorkflowCommand::Approve).unwrap();
+
+        let events = engine.poll_events();
+        assert!(messages(&events).iter().any(|m| m.starts_with("문서화 단계를 시작합니다.")));
+        assert!(messages(&events).contains(&"문서화 작업이 차단되었습니다. 문서 없이 세션을 종료합니다."));
+        let tasks = &engine.coding_state.as_ref().unwrap().tasks;
+        assert_eq!(tasks.last().unwrap().kind, CodingTaskKind::Documentation);
+        assert_eq!(engine.phase(), WorkflowPhase::Delivery);
+
+        std::fs::write(workspace.join("init.txt"), "edited").unwrap();
+        engine.handle_command(WorkflowCommand::SubmitAnswer("squash".to_string())).unwrap();
+
+        let events = engine.poll_events();
+        assert!(messages(&events)[0].starts_with("브랜치를 옮기기 전에 워크스페이스를 정리해야 합니다"));
+        assert_eq!(engine.phase(), WorkflowPhase::Delivery);
+
+        run_git(&workspace, &["checkout", "--", "init.txt"]);
+        engine.handle_command(WorkflowCommand::SubmitAnswer("squash".to_string())).unwrap();
+
+        assert_eq!(engine.phase(), WorkflowPhase::Done);
+        let log = git::run(&workspace, ["log", "--format=%s", "main"]).unwrap();
+        assert_eq!(log.lines().count(), 2);
+    }
+
+    #[test]
+    fn report_bundle_can_be_exported_after_merge_delivery() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = init_workspace(&temp_dir);
+        let mut engine = engine_in_coding_phase(
+            &workspace,
+            vec![implementation_task("TASK-00")],
+            vec![task_report("TASK-00", CodingTaskStatus::ImplementationSuccess)],
+        );
+        let integration_worktree = engine.integration_worktree.clone().unwrap();
+        // 커밋하지 않은 내보내기 결과도 워크트리와 함께 사라지면 안 된다.
+        let patch_dir = engine.workspace_journal_dir().join("patches");
+        std::fs::create_dir_all(&patch_dir).unwrap();
+        std::fs::write(patch_dir.join("0001-task.patch"), "patch").unwrap();
+        engine.set_phase(WorkflowPhase::Delivery);
+
+        engine.handle_command(WorkflowCommand::SubmitAnswer("merge".to_string())).unwrap();
+        assert_eq!(engine.phase(), WorkflowPhase::Done);
+        assert!(!integration_worktree.exists());
+        engine.poll_events();
+        engine.handle_command(WorkflowCommand::ExportReportBundle).unwrap();
+
+        let journal_dir = engine.workspace_journal_dir();
+        assert!(journal_dir.starts_with(journal_storage::external_journal_root(&workspace)));
+        assert_eq!(std::fs::read_to_string(journal_dir.join("patches/0001-task.patch")).unwrap(), "patch");
+        let events = engine.poll_events();
+        assert!(messages(&events)[0].starts_with("보고서와 산출물을 묶었습니다"));
+        assert!(journal_dir.with_extension("tar.gz").is_file());
+    }
+
+    #[test]
+    fn entered_build_and_test_commands_become_a_saved_pipeline() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = init_workspace(&temp_dir);
+        let mut engine = engine_in_coding_phase(&workspace, vec![implementation_task("TASK-00")], Vec::new());
+        open_current_task_worktree(&mut engine);
+        engine.claude_client = Some(ClaudeCodeClient::for_tests(workspace.clone()));
+
+        engine.verify_build_and_test("TASK-00".to_string(), "보고서".to_string());
+        assert_eq!(engine.phase(), WorkflowPhase::BuildTestCommandInput);
+        engine.handle_command(WorkflowCommand::SubmitAnswer("true".to_string())).unwrap();
+        assert_eq!(engine.phase(), WorkflowPhase::BuildTestCommandInput);
+        engine.handle_command(WorkflowCommand::SubmitAnswer("true".to_string())).unwrap();
+
+        let expected = verification_pipeline::build_and_test_stages("true", "true");
+        assert_eq!(engine.phase(), WorkflowPhase::Coding);
+        assert_eq!(engine.coding_state.as_ref().unwrap().verification_pipeline.as_ref(), Some(&expected));
+        // 사용자의 체크아웃에는 아무것도 쓰지 않고 통합 브랜치에 커밋한다.
+        assert!(!project_config::project_config_path(&workspace).exists());
+        let integration_workspace = engine.integration_workspace();
+        let saved = project_config::load_project_config(&integration_workspace).unwrap();
+        assert_eq!(saved.verification_pipeline, expected);
+        let status = git::run(&integration_workspace, ["status", "--porcelain", "--", ".bear/config.json"]).unwrap();
+        assert_eq!(status, "");
+        let log = git::run(&integration_workspace, ["log", "-1", "--format=%s"]).unwrap();
+        assert_eq!(log, "Save verification pipeline");
+    }
+}
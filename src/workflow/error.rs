@@ -0,0 +1,16 @@
+use super::WorkflowPhase;
+
+#[derive(Debug, thiserror::Error)]
+pub enum WorkflowError {
+    #[error("command is not accepted in phase {phase:?}")]
+    UnexpectedCommand { phase: WorkflowPhase },
+
+    #[error("input must not be empty")]
+    EmptyInput,
+
+    #[error("{message}")]
+    InvalidSessionDirectory { message: String },
+
+    #[error("{message}")]
+    InvalidRequirementsSource { message: String },
+}
@@ -0,0 +1,283 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::coding::CodingTask;
+
+#[derive(Debug, Deserialize)]
+pub struct HandoffDocument {
+    pub summary: String,
+    pub public_interfaces: Vec<String>,
+    pub known_caveats: Vec<String>,
+    pub test_entry_points: Vec<String>,
+}
+
+pub fn handoff_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "summary": { "type": "string" },
+            "public_interfaces": {
+                "type": "array",
+                "items": { "type": "string" }
+            },
+            "known_caveats": {
+                "type": "array",
+                "items": { "type": "string" }
+            },
+            "test_entry_points": {
+                "type": "array",
+                "items": { "type": "string" }
+            }
+        },
+        "required": ["summary", "public_interfaces", "known_caveats", "test_entry_points"],
+        "additionalProperties": false
+    })
+}
+
+const HANDOFF_PROMPT_TEMPLATE: &str = r#"Your task {{TASK_ID}} ({{TASK_TITLE}}) has been reviewed, verified, and merged into the integration branch.
+
+Write a handoff document for the coding agents and reviewers of downstream tasks that depend on your work. They have NOT seen this session, so the handoff MUST contain everything they need to build on your changes without re-reading the whole diff.
+
+Use what you learned in this session and inspect the merged changes:
+- `git log --oneline {{MERGE_RANGE}}`
+- `git diff {{MERGE_RANGE}}`
+
+Files touched by the merge:
+{{FILES_TOUCHED}}
+
+Fill the fields as follows:
+- summary: 2-5 sentences on what the task delivered and how it fits into the system.
+- public_interfaces: every public interface added or changed (function/type signatures, modules, CLI options, configuration keys, endpoints), one entry each, with a short note on its contract.
+- known_caveats: limitations, assumptions, follow-ups, or pitfalls downstream tasks MUST know about. Use an empty array if there are none.
+- test_entry_points: test files, test names, or commands that exercise the delivered behavior.
+
+Do NOT modify any files in this step.
+Write in Korean, preserving code identifiers as-is.
+
+Output MUST be valid JSON conforming to the provided JSON Schema."#;
+
+pub fn build_handoff_prompt(
+    task: &CodingTask,
+    merge_range: &str,
+    files_touched: &[String],
+) -> String {
+    HANDOFF_PROMPT_TEMPLATE
+        .replace("{{TASK_ID}}", &task.task_id)
+        .replace("{{TASK_TITLE}}", &task.title)
+        .replace("{{MERGE_RANGE}}", merge_range)
+        .replace("{{FILES_TOUCHED}}", &format_bullet_list(files_touched))
+}
+
+/// 구조화된 핸드오프 응답과 병합으로 변경된 파일 목록을 Markdown 문서로 변환한다.
+pub fn render_handoff_markdown(
+    task: &CodingTask,
+    document: &HandoffDocument,
+    files_touched: &[String],
+) -> String {
+    let mut markdown = format!("# Handoff: {} {}\n\n", task.task_id, task.title);
+
+    markdown.push_str("## Summary\n");
+    markdown.push_str(document.summary.trim());
+    markdown.push_str("\n\n## Public Interfaces\n");
+    markdown.push_str(&format_bullet_list(&document.public_interfaces));
+    markdown.push_str("\n\n## Files Touched\n");
+    markdown.push_str(&format_bullet_list(files_touched));
+    markdown.push_str("\n\n## Known Caveats\n");
+    markdown.push_str(&format_bullet_list(&document.known_caveats));
+    markdown.push_str("\n\n## Test Entry Points\n");
+    markdown.push_str(&format_bullet_list(&document.test_entry_points));
+    markdown.push('\n');
+
+    markdown
+}
+
+fn format_bullet_list(items: &[String]) -> String {
+    if items.is_empty() {
+        return "- N/A".to_string();
+    }
+
+    items
+        .iter()
+        .map(|item| format!("- {}", item))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn handoff_file_path(dir: &Path, task_id: &str) -> PathBuf {
+    dir.join(format!("HANDOFF-{}.md", task_id))
+}
+
+pub fn save_handoff(dir: &Path, task_id: &str, content: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+
+    let file_path = handoff_file_path(dir, task_id);
+    fs::write(&file_path, content)?;
+
+    Ok(file_path)
+}
+
+/// 태스크가 직접 또는 간접적으로 의존하는 모든 태스크 ID를 의존성 순서대로 반환한다.
+pub fn collect_transitive_dependencies(task: &CodingTask, tasks: &[CodingTask]) -> Vec<String> {
+    let mut collected: Vec<String> = Vec::new();
+    let mut pending: Vec<String> = task.dependencies.iter().rev().cloned().collect();
+
+    while let Some(task_id) = pending.pop() {
+        if collected.contains(&task_id) {
+            continue;
+        }
+
+        if let Some(dependency) = tasks.iter().find(|t| t.task_id == task_id) {
+            pending.extend(dependency.dependencies.iter().rev().cloned());
+        }
+        collected.push(task_id);
+    }
+
+    // 태스크 목록은 위상 정렬되어 있으므로 목록 순서를 따르면 의존성 순서가 된다.
+    let position = |task_id: &String| {
+        tasks
+            .iter()
+            .position(|t| &t.task_id == task_id)
+            .unwrap_or(usize::MAX)
+    };
+    collected.sort_by_key(position);
+    collected
+}
+
+/// 전이 의존성을 포함한 상위 태스크 중 핸드오프 문서가 생성된 태스크의 경로를 반환한다.
+pub fn collect_upstream_handoff_paths(
+    task: &CodingTask,
+    tasks: &[CodingTask],
+    journal_dir: &Path,
+) -> Vec<PathBuf> {
+    collect_transitive_dependencies(task, tasks)
+        .iter()
+        .map(|task_id| handoff_file_path(journal_dir, task_id))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::coding::CodingTaskKind;
+    use tempfile::TempDir;
+
+    fn make_task(task_id: &str, dependencies: &[&str]) -> CodingTask {
+        CodingTask {
+            task_id: task_id.to_string(),
+            title: format!("{} 작업", task_id),
+            description: "설명".to_string(),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: Vec::new(),
+        }
+    }
+
+    fn make_document() -> HandoffDocument {
+        HandoffDocument {
+            summary: "파서 모듈을 추가했습니다.".to_string(),
+            public_interfaces: vec!["`pub fn parse(input: &str) -> Ast`".to_string()],
+            known_caveats: vec![],
+            test_entry_points: vec!["cargo test parser".to_string()],
+        }
+    }
+
+    #[test]
+    fn handoff_schema_is_valid_json() {
+        let schema = handoff_schema();
+        assert_eq!(schema["type"], "object");
+
+        let required = schema["required"].as_array().unwrap();
+        assert_eq!(required.len(), 4);
+        assert!(schema["properties"]["public_interfaces"].is_object());
+    }
+
+    #[test]
+    fn deserialize_handoff_document() {
+        let json = serde_json::json!({
+            "summary": "요약",
+            "public_interfaces": ["fn a()"],
+            "known_caveats": ["주의"],
+            "test_entry_points": []
+        });
+
+        let document: HandoffDocument = serde_json::from_value(json).unwrap();
+
+        assert_eq!(document.public_interfaces, vec!["fn a()"]);
+        assert!(document.test_entry_points.is_empty());
+    }
+
+    #[test]
+    fn handoff_prompt_contains_range_and_files() {
+        let task = make_task("TASK-01", &[]);
+        let prompt = build_handoff_prompt(
+            &task,
+            "abc123..bear/integration/x",
+            &["src/parser.rs".to_string()],
+        );
+
+        assert!(prompt.contains("TASK-01"));
+        assert!(prompt.contains("git diff abc123..bear/integration/x"));
+        assert!(prompt.contains("- src/parser.rs"));
+    }
+
+    #[test]
+    fn render_handoff_markdown_contains_all_sections() {
+        let task = make_task("TASK-01", &[]);
+        let markdown =
+            render_handoff_markdown(&task, &make_document(), &["src/parser.rs".to_string()]);
+
+        assert!(markdown.starts_with("# Handoff: TASK-01"));
+        assert!(markdown.contains("## Public Interfaces\n- `pub fn parse"));
+        assert!(markdown.contains("## Files Touched\n- src/parser.rs"));
+        assert!(markdown.contains("## Known Caveats\n- N/A"));
+        assert!(markdown.contains("## Test Entry Points\n- cargo test parser"));
+    }
+
+    #[test]
+    fn collect_transitive_dependencies_follows_chain() {
+        let tasks = vec![
+            make_task("TASK-00", &[]),
+            make_task("TASK-01", &["TASK-00"]),
+            make_task("TASK-02", &[]),
+            make_task("TASK-03", &["TASK-01", "TASK-02"]),
+        ];
+
+        let dependencies = collect_transitive_dependencies(&tasks[3], &tasks);
+
+        assert_eq!(dependencies, vec!["TASK-00", "TASK-01", "TASK-02"]);
+    }
+
+    #[test]
+    fn collect_transitive_dependencies_handles_diamond() {
+        let tasks = vec![
+            make_task("TASK-00", &[]),
+            make_task("TASK-01", &["TASK-00"]),
+            make_task("TASK-02", &["TASK-00"]),
+            make_task("TASK-03", &["TASK-01", "TASK-02"]),
+        ];
+
+        let dependencies = collect_transitive_dependencies(&tasks[3], &tasks);
+
+        assert_eq!(dependencies, vec!["TASK-00", "TASK-01", "TASK-02"]);
+    }
+
+    #[test]
+    fn collect_upstream_handoff_paths_returns_existing_files_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let tasks = vec![
+            make_task("TASK-00", &[]),
+            make_task("TASK-01", &["TASK-00"]),
+            make_task("TASK-02", &["TASK-01"]),
+        ];
+        save_handoff(temp_dir.path(), "TASK-00", "# Handoff").unwrap();
+
+        let paths = collect_upstream_handoff_paths(&tasks[2], &tasks, temp_dir.path());
+
+        assert_eq!(paths, vec![temp_dir.path().join("HANDOFF-TASK-00.md")]);
+    }
+}
@@ -0,0 +1,371 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::acceptance;
+use super::coding::{CodingTask, CodingTaskKind};
+
+pub const INTEGRATION_REVIEW_FILE_NAME: &str = "integration-review.md";
+const CLEANUP_COMMAND: &str = "/cleanup";
+
+#[derive(Debug, Deserialize)]
+pub struct IntegrationReviewResult {
+    pub findings: Vec<IntegrationFinding>,
+    pub summary: String,
+}
+
+/// 여러 태스크가 합쳐졌을 때만 드러나는 문제 하나.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct IntegrationFinding {
+    pub finding_id: String,
+    pub category: FindingCategory,
+    pub severity: FindingSeverity,
+    pub title: String,
+    pub description: String,
+    pub locations: Vec<String>,
+    pub related_task_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+pub enum FindingCategory {
+    #[serde(rename = "DUPLICATION")]
+    Duplication,
+    #[serde(rename = "INCONSISTENT_ERROR_HANDLING")]
+    InconsistentErrorHandling,
+    #[serde(rename = "NAMING_DRIFT")]
+    NamingDrift,
+    #[serde(rename = "INTERFACE_MISMATCH")]
+    InterfaceMismatch,
+    #[serde(rename = "DEAD_CODE")]
+    DeadCode,
+    #[serde(rename = "OTHER")]
+    Other,
+}
+
+impl FindingCategory {
+    fn label(self) -> &'static str {
+        match self {
+            FindingCategory::Duplication => "중복",
+            FindingCategory::InconsistentErrorHandling => "오류 처리 불일치",
+            FindingCategory::NamingDrift => "이름 불일치",
+            FindingCategory::InterfaceMismatch => "인터페이스 불일치",
+            FindingCategory::DeadCode => "사용되지 않는 코드",
+            FindingCategory::Other => "기타",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+pub enum FindingSeverity {
+    #[serde(rename = "HIGH")]
+    High,
+    #[serde(rename = "MEDIUM")]
+    Medium,
+    #[serde(rename = "LOW")]
+    Low,
+}
+
+impl FindingSeverity {
+    fn label(self) -> &'static str {
+        match self {
+            FindingSeverity::High => "HIGH",
+            FindingSeverity::Medium => "MEDIUM",
+            FindingSeverity::Low => "LOW",
+        }
+    }
+}
+
+pub fn integration_review_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "findings": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "finding_id": { "type": "string" },
+                        "category": {
+                            "type": "string",
+                            "enum": [
+                                "DUPLICATION",
+                                "INCONSISTENT_ERROR_HANDLING",
+                                "NAMING_DRIFT",
+                                "INTERFACE_MISMATCH",
+                                "DEAD_CODE",
+                                "OTHER"
+                            ]
+                        },
+                        "severity": {
+                            "type": "string",
+                            "enum": ["HIGH", "MEDIUM", "LOW"]
+                        },
+                        "title": { "type": "string" },
+                        "description": { "type": "string" },
+                        "locations": {
+                            "type": "array",
+                            "items": { "type": "string" }
+                        },
+                        "related_task_ids": {
+                            "type": "array",
+                            "items": { "type": "string" }
+                        }
+                    },
+                    "required": [
+                        "finding_id",
+                        "category",
+                        "severity",
+                        "title",
+                        "description",
+                        "locations",
+                        "related_task_ids"
+                    ],
+                    "additionalProperties": false
+                }
+            },
+            "summary": { "type": "string" }
+        },
+        "required": ["findings", "summary"],
+        "additionalProperties": false
+    })
+}
+
+pub fn system_prompt() -> &'static str {
+    r#"# Role
+
+You are the **integration review** assistant. Each task of this session was already reviewed on its own, against its own commit. Your job is to review the combined result of ALL tasks on the integration branch and find problems that only appear when the tasks are put together.
+
+**Core rules:**
+- Do NOT modify, create, or delete any files, and do NOT commit. Review only.
+- Do NOT repeat per-task review feedback (logic bugs inside one task's change). Focus on cross-task issues.
+- Be precise: every finding MUST point to concrete files and symbols.
+
+---
+
+# What to look for
+
+- DUPLICATION: helpers, types, constants, or logic implemented more than once by different tasks.
+- INCONSISTENT_ERROR_HANDLING: different error types, error propagation styles, or messages for the same kind of failure.
+- NAMING_DRIFT: the same concept named differently across tasks, or naming that breaks the conventions of the existing codebase.
+- INTERFACE_MISMATCH: modules from different tasks that connect awkwardly (adapter code, redundant conversions, mismatched signatures or assumptions).
+- DEAD_CODE: code added by one task that ended up unused after later tasks.
+- OTHER: any other cross-task integration problem worth a cleanup.
+
+---
+
+# Review process
+
+1. Read the specification, the plan, and the task reports listed in the prompt.
+2. Inspect the whole session diff with `git diff <range>` and `git log --oneline <range>`.
+3. Compare the changes of different tasks against each other and against the existing codebase.
+4. Report each problem as one finding. Report nothing if the tasks integrate cleanly.
+
+---
+
+# Output Format
+
+Return JSON following the given JSON Schema:
+- finding_id: `IR-<n>` numbered from 1.
+- category and severity (`HIGH`: causes or will likely cause defects, `MEDIUM`: maintainability cost worth fixing now, `LOW`: minor polish).
+- title: one line.
+- description: what is wrong and how a cleanup task should resolve it, precise enough for a coding agent to implement without further review.
+- locations: file paths with symbols (for example `src/config.rs::load_config`).
+- related_task_ids: the task IDs whose changes are involved, if known.
+- summary: 2-4 sentences on how well the tasks integrate.
+
+Write title, description and summary in Korean, preserving code identifiers as-is."#
+}
+
+const INTEGRATION_REVIEW_PROMPT_TEMPLATE: &str = r#"Review the combined changes of all tasks on the integration branch for cross-task integration problems.
+
+Output MUST be valid JSON conforming to the provided JSON Schema.
+
+---
+
+Review range:
+- `{{MERGE_RANGE}}` (the integration branch is checked out in the current working directory)
+
+You MUST read the following files before reviewing:
+- Specification:
+  - {{SPEC_PATH}}
+- Plan:
+  - {{PLAN_PATH}}
+- Task reports:
+{{TASK_REPORT_PATHS}}"#;
+
+pub fn build_integration_review_prompt(
+    spec_path: &Path,
+    plan_path: &Path,
+    task_report_paths: &[PathBuf],
+    merge_range: &str,
+) -> String {
+    let report_paths = if task_report_paths.is_empty() {
+        "  - N/A".to_string()
+    } else {
+        task_report_paths
+            .iter()
+            .map(|path| format!("  - {}", path.display()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    INTEGRATION_REVIEW_PROMPT_TEMPLATE
+        .replace("{{MERGE_RANGE}}", merge_range)
+        .replace("{{SPEC_PATH}}", &spec_path.display().to_string())
+        .replace("{{PLAN_PATH}}", &plan_path.display().to_string())
+        .replace("{{TASK_REPORT_PATHS}}", &report_paths)
+}
+
+pub fn format_integration_review_summary(result: &IntegrationReviewResult) -> String {
+    let mut summary = format!(
+        "통합 리뷰: 발견 사항 {}건\n{}",
+        result.findings.len(),
+        result.summary.trim(),
+    );
+    for finding in &result.findings {
+        summary.push_str(&format!(
+            "\n- [{}] {} ({}): {}",
+            finding.finding_id,
+            finding.severity.label(),
+            finding.category.label(),
+            finding.title,
+        ));
+    }
+
+    if !result.findings.is_empty() {
+        summary.push_str(&format!(
+            "\n\n발견 사항을 정리 작업으로 만들려면 `{} [<ID> ...]`를 입력하세요. ID를 생략하면 모든 발견 사항을 작업으로 만듭니다.",
+            CLEANUP_COMMAND,
+        ));
+    }
+    summary
+}
+
+pub fn render_integration_review_markdown(result: &IntegrationReviewResult) -> String {
+    let mut markdown = format!("# Integration Review\n\n{}\n", result.summary.trim());
+    for finding in &result.findings {
+        markdown.push_str(&format!(
+            "\n## {} {}\n\n- 분류: {}\n- 심각도: {}\n- 위치: {}\n- 관련 작업: {}\n\n{}\n",
+            finding.finding_id,
+            finding.title,
+            finding.category.label(),
+            finding.severity.label(),
+            join_or_placeholder(&finding.locations),
+            join_or_placeholder(&finding.related_task_ids),
+            finding.description.trim(),
+        ));
+    }
+    markdown
+}
+
+fn join_or_placeholder(items: &[String]) -> String {
+    if items.is_empty() {
+        "(없음)".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+pub fn save_integration_review(dir: &Path, result: &IntegrationReviewResult) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(INTEGRATION_REVIEW_FILE_NAME);
+    fs::write(&path, render_integration_review_markdown(result))?;
+    Ok(path)
+}
+
+/// `/cleanup IR-1 IR-3` 형식의 입력을 해석한다. ID가 없으면 빈 목록(모든 발견 사항)을 반환한다.
+/// 명령이 아니면 None을 반환한다.
+pub fn parse_cleanup_request(input: &str) -> Option<Vec<String>> {
+    acceptance::parse_command_ids(input, CLEANUP_COMMAND)
+}
+
+/// 통합 리뷰 발견 사항을 통합 브랜치에서 처리할 정리 태스크로 변환한다.
+pub fn build_cleanup_task(tasks: &[CodingTask], finding: &IntegrationFinding) -> CodingTask {
+    let description = format!(
+        "통합 리뷰에서 여러 작업이 합쳐지며 생긴 문제로 보고된 발견 사항입니다. \
+         통합 브랜치의 현재 코드를 기준으로 동작을 바꾸지 않고 정리하세요.\n\n\
+         발견 사항 ({}, {}):\n{}\n\n위치:\n{}",
+        finding.finding_id,
+        finding.category.label(),
+        finding.description.trim(),
+        join_or_placeholder(&finding.locations),
+    );
+
+    CodingTask {
+        task_id: acceptance::next_task_id(tasks),
+        title: format!("통합 정리: {}", finding.title),
+        description,
+        dependencies: Vec::new(),
+        kind: CodingTaskKind::Implementation,
+        retry: None,
+        requirement_ids: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_finding(id: &str, severity: FindingSeverity) -> IntegrationFinding {
+        IntegrationFinding {
+            finding_id: id.to_string(),
+            category: FindingCategory::Duplication,
+            severity,
+            title: "경로 정규화 함수 중복".to_string(),
+            description: "두 작업이 같은 함수를 따로 구현했습니다.".to_string(),
+            locations: vec!["src/a.rs::normalize".to_string(), "src/b.rs::normalize_path".to_string()],
+            related_task_ids: vec!["TASK-00".to_string(), "TASK-02".to_string()],
+        }
+    }
+
+    #[test]
+    fn integration_review_result_deserializes_findings() {
+        let json = serde_json::json!({
+            "findings": [{
+                "finding_id": "IR-1",
+                "category": "NAMING_DRIFT",
+                "severity": "LOW",
+                "title": "이름 불일치",
+                "description": "설명",
+                "locations": ["src/a.rs"],
+                "related_task_ids": []
+            }],
+            "summary": "요약"
+        });
+
+        let result: IntegrationReviewResult = serde_json::from_value(json).unwrap();
+
+        assert_eq!(result.findings[0].category, FindingCategory::NamingDrift);
+        assert_eq!(result.findings[0].severity, FindingSeverity::Low);
+    }
+
+    #[test]
+    fn summary_lists_findings_with_cleanup_hint() {
+        let result = IntegrationReviewResult {
+            findings: vec![make_finding("IR-1", FindingSeverity::High)],
+            summary: "중복이 있습니다.".to_string(),
+        };
+
+        let summary = format_integration_review_summary(&result);
+
+        assert!(summary.contains("- [IR-1] HIGH (중복): 경로 정규화 함수 중복"));
+        assert!(summary.contains("/cleanup"));
+
+        let clean = IntegrationReviewResult { findings: vec![], summary: "깔끔합니다.".to_string() };
+        assert!(!format_integration_review_summary(&clean).contains("/cleanup"));
+    }
+
+    #[test]
+    fn cleanup_task_describes_finding_and_locations() {
+        let task = build_cleanup_task(&[], &make_finding("IR-2", FindingSeverity::Medium));
+
+        assert_eq!(task.task_id, "TASK-00");
+        assert_eq!(task.title, "통합 정리: 경로 정규화 함수 중복");
+        assert!(task.description.contains("IR-2"));
+        assert!(task.description.contains("src/b.rs::normalize_path"));
+        assert_eq!(parse_cleanup_request("/cleanup IR-2"), Some(vec!["IR-2".to_string()]));
+        assert_eq!(parse_cleanup_request("/cleanups"), None);
+    }
+}
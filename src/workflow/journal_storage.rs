@@ -0,0 +1,197 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::git::{self, GitError};
+
+use super::cleanup;
+use super::commit_policy::CommitPolicy;
+
+/// 코드 이력과 섞이지 않는 저널 전용 고아 브랜치.
+pub const JOURNAL_BRANCH: &str = "bear/journal";
+const JOURNAL_ROOT_NAME_MARKER: &str = "-bear-journal";
+const IGNORE_EVERYTHING: &str = "*\n";
+
+/// 세션 저널(`.bear/<date>/<session>/`)을 보관하는 방식.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalStorage {
+    /// 저널을 통합 브랜치와 태스크 커밋에 함께 커밋한다.
+    #[default]
+    InTree,
+    /// 워크스페이스 옆 저널 워크트리에 두고 `bear/journal` 고아 브랜치에 커밋한다.
+    OrphanBranch,
+    /// 워크스페이스 옆의 추적되지 않는 디렉토리에 두고 커밋하지 않는다.
+    External,
+}
+
+impl JournalStorage {
+    pub fn is_in_tree(self) -> bool {
+        self == JournalStorage::InTree
+    }
+}
+
+/// 코드 밖에 둔 저널의 루트 `<workspace>-bear-journal`. 그 아래도 `.bear/<date>/<session>` 구조를 쓴다.
+pub fn external_journal_root(workspace: &Path) -> PathBuf {
+    let workspace_dir_name = workspace
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("workspace");
+
+    workspace
+        .parent()
+        .unwrap_or(workspace)
+        .join(format!("{}{}", workspace_dir_name, JOURNAL_ROOT_NAME_MARKER))
+}
+
+/// 고아 브랜치 방식이면 저널 루트에 `bear/journal` 워크트리를 준비한다. 이미 있으면 그대로 쓴다.
+pub fn prepare_journal_root(
+    workspace: &Path,
+    storage: JournalStorage,
+    policy: &CommitPolicy,
+) -> Result<(), GitError> {
+    if storage != JournalStorage::OrphanBranch {
+        return Ok(());
+    }
+    let journal_root = external_journal_root(workspace);
+    if cleanup::find_branch_worktree(workspace, JOURNAL_BRANCH).as_deref() == Some(journal_root.as_path()) {
+        return Ok(());
+    }
+
+    // 손으로 지운 저널 루트는 워크트리 메타데이터가 남아 `worktree add`를 막는다.
+    git::run(workspace, ["worktree", "prune"])?;
+    let journal_root_arg = journal_root.display().to_string();
+    let branch_ref = format!("refs/heads/{}", JOURNAL_BRANCH);
+    if git::succeeds(workspace, ["rev-parse", "--verify", "--quiet", &branch_ref]) {
+        git::run(workspace, ["worktree", "add", &journal_root_arg, JOURNAL_BRANCH])?;
+        return Ok(());
+    }
+
+    git::run(workspace, ["worktree", "add", "--detach", &journal_root_arg])?;
+    git::run(&journal_root, ["checkout", "--orphan", JOURNAL_BRANCH])?;
+    git::run(&journal_root, ["rm", "-r", "-f", "--quiet", "--ignore-unmatch", "."])?;
+    git::run(
+        &journal_root,
+        policy.commit_arguments(&["--allow-empty", "-m", "Start Bear journal"]),
+    )?;
+    Ok(())
+}
+
+/// 세션 디렉토리가 놓인 위치로 그 세션이 쓰던 보관 방식을 알아낸다.
+pub fn storage_of_session(workspace: &Path, session_dir: &Path) -> JournalStorage {
+    let journal_root = external_journal_root(workspace);
+    if !session_dir.starts_with(&journal_root) {
+        return JournalStorage::InTree;
+    }
+    if cleanup::find_branch_worktree(workspace, JOURNAL_BRANCH).as_deref() == Some(journal_root.as_path()) {
+        JournalStorage::OrphanBranch
+    } else {
+        JournalStorage::External
+    }
+}
+
+/// 태스크 워크트리에 복사한 저널 사본이 에이전트의 커밋에 섞이지 않도록 디렉토리 전체를 무시한다.
+/// `*` 패턴은 `.gitignore` 자신도 무시하므로 워크트리에는 아무 변경도 남지 않는다.
+pub fn hide_from_git(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(".gitignore"), IGNORE_EVERYTHING)
+}
+
+/// 지울 워크트리 안의 세션 저널을 추적하지 않는 파일까지 코드 밖 저널 루트의 같은 위치로 복사하고 새 경로를 반환한다.
+pub fn copy_journal_out_of_worktree(workspace: &Path, worktree: &Path, journal_dir: &Path) -> io::Result<PathBuf> {
+    let relative = journal_dir.strip_prefix(worktree).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not inside {}", journal_dir.display(), worktree.display()),
+        )
+    })?;
+    let destination = external_journal_root(workspace).join(relative);
+    copy_directory(journal_dir, &destination)?;
+    Ok(destination)
+}
+
+fn copy_directory(source: &Path, destination: &Path) -> io::Result<()> {
+    fs::create_dir_all(destination)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = destination.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_directory(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run_git(dir: &Path, args: &[&str]) -> String {
+        git::run(dir, args).unwrap()
+    }
+
+    fn init_git_repo(dir: &Path) {
+        run_git(dir, &["init"]);
+        run_git(dir, &["symbolic-ref", "HEAD", "refs/heads/master"]);
+        run_git(dir, &["config", "user.email", "test@test.com"]);
+        run_git(dir, &["config", "user.name", "Test"]);
+        run_git(dir, &["config", "commit.gpgsign", "false"]);
+        fs::write(dir.join("init.txt"), "init").unwrap();
+        run_git(dir, &["add", "init.txt"]);
+        run_git(dir, &["commit", "-m", "initial commit"]);
+    }
+
+    #[test]
+    fn orphan_branch_journal_shares_no_history_with_code() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("repo");
+        fs::create_dir_all(&workspace).unwrap();
+        init_git_repo(&workspace);
+        let policy = CommitPolicy::default();
+
+        prepare_journal_root(&workspace, JournalStorage::OrphanBranch, &policy).unwrap();
+        prepare_journal_root(&workspace, JournalStorage::OrphanBranch, &policy).unwrap();
+
+        let journal_root = external_journal_root(&workspace);
+        assert_eq!(journal_root, temp_dir.path().join("repo-bear-journal"));
+        assert!(!journal_root.join("init.txt").exists());
+        assert!(!git::succeeds(&workspace, ["merge-base", "master", JOURNAL_BRANCH]));
+
+        let session_dir = journal_root.join(".bear").join("20260101").join("session-1");
+        fs::create_dir_all(&session_dir).unwrap();
+        assert_eq!(storage_of_session(&workspace, &session_dir), JournalStorage::OrphanBranch);
+        assert_eq!(
+            storage_of_session(&workspace, &workspace.join(".bear").join("20260101").join("session-1")),
+            JournalStorage::InTree,
+        );
+    }
+
+    #[test]
+    fn hidden_journal_copy_leaves_worktree_clean() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path());
+        let journal_copy = temp_dir.path().join(".bear").join("20260101").join("session-1");
+
+        hide_from_git(&journal_copy).unwrap();
+        fs::write(journal_copy.join("spec.md"), "spec").unwrap();
+
+        assert_eq!(run_git(temp_dir.path(), &["status", "--porcelain", "--untracked-files=all"]), "");
+    }
+
+    #[test]
+    fn external_storage_needs_no_preparation() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("repo");
+        let session_dir = external_journal_root(&workspace).join(".bear").join("20260101").join("s");
+
+        prepare_journal_root(&workspace, JournalStorage::External, &CommitPolicy::default()).unwrap();
+
+        assert!(!external_journal_root(&workspace).exists());
+        assert_eq!(storage_of_session(&workspace, &session_dir), JournalStorage::External);
+    }
+}
@@ -164,6 +164,7 @@ For every task you output, provide enough detail that a coding agent can impleme
 - Inputs/Outputs: interfaces, files, modules, endpoints, or data structures it touches.
 - Constraints: performance, security, compatibility, error handling, logging, and style requirements if relevant.
 - Acceptance criteria: concrete checks/tests that define "done".
+- Requirement IDs: the spec requirement IDs (`REQ-xxx`) the task implements. Every requirement ID in the spec MUST be covered by at least one task.
 - Dependency list: prerequisite task IDs (or "none").
 
 4) Right-size tasks: prefer fewer, cohesive tasks over many fine-grained ones.
@@ -392,6 +393,7 @@ When you finish you MUST produce an output in Markdown format that includes:
      - Current logic flow in pseudocode (if existing logic is being modified)
      - New logic flow in pseudocode
      - Dependencies (an adjacency list of prerequisite task IDs, or "none")
+     - Requirement IDs covered (`REQ-xxx` from the spec)
    - Size each task as a cohesive, meaningful deliverable — not as a single file edit or implementation step. Refer to the "Task Decomposition Rules" for detailed sizing guidance.
 
 5. **Testing & Validation**
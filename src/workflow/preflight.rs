@@ -0,0 +1,326 @@
+use std::path::Path;
+
+use crate::git::{self, GitError};
+
+use super::commit_policy::CommitPolicy;
+use super::project_config;
+
+const BEAR_IDENTITY_NAME: &str = "Bear";
+const BEAR_IDENTITY_EMAIL: &str = "bear@localhost";
+const MAX_LISTED_FILES: usize = 10;
+
+/// 세션을 시작하기 전에 발견한 워크스페이스 문제.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreflightIssue {
+    NotGitRepository,
+    NoCommits,
+    DetachedHead,
+    OperationInProgress(git::Operation),
+    DirtyWorkingTree { files: Vec<String> },
+    MissingIdentity,
+}
+
+/// 문제를 해결하기 위해 Bear가 대신 실행할 수 있는 조치.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PreflightRemedy {
+    InitRepository,
+    SetLocalIdentity,
+    CreateInitialCommit,
+    StashChanges,
+}
+
+impl PreflightIssue {
+    /// Bear가 대신 해결할 수 없는 문제는 None이다.
+    pub fn remedy(&self) -> Option<PreflightRemedy> {
+        match self {
+            PreflightIssue::NotGitRepository => Some(PreflightRemedy::InitRepository),
+            PreflightIssue::NoCommits => Some(PreflightRemedy::CreateInitialCommit),
+            PreflightIssue::DirtyWorkingTree { .. } => Some(PreflightRemedy::StashChanges),
+            PreflightIssue::MissingIdentity => Some(PreflightRemedy::SetLocalIdentity),
+            PreflightIssue::DetachedHead | PreflightIssue::OperationInProgress(_) => None,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            PreflightIssue::NotGitRepository => "git 저장소가 아닙니다.".to_string(),
+            PreflightIssue::NoCommits => {
+                "커밋이 하나도 없어 통합 브랜치를 만들 기준이 없습니다.".to_string()
+            }
+            PreflightIssue::DetachedHead => {
+                "HEAD가 브랜치를 가리키지 않습니다 (detached HEAD). 작업을 머지할 브랜치를 체크아웃하세요."
+                    .to_string()
+            }
+            PreflightIssue::OperationInProgress(operation) => format!(
+                "진행 중인 git {} 작업이 있습니다. `git {} --continue` 또는 `--abort`로 끝내세요.",
+                operation.label(),
+                operation.label(),
+            ),
+            PreflightIssue::DirtyWorkingTree { files } => {
+                let mut description = format!("커밋되지 않은 변경이 {}개 있습니다:", files.len());
+                for file in files.iter().take(MAX_LISTED_FILES) {
+                    description.push_str(&format!("\n    {}", file));
+                }
+                if files.len() > MAX_LISTED_FILES {
+                    description.push_str(&format!("\n    외 {}개", files.len() - MAX_LISTED_FILES));
+                }
+                description
+            }
+            PreflightIssue::MissingIdentity => {
+                "커밋에 쓸 git 사용자 이름(user.name) 또는 이메일(user.email)이 설정되지 않았습니다.".to_string()
+            }
+        }
+    }
+}
+
+impl PreflightRemedy {
+    pub fn description(self) -> String {
+        match self {
+            PreflightRemedy::InitRepository => "`git init`으로 저장소를 만듭니다.".to_string(),
+            PreflightRemedy::SetLocalIdentity => format!(
+                "이 저장소에만 적용되는 커밋 사용자를 `{} <{}>`로 설정합니다.",
+                BEAR_IDENTITY_NAME, BEAR_IDENTITY_EMAIL,
+            ),
+            PreflightRemedy::CreateInitialCommit => "빈 초기 커밋을 만듭니다.".to_string(),
+            PreflightRemedy::StashChanges => {
+                "변경 사항을 `git stash`로 보관합니다. 나중에 `git stash pop`으로 되돌릴 수 있습니다."
+                    .to_string()
+            }
+        }
+    }
+}
+
+/// 세션을 시작할 수 있는 워크스페이스인지 검사한다. git 저장소가 아니면 다른 검사는 하지 않는다.
+pub fn run_preflight_checks(workspace: &Path) -> Vec<PreflightIssue> {
+    let Ok(operation) = git::operation_in_progress(workspace) else {
+        return vec![PreflightIssue::NotGitRepository];
+    };
+
+    let mut issues = Vec::new();
+    if let Some(operation) = operation {
+        issues.push(PreflightIssue::OperationInProgress(operation));
+    }
+    if !has_commits(workspace) {
+        issues.push(PreflightIssue::NoCommits);
+    } else if is_detached_head(workspace) {
+        issues.push(PreflightIssue::DetachedHead);
+    }
+    let changed_files = list_uncommitted_changes(workspace);
+    if !changed_files.is_empty() {
+        issues.push(PreflightIssue::DirtyWorkingTree { files: changed_files });
+    }
+    if !has_identity(workspace) && load_commit_policy(workspace).identity.is_none() {
+        issues.push(PreflightIssue::MissingIdentity);
+    }
+    issues
+}
+
+/// 문제와 해결 방법을 사용자에게 보여줄 메시지로 만든다.
+pub fn format_preflight_report(issues: &[PreflightIssue]) -> String {
+    let mut report = String::from("워크스페이스 사전 점검에서 문제를 발견했습니다:");
+    for issue in issues {
+        report.push_str(&format!("\n- {}", issue.description()));
+        match issue.remedy() {
+            Some(remedy) => report.push_str(&format!("\n  해결: {}", remedy.description())),
+            None => report.push_str("\n  해결: 직접 정리한 뒤 다시 시도하세요."),
+        }
+    }
+    report
+}
+
+/// 모든 문제에 Bear가 실행할 수 있는 조치가 있으면 실행 순서대로 반환한다. 하나라도 없으면 None이다.
+pub fn planned_remedies(issues: &[PreflightIssue]) -> Option<Vec<PreflightRemedy>> {
+    let mut remedies: Vec<PreflightRemedy> = issues
+        .iter()
+        .map(PreflightIssue::remedy)
+        .collect::<Option<_>>()?;
+    // 초기 커밋에는 저장소와 사용자 정보가, stash에는 커밋이 먼저 있어야 한다.
+    remedies.sort_by_key(|remedy| match remedy {
+        PreflightRemedy::InitRepository => 0,
+        PreflightRemedy::SetLocalIdentity => 1,
+        PreflightRemedy::CreateInitialCommit => 2,
+        PreflightRemedy::StashChanges => 3,
+    });
+    Some(remedies)
+}
+
+pub fn apply_remedy(workspace: &Path, remedy: PreflightRemedy) -> Result<(), GitError> {
+    match remedy {
+        PreflightRemedy::InitRepository => {
+            git::run(workspace, ["init"])?;
+        }
+        PreflightRemedy::SetLocalIdentity => {
+            if read_git_config(workspace, "user.name").is_none() {
+                git::run(workspace, ["config", "--local", "user.name", BEAR_IDENTITY_NAME])?;
+            }
+            if read_git_config(workspace, "user.email").is_none() {
+                git::run(workspace, ["config", "--local", "user.email", BEAR_IDENTITY_EMAIL])?;
+            }
+        }
+        PreflightRemedy::CreateInitialCommit => {
+            let policy = load_commit_policy(workspace);
+            git::run(workspace, policy.commit_arguments(&["--allow-empty", "-m", "Initial commit"]))?;
+        }
+        PreflightRemedy::StashChanges => {
+            // stash도 커밋을 만들므로 정책의 사용자 정보가 필요하다.
+            let mut arguments = load_commit_policy(workspace).git_config_arguments();
+            arguments.extend(["stash", "push", "-m", "Stashed by Bear before starting a session"].map(String::from));
+            git::run(workspace, arguments)?;
+        }
+    }
+    Ok(())
+}
+
+fn has_commits(workspace: &Path) -> bool {
+    git::succeeds(workspace, ["rev-parse", "--verify", "--quiet", "HEAD"])
+}
+
+fn is_detached_head(workspace: &Path) -> bool {
+    !git::succeeds(workspace, ["symbolic-ref", "--quiet", "HEAD"])
+}
+
+/// 추적 중인 파일의 커밋되지 않은 변경. 추적하지 않는 파일은 세션에 영향을 주지 않으므로 제외한다.
+fn list_uncommitted_changes(workspace: &Path) -> Vec<String> {
+    git::run(workspace, ["status", "--porcelain", "--untracked-files=no"])
+        .unwrap_or_default()
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+fn has_identity(workspace: &Path) -> bool {
+    read_git_config(workspace, "user.name").is_some() && read_git_config(workspace, "user.email").is_some()
+}
+
+/// `.bear/config.json`의 커밋 정책. 설정 파일을 읽지 못하면 세션 시작 때 따로 보고되므로 여기서는 기본값을 쓴다.
+fn load_commit_policy(workspace: &Path) -> CommitPolicy {
+    project_config::load_project_config(workspace)
+        .map(|config| config.commit_policy)
+        .unwrap_or_default()
+}
+
+fn read_git_config(workspace: &Path, key: &str) -> Option<String> {
+    git::run(workspace, ["config", "--get", key])
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        git::run(dir, args).unwrap();
+    }
+
+    /// 전역 git 설정과 무관하게 동작하도록 저장소 설정에 사용자를 지정한다.
+    fn init_git_repo(dir: &Path) {
+        git(dir, &["init", "-b", "main"]);
+        git(dir, &["config", "user.email", "test@test.com"]);
+        git(dir, &["config", "user.name", "Test"]);
+        git(dir, &["config", "commit.gpgsign", "false"]);
+        fs::write(dir.join("init.txt"), "init").unwrap();
+        git(dir, &["add", "init.txt"]);
+        git(dir, &["commit", "-m", "initial commit"]);
+    }
+
+    #[test]
+    fn clean_repository_passes() {
+        let temp_dir = TempDir::new().unwrap();
+        init_git_repo(temp_dir.path());
+        fs::write(temp_dir.path().join("untracked.txt"), "new").unwrap();
+
+        assert!(run_preflight_checks(temp_dir.path()).is_empty());
+    }
+
+    #[test]
+    fn plain_directory_is_not_a_repository() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let issues = run_preflight_checks(temp_dir.path());
+
+        assert_eq!(issues, vec![PreflightIssue::NotGitRepository]);
+        assert_eq!(planned_remedies(&issues), Some(vec![PreflightRemedy::InitRepository]));
+    }
+
+    #[test]
+    fn detects_dirty_tree_detached_head_and_merge_in_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path();
+        init_git_repo(workspace);
+        git(workspace, &["checkout", "--detach"]);
+        fs::write(workspace.join("init.txt"), "changed").unwrap();
+        let git_dir = git::git_dir(workspace).unwrap();
+        fs::write(git_dir.join("MERGE_HEAD"), "0000").unwrap();
+
+        let issues = run_preflight_checks(workspace);
+
+        assert_eq!(
+            issues,
+            vec![
+                PreflightIssue::OperationInProgress(git::Operation::Merge),
+                PreflightIssue::DetachedHead,
+                PreflightIssue::DirtyWorkingTree { files: vec!["M init.txt".to_string()] },
+            ]
+        );
+        assert_eq!(planned_remedies(&issues), None);
+        let report = format_preflight_report(&issues);
+        assert!(report.contains("git merge"));
+        assert!(report.contains("직접 정리한 뒤 다시 시도하세요."));
+    }
+
+    #[test]
+    fn remedies_prepare_a_new_repository_and_stash_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path();
+
+        for remedy in planned_remedies(&run_preflight_checks(workspace)).unwrap() {
+            apply_remedy(workspace, remedy).unwrap();
+        }
+        // 새 저장소에는 아직 커밋이 없고, 이 머신에 사용자 정보가 없을 수도 있다.
+        let issues = run_preflight_checks(workspace);
+        assert!(issues.contains(&PreflightIssue::NoCommits));
+        git(workspace, &["config", "commit.gpgsign", "false"]);
+        for remedy in planned_remedies(&issues).unwrap() {
+            apply_remedy(workspace, remedy).unwrap();
+        }
+        assert!(run_preflight_checks(workspace).is_empty());
+
+        fs::write(workspace.join("file.txt"), "v1").unwrap();
+        git(workspace, &["add", "file.txt"]);
+        git(workspace, &["commit", "-m", "add file"]);
+        fs::write(workspace.join("file.txt"), "v2").unwrap();
+        let issues = run_preflight_checks(workspace);
+        assert_eq!(planned_remedies(&issues), Some(vec![PreflightRemedy::StashChanges]));
+        apply_remedy(workspace, PreflightRemedy::StashChanges).unwrap();
+
+        assert!(run_preflight_checks(workspace).is_empty());
+        assert_eq!(fs::read_to_string(workspace.join("file.txt")).unwrap(), "v1");
+    }
+
+    #[test]
+    fn commit_policy_identity_satisfies_the_identity_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path();
+        git(workspace, &["init", "-b", "main"]);
+        git(workspace, &["config", "commit.gpgsign", "false"]);
+        fs::create_dir_all(workspace.join(".bear")).unwrap();
+        fs::write(
+            workspace.join(".bear/config.json"),
+            r#"{"commit_policy": {"identity": {"name": "Policy", "email": "policy@example.com"}}}"#,
+        )
+        .unwrap();
+
+        let issues = run_preflight_checks(workspace);
+        assert_eq!(issues, vec![PreflightIssue::NoCommits]);
+        apply_remedy(workspace, PreflightRemedy::CreateInitialCommit).unwrap();
+
+        assert!(run_preflight_checks(workspace).is_empty());
+        let author = git::run(workspace, ["log", "-1", "--format=%an <%ae>"]).unwrap();
+        assert_eq!(author, "Policy <policy@example.com>");
+    }
+}
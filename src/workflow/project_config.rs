@@ -0,0 +1,173 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::commit_policy::CommitPolicy;
+use super::journal_storage::JournalStorage;
+use super::verification_pipeline::{self, VerificationStage};
+
+pub const PROJECT_CONFIG_FILE_NAME: &str = "config.json";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProjectConfigError {
+    #[error("failed to read {path}: {source}")]
+    ReadFailed {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse {path}: {source}")]
+    ParseFailed {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[error("invalid {path}: {message}")]
+    Invalid { path: PathBuf, message: String },
+
+    #[error("failed to write {path}: {source}")]
+    WriteFailed {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// 저장소의 `.bear/config.json`에 둔 프로젝트별 설정. 파일이 없으면 기본값을 쓴다.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    pub commit_policy: CommitPolicy,
+    pub journal_storage: JournalStorage,
+    /// 태스크를 머지하기 전에 실행할 검증 단계. 비어 있으면 빌드 시스템을 감지하거나 사용자에게 묻는다.
+    pub verification_pipeline: Vec<VerificationStage>,
+}
+
+pub fn project_config_path(workspace: &Path) -> PathBuf {
+    workspace.join(".bear").join(PROJECT_CONFIG_FILE_NAME)
+}
+
+pub fn load_project_config(workspace: &Path) -> Result<ProjectConfig, ProjectConfigError> {
+    let path = project_config_path(workspace);
+    if !path.is_file() {
+        return Ok(ProjectConfig::default());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|source| ProjectConfigError::ReadFailed {
+        path: path.clone(),
+        source,
+    })?;
+    let config: ProjectConfig = serde_json::from_str(&content).map_err(|source| {
+        ProjectConfigError::ParseFailed {
+            path: path.clone(),
+            source,
+        }
+    })?;
+    config
+        .commit_policy
+        .validate()
+        .and_then(|()| verification_pipeline::validate_pipeline(&config.verification_pipeline))
+        .map_err(|message| ProjectConfigError::Invalid { path, message })?;
+    Ok(config)
+}
+
+/// 검증 파이프라인을 프로젝트 설정 파일에 저장한다. 파일의 다른 설정은 그대로 둔다.
+pub fn save_verification_pipeline(
+    workspace: &Path,
+    stages: &[VerificationStage],
+) -> Result<PathBuf, ProjectConfigError> {
+    let path = project_config_path(workspace);
+    let mut document = if path.is_file() {
+        let content = fs::read_to_string(&path).map_err(|source| ProjectConfigError::ReadFailed {
+            path: path.clone(),
+            source,
+        })?;
+        serde_json::from_str(&content).map_err(|source| ProjectConfigError::ParseFailed {
+            path: path.clone(),
+            source,
+        })?
+    } else {
+        serde_json::Value::Object(serde_json::Map::new())
+    };
+    let Some(settings) = document.as_object_mut() else {
+        return Err(ProjectConfigError::Invalid {
+            path,
+            message: "top level must be an object".to_string(),
+        });
+    };
+    let pipeline = serde_json::to_value(stages).expect("verification stages always serialize");
+    settings.insert("verification_pipeline".to_string(), pipeline);
+
+    let content = serde_json::to_string_pretty(&document).expect("JSON values always serialize");
+    fs::create_dir_all(path.parent().unwrap_or(workspace))
+        .and_then(|()| fs::write(&path, content))
+        .map_err(|source| ProjectConfigError::WriteFailed {
+            path: path.clone(),
+            source,
+        })?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::commit_policy::ReportCommitMode;
+    use tempfile::TempDir;
+
+    fn write_config(workspace: &Path, content: &str) {
+        fs::create_dir_all(workspace.join(".bear")).unwrap();
+        fs::write(project_config_path(workspace), content).unwrap();
+    }
+
+    #[test]
+    fn missing_file_yields_default_config() {
+        let temp_dir = TempDir::new().unwrap();
+
+        assert_eq!(load_project_config(temp_dir.path()).unwrap(), ProjectConfig::default());
+    }
+
+    #[test]
+    fn partial_commit_policy_keeps_other_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        write_config(
+            temp_dir.path(),
+            r#"{ "commit_policy": { "report_commit": "separate" }, "journal_storage": "orphan_branch" }"#,
+        );
+
+        let config = load_project_config(temp_dir.path()).unwrap();
+
+        assert_eq!(config.commit_policy.report_commit, ReportCommitMode::Separate);
+        assert_eq!(config.commit_policy.task_message_template, "{summary}");
+        assert_eq!(config.journal_storage, JournalStorage::OrphanBranch);
+    }
+
+    #[test]
+    fn saved_pipeline_is_loaded_and_keeps_other_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        write_config(temp_dir.path(), r#"{ "commit_policy": { "report_commit": "separate" } }"#);
+        let mut pipeline = verification_pipeline::build_and_test_stages("make build", "make test");
+        pipeline[1].required = false;
+
+        save_verification_pipeline(temp_dir.path(), &pipeline).unwrap();
+        let config = load_project_config(temp_dir.path()).unwrap();
+
+        assert_eq!(config.verification_pipeline, pipeline);
+        assert_eq!(config.commit_policy.report_commit, ReportCommitMode::Separate);
+    }
+
+    #[test]
+    fn invalid_or_unknown_settings_are_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        write_config(temp_dir.path(), r#"{ "commit_policy": { "task_branch_template": "task-{uuid}" } }"#);
+        assert!(matches!(
+            load_project_config(temp_dir.path()),
+            Err(ProjectConfigError::Invalid { .. })
+        ));
+
+        write_config(temp_dir.path(), r#"{ "commit_polcy": {} }"#);
+        assert!(matches!(
+            load_project_config(temp_dir.path()),
+            Err(ProjectConfigError::ParseFailed { .. })
+        ));
+    }
+}
@@ -0,0 +1,405 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+const SOURCES_DIR_NAME: &str = "sources";
+const IMPORT_PREFIX: char = '@';
+const DIRECTORY_SOURCE_EXTENSIONS: [&str; 4] = ["md", "markdown", "txt", "json"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceKind {
+    Document,
+    GitHubIssue,
+    GitLabIssue,
+}
+
+impl SourceKind {
+    fn label(self) -> &'static str {
+        match self {
+            SourceKind::Document => "document",
+            SourceKind::GitHubIssue => "GitHub issue export",
+            SourceKind::GitLabIssue => "GitLab issue export",
+        }
+    }
+}
+
+/// 요구사항으로 가져온 원본 파일. `content`는 user-request.md에 넣을 정규화된 Markdown이다.
+#[derive(Debug, Clone)]
+pub struct RequirementSource {
+    pub original_path: PathBuf,
+    pub kind: SourceKind,
+    pub content: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct IssueComment {
+    author: String,
+    body: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct IssueExport {
+    kind: SourceKind,
+    title: String,
+    body: String,
+    labels: Vec<String>,
+    comments: Vec<IssueComment>,
+    url: Option<String>,
+}
+
+/// 요구사항 입력을 직접 입력한 텍스트와 `@<경로>` 줄로 지정한 가져오기 대상으로 나눈다.
+pub fn split_import_references(input: &str) -> (String, Vec<String>) {
+    let mut typed_lines = Vec::new();
+    let mut references = Vec::new();
+
+    for line in input.lines() {
+        match line.trim().strip_prefix(IMPORT_PREFIX) {
+            Some(reference) if !reference.trim().is_empty() => {
+                references.push(reference.trim().to_string());
+            }
+            _ => typed_lines.push(line),
+        }
+    }
+
+    (typed_lines.join("\n").trim().to_string(), references)
+}
+
+/// `@` 참조를 파일 목록으로 읽는다. 상대 경로는 워크스페이스 기준이며, 디렉토리는 하위의
+/// Markdown/텍스트/이슈 JSON 파일을 이름 순으로 모두 가져온다.
+pub fn load_sources(references: &[String], workspace: &Path) -> Result<Vec<RequirementSource>, String> {
+    let mut sources = Vec::new();
+
+    for reference in references {
+        let path = resolve_path(reference, workspace);
+        if path.is_dir() {
+            let files = collect_directory_files(&path)
+                .map_err(|err| format!("디렉토리를 읽을 수 없습니다: {} ({})", path.display(), err))?;
+            if files.is_empty() {
+                return Err(format!("가져올 요구사항 파일이 없습니다: {}", path.display()));
+            }
+            for file in files {
+                sources.push(load_source_file(&file)?);
+            }
+        } else if path.is_file() {
+            sources.push(load_source_file(&path)?);
+        } else {
+            return Err(format!("요구사항 파일을 찾을 수 없습니다: {}", path.display()));
+        }
+    }
+
+    Ok(sources)
+}
+
+fn resolve_path(reference: &str, workspace: &Path) -> PathBuf {
+    let path = PathBuf::from(reference);
+    if path.is_absolute() {
+        path
+    } else {
+        workspace.join(path)
+    }
+}
+
+fn collect_directory_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<_>>()?;
+    entries.sort();
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let is_hidden = entry
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if is_hidden {
+            continue;
+        }
+
+        if entry.is_dir() {
+            files.extend(collect_directory_files(&entry)?);
+        } else if has_source_extension(&entry) {
+            files.push(entry);
+        }
+    }
+    Ok(files)
+}
+
+fn has_source_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            DIRECTORY_SOURCE_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+        })
+}
+
+fn load_source_file(path: &Path) -> Result<RequirementSource, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("요구사항 파일을 읽을 수 없습니다: {} ({})", path.display(), err))?;
+
+    let is_json = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    if !is_json {
+        return Ok(RequirementSource {
+            original_path: path.to_path_buf(),
+            kind: SourceKind::Document,
+            content: text.trim().to_string(),
+        });
+    }
+
+    let issue = parse_issue_export(&text)
+        .map_err(|message| format!("이슈 JSON을 해석할 수 없습니다: {} ({})", path.display(), message))?;
+    Ok(RequirementSource {
+        original_path: path.to_path_buf(),
+        kind: issue.kind,
+        content: format_issue(&issue),
+    })
+}
+
+/// `gh issue view --json` 또는 GitLab API 형식의 이슈 JSON을 읽는다.
+/// GitLab은 본문을 `description`에, 댓글을 `notes`에 담는다.
+fn parse_issue_export(text: &str) -> Result<IssueExport, String> {
+    let value: Value = serde_json::from_str(text).map_err(|err| err.to_string())?;
+    let title = string_field(&value, "title").ok_or("title 필드가 없습니다")?;
+
+    let kind = if value.get("description").is_some() && value.get("body").is_none() {
+        SourceKind::GitLabIssue
+    } else {
+        SourceKind::GitHubIssue
+    };
+    let (body_field, comments_field, url_fields) = match kind {
+        SourceKind::GitLabIssue => ("description", "notes", ["web_url", "url"]),
+        _ => ("body", "comments", ["url", "html_url"]),
+    };
+
+    let labels = value
+        .get("labels")
+        .and_then(Value::as_array)
+        .map(|labels| labels.iter().filter_map(label_name).collect())
+        .unwrap_or_default();
+    let comments = value
+        .get(comments_field)
+        .and_then(Value::as_array)
+        .map(|comments| comments.iter().filter_map(parse_comment).collect())
+        .unwrap_or_default();
+
+    Ok(IssueExport {
+        kind,
+        title,
+        body: string_field(&value, body_field).unwrap_or_default(),
+        labels,
+        comments,
+        url: url_fields.iter().find_map(|field| string_field(&value, field)),
+    })
+}
+
+fn string_field(value: &Value, field: &str) -> Option<String> {
+    value.get(field).and_then(Value::as_str).map(str::to_string)
+}
+
+/// 라벨은 문자열(GitLab) 또는 `{"name": ...}` 객체(GitHub)다.
+fn label_name(label: &Value) -> Option<String> {
+    label
+        .as_str()
+        .map(str::to_string)
+        .or_else(|| string_field(label, "name"))
+}
+
+fn parse_comment(comment: &Value) -> Option<IssueComment> {
+    let body = string_field(comment, "body")?;
+    let author = ["author", "user"]
+        .iter()
+        .filter_map(|field| comment.get(field))
+        .find_map(|author| string_field(author, "login").or_else(|| string_field(author, "username")))
+        .unwrap_or_else(|| "unknown".to_string());
+    Some(IssueComment { author, body })
+}
+
+fn format_issue(issue: &IssueExport) -> String {
+    let mut text = format!("#### {}\n", issue.title);
+    if let Some(url) = &issue.url {
+        text.push_str(&format!("\n- URL: {}", url));
+    }
+    if !issue.labels.is_empty() {
+        text.push_str(&format!("\n- Labels: {}", issue.labels.join(", ")));
+    }
+
+    let body = issue.body.trim();
+    text.push_str("\n\n");
+    text.push_str(if body.is_empty() { "(본문 없음)" } else { body });
+
+    for comment in &issue.comments {
+        text.push_str(&format!("\n\n**Comment by {}:**\n\n{}", comment.author, comment.body.trim()));
+    }
+    text
+}
+
+/// 저널에 복사할 원본 파일 경로. 이름이 같은 파일이 여러 개여도 겹치지 않도록 순번을 붙인다.
+pub fn source_copy_path(journal_dir: &Path, index: usize, source: &RequirementSource) -> PathBuf {
+    let file_name = source
+        .original_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("source");
+    journal_dir
+        .join(SOURCES_DIR_NAME)
+        .join(format!("{:02}-{}", index + 1, file_name))
+}
+
+/// 원본 파일을 저널의 `sources/` 디렉토리로 복사하고 복사된 경로를 반환한다.
+pub fn copy_sources_to_journal(journal_dir: &Path, sources: &[RequirementSource]) -> io::Result<Vec<PathBuf>> {
+    let mut copied_paths = Vec::new();
+    for (index, source) in sources.iter().enumerate() {
+        let destination = source_copy_path(journal_dir, index, source);
+        fs::create_dir_all(destination.parent().unwrap_or(journal_dir))?;
+        fs::copy(&source.original_path, &destination)?;
+        copied_paths.push(destination);
+    }
+    Ok(copied_paths)
+}
+
+/// 직접 입력한 텍스트와 가져온 파일을 하나의 user-request.md 문서로 합친다.
+/// 각 파일에는 원본 경로와 저널 사본 경로를 출처로 남긴다.
+pub fn build_user_request_document(typed_text: &str, sources: &[RequirementSource]) -> String {
+    if sources.is_empty() {
+        return typed_text.to_string();
+    }
+
+    let mut document = String::from("# User Request\n");
+    if !typed_text.is_empty() {
+        document.push_str(&format!("\n{}\n", typed_text));
+    }
+
+    document.push_str("\n## Imported Sources\n");
+    for (index, source) in sources.iter().enumerate() {
+        let copy_path = source_copy_path(Path::new(""), index, source);
+        document.push_str(&format!(
+            "\n### Source {}: {}\n\n- Original path: {}\n- Type: {}\n- Journal copy: {}\n\n{}\n",
+            index + 1,
+            copy_path.file_name().and_then(|name| name.to_str()).unwrap_or_default(),
+            source.original_path.display(),
+            source.kind.label(),
+            copy_path.display(),
+            source.content,
+        ));
+    }
+    document
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn split_import_references_separates_paths_from_text() {
+        let (typed, references) = split_import_references("로그인 기능\n@docs/login.md\n  @ issues/12.json \n@\n추가 설명");
+
+        assert_eq!(typed, "로그인 기능\n@\n추가 설명");
+        assert_eq!(references, vec!["docs/login.md".to_string(), "issues/12.json".to_string()]);
+    }
+
+    #[test]
+    fn parses_github_issue_export() {
+        let json = r#"{
+            "title": "Add login",
+            "body": "Users need to log in.",
+            "labels": [{"name": "feature"}, {"name": "auth"}],
+            "comments": [{"author": {"login": "alice"}, "body": "Use OAuth."}],
+            "url": "https://github.com/org/repo/issues/12"
+        }"#;
+
+        let issue = parse_issue_export(json).unwrap();
+
+        assert_eq!(issue.kind, SourceKind::GitHubIssue);
+        assert_eq!(issue.labels, vec!["feature".to_string(), "auth".to_string()]);
+        assert_eq!(issue.comments, vec![IssueComment { author: "alice".to_string(), body: "Use OAuth.".to_string() }]);
+        assert_eq!(issue.url.as_deref(), Some("https://github.com/org/repo/issues/12"));
+    }
+
+    #[test]
+    fn parses_gitlab_issue_export() {
+        let json = r#"{
+            "title": "Add export",
+            "description": "Export to CSV.",
+            "labels": ["feature"],
+            "notes": [{"author": {"username": "bob"}, "body": "Include headers."}],
+            "web_url": "https://gitlab.com/org/repo/-/issues/3"
+        }"#;
+
+        let issue = parse_issue_export(json).unwrap();
+        let formatted = format_issue(&issue);
+
+        assert_eq!(issue.kind, SourceKind::GitLabIssue);
+        assert!(formatted.contains("Export to CSV."));
+        assert!(formatted.contains("- Labels: feature"));
+        assert!(formatted.contains("**Comment by bob:**\n\nInclude headers."));
+    }
+
+    #[test]
+    fn issue_export_without_title_is_rejected() {
+        assert!(parse_issue_export(r#"{"body": "no title"}"#).is_err());
+        assert!(parse_issue_export("not json").is_err());
+    }
+
+    #[test]
+    fn load_sources_reads_directory_in_name_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let requirements_dir = temp_dir.path().join("reqs");
+        fs::create_dir_all(requirements_dir.join("nested")).unwrap();
+        fs::write(requirements_dir.join("b.md"), "B").unwrap();
+        fs::write(requirements_dir.join("a.txt"), "A").unwrap();
+        fs::write(requirements_dir.join("nested").join("c.md"), "C").unwrap();
+        fs::write(requirements_dir.join("image.png"), "binary").unwrap();
+        fs::write(requirements_dir.join(".hidden.md"), "hidden").unwrap();
+
+        let sources = load_sources(&["reqs".to_string()], temp_dir.path()).unwrap();
+
+        let contents: Vec<&str> = sources.iter().map(|s| s.content.as_str()).collect();
+        assert_eq!(contents, vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn load_sources_rejects_missing_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = load_sources(&["missing.md".to_string()], temp_dir.path());
+        assert!(result.unwrap_err().contains("missing.md"));
+    }
+
+    #[test]
+    fn user_request_document_records_provenance() {
+        let source = RequirementSource {
+            original_path: PathBuf::from("/docs/login.md"),
+            kind: SourceKind::Document,
+            content: "로그인 요구사항".to_string(),
+        };
+
+        let document = build_user_request_document("이 문서대로 구현하세요.", std::slice::from_ref(&source));
+
+        assert!(document.starts_with("# User Request\n\n이 문서대로 구현하세요.\n"));
+        assert!(document.contains("- Original path: /docs/login.md"));
+        assert!(document.contains("- Journal copy: sources/01-login.md"));
+        assert!(document.contains("로그인 요구사항"));
+        assert_eq!(build_user_request_document("텍스트만", &[]), "텍스트만");
+    }
+
+    #[test]
+    fn copy_sources_to_journal_copies_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let original = temp_dir.path().join("spec.md");
+        fs::write(&original, "원본").unwrap();
+        let source = RequirementSource {
+            original_path: original,
+            kind: SourceKind::Document,
+            content: "원본".to_string(),
+        };
+        let journal_dir = temp_dir.path().join("journal");
+
+        let copied = copy_sources_to_journal(&journal_dir, &[source]).unwrap();
+
+        assert_eq!(copied, vec![journal_dir.join("sources").join("01-spec.md")]);
+        assert_eq!(fs::read_to_string(&copied[0]).unwrap(), "원본");
+    }
+}
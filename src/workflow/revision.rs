@@ -0,0 +1,379 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const DIFF_CONTEXT_LINES: usize = 3;
+/// LCS 표의 최대 칸 수. 공통 앞뒤 줄을 뺀 나머지가 이보다 크면 전부 지우고 새로 넣은 것으로 보여준다.
+const MAX_LCS_CELLS: usize = 1_000_000;
+
+/// 스펙 또는 플랜 드래프트의 모든 리비전. 리비전 번호는 1부터 시작한다.
+pub struct RevisionHistory {
+    file_stem: &'static str,
+    revisions: Vec<String>,
+}
+
+impl RevisionHistory {
+    pub fn new(file_stem: &'static str) -> Self {
+        Self {
+            file_stem,
+            revisions: Vec::new(),
+        }
+    }
+
+    pub fn latest(&self) -> Option<&String> {
+        self.revisions.last()
+    }
+
+    pub fn latest_version(&self) -> usize {
+        self.revisions.len()
+    }
+
+    pub fn get(&self, version: usize) -> Option<&String> {
+        version.checked_sub(1).and_then(|index| self.revisions.get(index))
+    }
+
+    /// 새 리비전을 추가하고 그 번호를 반환한다.
+    pub fn push(&mut self, draft: String) -> usize {
+        self.revisions.push(draft);
+        self.revisions.len()
+    }
+
+    pub fn revision_file_path(&self, dir: &Path, version: usize) -> PathBuf {
+        dir.join(format!("{}.v{}.md", self.file_stem, version))
+    }
+
+    pub fn feedback_file_path(&self, dir: &Path, version: usize) -> PathBuf {
+        dir.join(format!("{}.v{}.feedback.md", self.file_stem, version))
+    }
+
+    /// 리비전 본문을 `<stem>.vN.md`에, 이 리비전을 만든 사용자 입력을 `<stem>.vN.feedback.md`에 저장한다.
+    pub fn save(&self, dir: &Path, version: usize, feedback: Option<&str>) -> io::Result<PathBuf> {
+        let draft = self
+            .get(version)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "unknown revision"))?;
+        fs::create_dir_all(dir)?;
+
+        let file_path = self.revision_file_path(dir, version);
+        fs::write(&file_path, draft)?;
+
+        if let Some(feedback) = feedback {
+            fs::write(self.feedback_file_path(dir, version), feedback)?;
+        }
+
+        Ok(file_path)
+    }
+
+    /// 저널 디렉토리에 저장된 모든 리비전 파일과 피드백 파일 경로.
+    pub fn saved_file_paths(&self, dir: &Path) -> Vec<PathBuf> {
+        (1..=self.latest_version())
+            .flat_map(|version| {
+                [
+                    self.revision_file_path(dir, version),
+                    self.feedback_file_path(dir, version),
+                ]
+            })
+            .filter(|path| path.is_file())
+            .collect()
+    }
+
+    /// 최신 리비전을 보여줄 메시지. 이전 리비전이 있으면 전체 문서 대신 unified diff를 보여준다.
+    pub fn describe_latest(&self, document_label: &str) -> String {
+        let version = self.latest_version();
+        let latest = self.latest().map(String::as_str).unwrap_or_default();
+
+        let first_draft = || format!("{} v{}:\n\n{}", document_label, version, latest);
+        let Some(previous_version) = version.checked_sub(1) else {
+            return first_draft();
+        };
+        let Some(previous) = self.get(previous_version) else {
+            return first_draft();
+        };
+
+        let diff = unified_diff(
+            previous,
+            latest,
+            &format!("{}.v{}.md", self.file_stem, previous_version),
+            &format!("{}.v{}.md", self.file_stem, version),
+        );
+        if diff.is_empty() {
+            return format!("{} v{}: v{}와 변경 사항이 없습니다.", document_label, version, previous_version);
+        }
+        format!("{} v{} (v{} 대비 변경):\n\n{}", document_label, version, previous_version, diff)
+    }
+}
+
+/// `/rollback N` 입력이면 리비전 번호를 반환한다. 롤백 명령이 아니면 None을 반환한다.
+pub fn parse_rollback_command(input: &str) -> Option<Result<usize, String>> {
+    let arguments = input.trim().strip_prefix("/rollback")?;
+    if !arguments.is_empty() && !arguments.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let version = arguments
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|version| *version >= 1)
+        .ok_or_else(|| "롤백할 리비전 번호를 입력하세요. 예: /rollback 2".to_string());
+    Some(version)
+}
+
+/// 롤백 직후의 피드백 앞에 붙여, 에이전트가 자신의 최신 드래프트 대신 롤백된 파일을 기준으로 수정하게 한다.
+pub fn build_rollback_note(version: usize, revision_path: &Path) -> String {
+    format!(
+        "NOTE: The user rolled back the draft to revision {}. Discard all later revisions. \
+         The current draft is the file at {}; you MUST read it and apply the feedback below to that draft.",
+        version,
+        revision_path.display(),
+    )
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DiffOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+struct DiffLine<'a> {
+    op: DiffOp,
+    text: &'a str,
+}
+
+/// 두 문서를 줄 단위 LCS로 비교해 unified diff 형식 문자열을 만든다. 같으면 빈 문자열을 반환한다.
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let diff_lines = diff_by_lcs(&old_lines, &new_lines);
+
+    if diff_lines.iter().all(|line| line.op == DiffOp::Equal) {
+        return String::new();
+    }
+
+    let mut output = format!("--- {}\n+++ {}", old_label, new_label);
+    for (start, end) in hunk_ranges(&diff_lines) {
+        output.push('\n');
+        output.push_str(&format_hunk(&diff_lines, start, end));
+    }
+    output
+}
+
+/// 공통 앞뒤 줄은 그대로 두고 가운데만 LCS로 비교한다. 리비전은 대개 일부만 바뀌므로 표가 작아진다.
+fn diff_by_lcs<'a>(old_lines: &[&'a str], new_lines: &[&'a str]) -> Vec<DiffLine<'a>> {
+    let prefix = old_lines
+        .iter()
+        .zip(new_lines)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let old_middle = &old_lines[prefix..old_lines.len() - suffix];
+    let new_middle = &new_lines[prefix..new_lines.len() - suffix];
+
+    let mut diff_lines: Vec<DiffLine> = old_lines[..prefix]
+        .iter()
+        .map(|text| DiffLine { op: DiffOp::Equal, text })
+        .collect();
+    let lcs_cells = (old_middle.len() + 1).saturating_mul(new_middle.len() + 1);
+    if lcs_cells > MAX_LCS_CELLS {
+        diff_lines.extend(old_middle.iter().map(|text| DiffLine { op: DiffOp::Delete, text }));
+        diff_lines.extend(new_middle.iter().map(|text| DiffLine { op: DiffOp::Insert, text }));
+    } else {
+        diff_lines.extend(diff_middle_by_lcs(old_middle, new_middle));
+    }
+    diff_lines.extend(
+        old_lines[old_lines.len() - suffix..]
+            .iter()
+            .map(|text| DiffLine { op: DiffOp::Equal, text }),
+    );
+    diff_lines
+}
+
+fn diff_middle_by_lcs<'a>(old_lines: &[&'a str], new_lines: &[&'a str]) -> Vec<DiffLine<'a>> {
+    // lcs_lengths[i][j]는 old_lines[i..]와 new_lines[j..]의 LCS 길이다.
+    let mut lcs_lengths = vec![vec![0usize; new_lines.len() + 1]; old_lines.len() + 1];
+    for i in (0..old_lines.len()).rev() {
+        for j in (0..new_lines.len()).rev() {
+            lcs_lengths[i][j] = if old_lines[i] == new_lines[j] {
+                lcs_lengths[i + 1][j + 1] + 1
+            } else {
+                lcs_lengths[i + 1][j].max(lcs_lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff_lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old_lines.len() && j < new_lines.len() {
+        if old_lines[i] == new_lines[j] {
+            diff_lines.push(DiffLine { op: DiffOp::Equal, text: old_lines[i] });
+            i += 1;
+            j += 1;
+        } else if lcs_lengths[i + 1][j] >= lcs_lengths[i][j + 1] {
+            diff_lines.push(DiffLine { op: DiffOp::Delete, text: old_lines[i] });
+            i += 1;
+        } else {
+            diff_lines.push(DiffLine { op: DiffOp::Insert, text: new_lines[j] });
+            j += 1;
+        }
+    }
+    diff_lines.extend(old_lines[i..].iter().map(|text| DiffLine { op: DiffOp::Delete, text }));
+    diff_lines.extend(new_lines[j..].iter().map(|text| DiffLine { op: DiffOp::Insert, text }));
+    diff_lines
+}
+
+/// 변경된 줄 주변에 문맥 줄을 붙인 구간을 만들고, 겹치거나 맞닿는 구간은 하나로 합친다.
+fn hunk_ranges(diff_lines: &[DiffLine]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (index, line) in diff_lines.iter().enumerate() {
+        if line.op == DiffOp::Equal {
+            continue;
+        }
+        let start = index.saturating_sub(DIFF_CONTEXT_LINES);
+        let end = (index + DIFF_CONTEXT_LINES + 1).min(diff_lines.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+fn format_hunk(diff_lines: &[DiffLine], start: usize, end: usize) -> String {
+    let count_before = |ops: &[DiffOp]| {
+        diff_lines[..start]
+            .iter()
+            .filter(|line| ops.contains(&line.op))
+            .count()
+    };
+    let count_within = |ops: &[DiffOp]| {
+        diff_lines[start..end]
+            .iter()
+            .filter(|line| ops.contains(&line.op))
+            .count()
+    };
+
+    let old_count = count_within(&[DiffOp::Equal, DiffOp::Delete]);
+    let new_count = count_within(&[DiffOp::Equal, DiffOp::Insert]);
+    // unified diff의 줄 번호는 1부터 시작하고, 비어 있는 쪽은 구간 바로 앞 줄을 가리킨다.
+    let old_start = count_before(&[DiffOp::Equal, DiffOp::Delete]) + usize::from(old_count > 0);
+    let new_start = count_before(&[DiffOp::Equal, DiffOp::Insert]) + usize::from(new_count > 0);
+
+    let mut hunk = format!("@@ -{},{} +{},{} @@", old_start, old_count, new_start, new_count);
+    for line in &diff_lines[start..end] {
+        let prefix = match line.op {
+            DiffOp::Equal => ' ',
+            DiffOp::Delete => '-',
+            DiffOp::Insert => '+',
+        };
+        hunk.push('\n');
+        hunk.push(prefix);
+        hunk.push_str(line.text);
+    }
+    hunk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn unified_diff_of_identical_documents_is_empty() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "old", "new"), "");
+    }
+
+    #[test]
+    fn unified_diff_shows_changed_lines_with_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "1\n2\n3\n4\n5\nsix\n7\n8\n9\n10\n";
+
+        let diff = unified_diff(old, new, "spec.v1.md", "spec.v2.md");
+
+        assert_eq!(
+            diff,
+            "--- spec.v1.md\n+++ spec.v2.md\n@@ -3,7 +3,7 @@\n 3\n 4\n 5\n-6\n+six\n 7\n 8\n 9"
+        );
+    }
+
+    #[test]
+    fn unified_diff_splits_distant_changes_into_hunks() {
+        let old: String = (1..=20).map(|n| format!("{}\n", n)).collect();
+        let new = old.replace("2\n", "two\n").replace("19\n", "nineteen\n");
+
+        let diff = unified_diff(&old, &new, "a", "b");
+
+        assert_eq!(diff.matches("@@ -").count(), 2);
+        assert!(diff.contains("-2\n+two"));
+        assert!(diff.contains("-19\n+nineteen"));
+    }
+
+    #[test]
+    fn unified_diff_handles_insertion_into_empty_document() {
+        let diff = unified_diff("", "new line\n", "a", "b");
+        assert_eq!(diff, "--- a\n+++ b\n@@ -0,0 +1,1 @@\n+new line");
+    }
+
+    #[test]
+    fn unified_diff_replaces_large_rewrites_without_full_lcs() {
+        let rewrite = |prefix: &str| {
+            let body: Vec<String> = (0..1200).map(|index| format!("{}{}", prefix, index)).collect();
+            format!("# 제목\n{}\n끝\n", body.join("\n"))
+        };
+
+        let diff = unified_diff(&rewrite("old"), &rewrite("new"), "a", "b");
+
+        let lines: Vec<&str> = diff.lines().collect();
+        assert_eq!(lines[2], "@@ -1,1202 +1,1202 @@");
+        assert_eq!(lines[3], " # 제목");
+        assert_eq!(lines[4], "-old0");
+        assert_eq!(lines[1204], "+new0");
+        assert_eq!(lines.last(), Some(&" 끝"));
+    }
+
+    #[test]
+    fn history_saves_revision_and_feedback_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut history = RevisionHistory::new("spec");
+        history.push("v1 본문".to_string());
+        let version = history.push("v2 본문".to_string());
+
+        let path = history.save(temp_dir.path(), version, Some("더 자세히")).unwrap();
+
+        assert_eq!(path, temp_dir.path().join("spec.v2.md"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "v2 본문");
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("spec.v2.feedback.md")).unwrap(),
+            "더 자세히"
+        );
+        assert_eq!(
+            history.saved_file_paths(temp_dir.path()),
+            vec![path, temp_dir.path().join("spec.v2.feedback.md")]
+        );
+    }
+
+    #[test]
+    fn describe_latest_shows_full_first_draft_then_diff() {
+        let mut history = RevisionHistory::new("plan");
+        history.push("첫 줄\n".to_string());
+        assert_eq!(history.describe_latest("개발 계획 드래프트"), "개발 계획 드래프트 v1:\n\n첫 줄\n");
+
+        history.push("첫 줄\n둘째 줄\n".to_string());
+        let description = history.describe_latest("개발 계획 드래프트");
+        assert!(description.starts_with("개발 계획 드래프트 v2 (v1 대비 변경):"));
+        assert!(description.contains("+둘째 줄"));
+    }
+
+    #[test]
+    fn parse_rollback_command_reads_version() {
+        assert_eq!(parse_rollback_command("/rollback 2"), Some(Ok(2)));
+        assert!(matches!(parse_rollback_command("/rollback"), Some(Err(_))));
+        assert!(matches!(parse_rollback_command("/rollback 0"), Some(Err(_))));
+        assert_eq!(parse_rollback_command("/rollbacks"), None);
+        assert_eq!(parse_rollback_command("스펙을 고쳐주세요"), None);
+    }
+}
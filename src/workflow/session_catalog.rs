@@ -0,0 +1,353 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::acceptance;
+use super::cleanup;
+use super::coding::{self, CodingTaskStatus};
+use super::journal_storage;
+use super::task_review;
+
+const USER_REQUEST_HEADING: &str = "# User Request";
+
+/// 저널 파일로 판단한, 세션이 도달한 마지막 단계.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionProgress {
+    Requirements,
+    SpecApproved,
+    PlanApproved,
+    TasksReviewed,
+    Coding { completed: usize, total: usize },
+    CodingFinished,
+}
+
+impl SessionProgress {
+    pub fn label(&self) -> String {
+        match self {
+            SessionProgress::Requirements => "요구사항".to_string(),
+            SessionProgress::SpecApproved => "스펙 승인".to_string(),
+            SessionProgress::PlanApproved => "계획 승인".to_string(),
+            SessionProgress::TasksReviewed => "작업 목록 확정".to_string(),
+            SessionProgress::Coding { completed, total } => {
+                format!("코딩 {}/{}", completed, total)
+            }
+            SessionProgress::CodingFinished => "코딩 완료".to_string(),
+        }
+    }
+}
+
+/// `.bear/<date>/<session-id>/` 저널 하나의 요약.
+#[derive(Debug, Clone)]
+pub struct SessionSummary {
+    pub dir: PathBuf,
+    pub date: String,
+    pub session_id: String,
+    pub request_title: String,
+    pub progress: SessionProgress,
+    pub succeeded_tasks: usize,
+    pub blocked_tasks: usize,
+    pub integration_branch: Option<String>,
+}
+
+impl SessionSummary {
+    /// 스펙이 있어야 이전 세션 이어서 진행할 수 있다.
+    pub fn is_resumable(&self) -> bool {
+        self.dir.join("spec.md").is_file()
+    }
+
+    fn search_text(&self) -> String {
+        format!(
+            "{} {} {} {} {}",
+            self.date,
+            self.session_id,
+            self.request_title,
+            self.progress.label(),
+            self.integration_branch.as_deref().unwrap_or_default(),
+        )
+        .to_lowercase()
+    }
+}
+
+/// 워크스페이스, 통합 워크트리와 코드 밖 저널 루트의 모든 세션 저널을 최신 날짜부터 나열한다.
+/// 같은 날짜는 최근 수정된 세션이 먼저 오고, 같은 세션은 통합 워크트리의 저널을 쓴다.
+pub fn scan_sessions(workspace: &Path) -> Vec<SessionSummary> {
+    let integration_branches = cleanup::list_bear_branches(workspace).unwrap_or_default();
+
+    let mut session_dirs: Vec<PathBuf> = Vec::new();
+    let journal_roots = cleanup::list_integration_worktrees(workspace)
+        .into_iter()
+        .chain([workspace.to_path_buf(), journal_storage::external_journal_root(workspace)]);
+    for dir in journal_roots.flat_map(|root| list_session_dirs(&root)) {
+        if !session_dirs.iter().any(|known| known.file_name() == dir.file_name()) {
+            session_dirs.push(dir);
+        }
+    }
+
+    let mut sessions: Vec<(SessionSummary, Option<std::time::SystemTime>)> = session_dirs
+        .into_iter()
+        .filter_map(|dir| {
+            let modified = fs::metadata(&dir).and_then(|m| m.modified()).ok();
+            summarize_session(&dir, &integration_branches).map(|summary| (summary, modified))
+        })
+        .collect();
+
+    sessions.sort_by(|(a, a_modified), (b, b_modified)| {
+        b.date.cmp(&a.date).then(b_modified.cmp(a_modified))
+    });
+    sessions.into_iter().map(|(summary, _)| summary).collect()
+}
+
+fn list_session_dirs(workspace: &Path) -> Vec<PathBuf> {
+    let Ok(date_dirs) = fs::read_dir(workspace.join(".bear")) else {
+        return Vec::new();
+    };
+
+    date_dirs
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|date_dir| fs::read_dir(date_dir).ok())
+        .flat_map(|session_dirs| session_dirs.filter_map(|entry| entry.ok()))
+        .map(|entry| entry.path())
+        .filter(|path| path.join("user-request.md").is_file() || path.join("spec.md").is_file())
+        .collect()
+}
+
+fn summarize_session(dir: &Path, integration_branches: &[String]) -> Option<SessionSummary> {
+    let session_id = dir.file_name()?.to_str()?.to_string();
+    let date = dir.parent()?.file_name()?.to_str()?.to_string();
+    let request_title = fs::read_to_string(dir.join("user-request.md"))
+        .map(|content| request_title(&content))
+        .unwrap_or_default();
+
+    let mut summary = SessionSummary {
+        dir: dir.to_path_buf(),
+        date,
+        session_id,
+        request_title,
+        progress: progress_from_files(dir),
+        succeeded_tasks: 0,
+        blocked_tasks: 0,
+        integration_branch: None,
+    };
+
+    match coding::load_coding_state(dir) {
+        Ok(snapshot) => {
+            let total = snapshot.tasks.len();
+            summary.progress = if snapshot.next_task_index >= total {
+                SessionProgress::CodingFinished
+            } else {
+                SessionProgress::Coding {
+                    completed: snapshot.next_task_index,
+                    total,
+                }
+            };
+            summary.blocked_tasks = acceptance::blocked_task_ids(&snapshot.task_reports).len();
+            summary.succeeded_tasks = count_succeeded_tasks(&snapshot.task_reports);
+            summary.integration_branch = Some(snapshot.integration_branch);
+        }
+        Err(_) => {
+            let prefix = format!("bear/integration/{}-", summary.session_id);
+            summary.integration_branch = integration_branches
+                .iter()
+                .find(|branch| branch.starts_with(&prefix))
+                .cloned();
+        }
+    }
+
+    Some(summary)
+}
+
+fn progress_from_files(dir: &Path) -> SessionProgress {
+    if dir.join(task_review::TASKS_FILE_NAME).is_file() {
+        SessionProgress::TasksReviewed
+    } else if dir.join("plan.md").is_file() {
+        SessionProgress::PlanApproved
+    } else if dir.join("spec.md").is_file() {
+        SessionProgress::SpecApproved
+    } else {
+        SessionProgress::Requirements
+    }
+}
+
+/// 가장 최근 보고서가 SUCCESS인 태스크 수. 재실행으로 성공한 태스크도 한 번만 센다.
+fn count_succeeded_tasks(task_reports: &[coding::TaskReport]) -> usize {
+    let mut task_ids: Vec<&str> = Vec::new();
+    for report in task_reports {
+        if !task_ids.contains(&report.task_id.as_str()) {
+            task_ids.push(&report.task_id);
+        }
+    }
+
+    task_ids
+        .iter()
+        .filter(|task_id| {
+            task_reports
+                .iter()
+                .rev()
+                .find(|r| r.task_id == **task_id)
+                .is_some_and(|r| r.status == CodingTaskStatus::ImplementationSuccess)
+        })
+        .count()
+}
+
+/// 요청 문서의 첫 번째 의미 있는 줄. 가져오기로 만든 문서의 고정 제목은 건너뛴다.
+fn request_title(content: &str) -> String {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && *line != USER_REQUEST_HEADING)
+        .map(|line| line.trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// 공백으로 나눈 모든 검색어를 포함하는 세션만 남긴다. 대소문자는 구분하지 않는다.
+pub fn filter_sessions<'a>(sessions: &'a [SessionSummary], query: &str) -> Vec<&'a SessionSummary> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    sessions
+        .iter()
+        .filter(|session| {
+            let text = session.search_text();
+            terms.iter().all(|term| text.contains(term.as_str()))
+        })
+        .collect()
+}
+
+/// 세션 브라우저 목록에 표시할 한 줄.
+pub fn format_session_line(session: &SessionSummary) -> String {
+    let mut line = format!(
+        "{}  {}  [{}]",
+        session.date,
+        if session.request_title.is_empty() { "(요청 없음)" } else { &session.request_title },
+        session.progress.label(),
+    );
+    if session.succeeded_tasks + session.blocked_tasks > 0 {
+        line.push_str(&format!(
+            "  성공 {} / 차단 {}",
+            session.succeeded_tasks, session.blocked_tasks,
+        ));
+    }
+    if let Some(branch) = &session.integration_branch {
+        line.push_str(&format!("  {}", branch));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::coding::{
+        CodingStateSnapshot, CodingTask, CodingTaskKind, TaskReport, TaskTrace,
+    };
+    use tempfile::TempDir;
+
+    fn make_task(task_id: &str) -> CodingTask {
+        CodingTask {
+            task_id: task_id.to_string(),
+            title: "작업".to_string(),
+            description: "설명".to_string(),
+            dependencies: vec![],
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: Vec::new(),
+        }
+    }
+
+    fn make_report(task_id: &str, status: CodingTaskStatus) -> TaskReport {
+        TaskReport {
+            task_id: task_id.to_string(),
+            status,
+            report: String::new(),
+            report_file_path: PathBuf::new(),
+            trace: TaskTrace::default(),
+        }
+    }
+
+    fn create_session(workspace: &Path, date: &str, id: &str, request: &str) -> PathBuf {
+        let dir = workspace.join(".bear").join(date).join(id);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("user-request.md"), request).unwrap();
+        dir
+    }
+
+    #[test]
+    fn scan_sessions_summarizes_journals_newest_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path();
+        let old_dir = create_session(workspace, "20260101", "old-session", "# User Request\n\n로그인 기능\n");
+        fs::write(old_dir.join("spec.md"), "spec").unwrap();
+        let new_dir = create_session(workspace, "20260301", "new-session", "내보내기 기능");
+        coding::save_coding_state(
+            &new_dir,
+            &CodingStateSnapshot {
+                integration_branch: "bear/integration/new-session-x".to_string(),
+                integration_base_commit: None,
+                integration_base_branch: None,
+                tasks: vec![make_task("TASK-00"), make_task("TASK-01"), make_task("TASK-02")],
+                next_task_index: 2,
+                task_reports: vec![
+                    make_report("TASK-00", CodingTaskStatus::ImplementationSuccess),
+                    make_report("TASK-01", CodingTaskStatus::ImplementationBlocked),
+                ],
+                usage: Default::default(),
+            },
+        )
+        .unwrap();
+
+        let sessions = scan_sessions(workspace);
+
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].session_id, "new-session");
+        assert_eq!(sessions[0].progress, SessionProgress::Coding { completed: 2, total: 3 });
+        assert_eq!((sessions[0].succeeded_tasks, sessions[0].blocked_tasks), (1, 1));
+        assert_eq!(sessions[0].integration_branch.as_deref(), Some("bear/integration/new-session-x"));
+        assert_eq!(sessions[1].request_title, "로그인 기능");
+        assert_eq!(sessions[1].progress, SessionProgress::SpecApproved);
+        assert!(sessions[1].is_resumable());
+    }
+
+    #[test]
+    fn scan_sessions_includes_journals_kept_outside_the_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("repo");
+        fs::create_dir_all(&workspace).unwrap();
+        let external_root = journal_storage::external_journal_root(&workspace);
+        let dir = create_session(&external_root, "20260101", "external-session", "외부 저널");
+
+        let sessions = scan_sessions(&workspace);
+
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].dir, dir);
+    }
+
+    #[test]
+    fn scan_sessions_returns_empty_without_bear_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(scan_sessions(temp_dir.path()).is_empty());
+    }
+
+    #[test]
+    fn filter_sessions_matches_all_terms_case_insensitively() {
+        let temp_dir = TempDir::new().unwrap();
+        create_session(temp_dir.path(), "20260101", "a", "Login page");
+        create_session(temp_dir.path(), "20260102", "b", "Export CSV");
+        let sessions = scan_sessions(temp_dir.path());
+
+        let matched = filter_sessions(&sessions, "login 20260101");
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].session_id, "a");
+        assert_eq!(filter_sessions(&sessions, "").len(), 2);
+        assert!(filter_sessions(&sessions, "missing").is_empty());
+    }
+
+    #[test]
+    fn count_succeeded_tasks_uses_latest_report() {
+        let reports = vec![
+            make_report("TASK-00", CodingTaskStatus::ImplementationBlocked),
+            make_report("TASK-00", CodingTaskStatus::ImplementationSuccess),
+            make_report("TASK-01", CodingTaskStatus::ImplementationSuccess),
+        ];
+        assert_eq!(count_succeeded_tasks(&reports), 2);
+    }
+}
@@ -1,10 +1,15 @@
-use chrono::{FixedOffset, Utc};
+use chrono::{DateTime, FixedOffset, Utc};
 use uuid::Uuid;
 
 /// Asia/Seoul (KST, UTC+9) 타임존 기준 오늘 날짜를 `YYYYMMDD` 형식으로 반환한다.
 pub fn today_date_string() -> String {
+    now_kst().format("%Y%m%d").to_string()
+}
+
+/// Asia/Seoul (KST, UTC+9) 타임존 기준 현재 시각.
+pub fn now_kst() -> DateTime<FixedOffset> {
     let kst = FixedOffset::east_opt(9 * 3600).expect("valid KST offset");
-    Utc::now().with_timezone(&kst).format("%Y%m%d").to_string()
+    Utc::now().with_timezone(&kst)
 }
 
 /// UUID v4 문자열을 생성하여 세션 식별자로 반환한다.
@@ -0,0 +1,490 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use chrono::{DateTime, FixedOffset, TimeDelta};
+
+use crate::claude_code_client::usage::UsageTotals;
+
+use super::clarification::{ClarificationAnswer, QaRound};
+use super::coding::{CodingTask, CodingTaskStatus, TaskReport};
+
+pub const REPORT_MARKDOWN_FILE_NAME: &str = "session-report.md";
+pub const REPORT_HTML_FILE_NAME: &str = "session-report.html";
+const MAX_BUILD_TEST_OUTPUT_LINES: usize = 30;
+
+/// 이번 실행 중 엔진이 기록한 활동. 보고서의 소요 시간, 리뷰, 빌드/테스트 항목의 근거가 된다.
+#[derive(Default)]
+pub struct SessionActivity {
+    pub started_at: Option<DateTime<FixedOffset>>,
+    pub task_runs: Vec<TaskRun>,
+    pub reviews: Vec<ReviewRecord>,
+    pub build_tests: Vec<BuildTestRecord>,
+}
+
+pub struct TaskRun {
+    pub task_id: String,
+    pub started_at: DateTime<FixedOffset>,
+    pub finished_at: Option<DateTime<FixedOffset>>,
+}
+
+pub struct ReviewRecord {
+    pub task_id: String,
+    pub iteration: usize,
+    pub approved: bool,
+    pub comment: String,
+}
+
+pub struct BuildTestRecord {
+    pub task_id: String,
+    pub passed: bool,
+    pub failed_stage: Option<String>,
+    pub output: String,
+}
+
+impl SessionActivity {
+    pub fn start_task(&mut self, task_id: &str, now: DateTime<FixedOffset>) {
+        self.task_runs.push(TaskRun {
+            task_id: task_id.to_string(),
+            started_at: now,
+            finished_at: None,
+        });
+    }
+
+    pub fn finish_task(&mut self, task_id: &str, now: DateTime<FixedOffset>) {
+        let unfinished_run = self
+            .task_runs
+            .iter_mut()
+            .rev()
+            .find(|run| run.task_id == task_id && run.finished_at.is_none());
+        if let Some(run) = unfinished_run {
+            run.finished_at = Some(now);
+        }
+    }
+
+    /// 같은 태스크를 여러 번 실행했으면 모든 실행 시간을 더한다.
+    fn task_duration(&self, task_id: &str) -> Option<TimeDelta> {
+        self.task_runs
+            .iter()
+            .filter(|run| run.task_id == task_id)
+            .filter_map(|run| run.finished_at.map(|finished| finished - run.started_at))
+            .reduce(|total, duration| total + duration)
+    }
+}
+
+/// 보고서를 만드는 데 필요한 세션 정보.
+pub struct SessionReportInput<'a> {
+    pub session_name: &'a str,
+    pub user_request: &'a str,
+    pub qa_log: &'a [QaRound],
+    pub spec: &'a str,
+    pub plan: &'a str,
+    pub tasks: &'a [CodingTask],
+    pub task_reports: &'a [TaskReport],
+    pub activity: &'a SessionActivity,
+    pub integration_branch: &'a str,
+    pub commit_log: &'a str,
+    pub usage: UsageTotals,
+    pub generated_at: DateTime<FixedOffset>,
+}
+
+struct ReportSection {
+    title: &'static str,
+    body: String,
+}
+
+/// 보고서를 Markdown과 단독 실행 가능한 HTML로 저널에 저장하고 두 파일 경로를 반환한다.
+pub fn save_session_report(dir: &Path, input: &SessionReportInput) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let title = format!("Bear 세션 보고서: {}", input.session_name);
+    let sections = build_sections(input);
+
+    let markdown_path = dir.join(REPORT_MARKDOWN_FILE_NAME);
+    fs::write(&markdown_path, render_markdown(&title, &sections))?;
+
+    let html_path = dir.join(REPORT_HTML_FILE_NAME);
+    fs::write(&html_path, render_html(&title, &sections))?;
+
+    Ok(vec![markdown_path, html_path])
+}
+
+/// 저널 디렉토리 전체(보고서, 스펙, 플랜, 작업 보고서, 첨부 파일)를
+/// 저널 옆의 `<session-id>.tar.gz`로 묶는다.
+pub fn create_session_bundle(journal_dir: &Path) -> Result<PathBuf, String> {
+    let (Some(parent), Some(session_dir_name)) = (
+        journal_dir.parent(),
+        journal_dir.file_name().and_then(|name| name.to_str()),
+    ) else {
+        return Err(format!("invalid journal directory: {}", journal_dir.display()));
+    };
+    let bundle_path = parent.join(format!("{}.tar.gz", session_dir_name));
+
+    let output = Command::new("tar")
+        .arg("-czf")
+        .arg(&bundle_path)
+        .arg("-C")
+        .arg(parent)
+        .arg(session_dir_name)
+        .output()
+        .map_err(|e| format!("failed to execute tar: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("failed to create bundle: {}", stderr.trim()));
+    }
+
+    Ok(bundle_path)
+}
+
+fn build_sections(input: &SessionReportInput) -> Vec<ReportSection> {
+    vec![
+        ReportSection { title: "개요", body: format_overview(input) },
+        ReportSection { title: "요구사항", body: non_empty_or_placeholder(input.user_request) },
+        ReportSection { title: "질의응답", body: format_qa_log(input.qa_log) },
+        ReportSection { title: "최종 스펙", body: non_empty_or_placeholder(input.spec) },
+        ReportSection { title: "개발 계획", body: non_empty_or_placeholder(input.plan) },
+        ReportSection { title: "작업 DAG", body: format_task_graph(input.tasks) },
+        ReportSection { title: "작업 결과", body: format_task_results(input) },
+        ReportSection { title: "코드 리뷰", body: format_reviews(input.activity) },
+        ReportSection { title: "빌드/테스트", body: format_build_tests(input.activity) },
+        ReportSection { title: "통합된 커밋", body: non_empty_or_placeholder(input.commit_log) },
+    ]
+}
+
+fn format_overview(input: &SessionReportInput) -> String {
+    let elapsed = input
+        .activity
+        .started_at
+        .map(|started| format_duration(input.generated_at - started))
+        .unwrap_or_else(|| "(기록 없음)".to_string());
+
+    format!(
+        "- 세션: {}\n\
+         - 통합 브랜치: {}\n\
+         - 작성 시각: {}\n\
+         - 이번 실행 소요 시간: {}\n\
+         - 에이전트 호출: {}회, 에이전트 실행 시간 {}\n\
+         - 비용: ${:.4}\n\n\
+         소요 시간, 리뷰, 빌드/테스트, 비용은 이 보고서를 만든 실행에서 기록된 내용입니다.",
+        input.session_name,
+        input.integration_branch,
+        input.generated_at.format("%Y-%m-%d %H:%M:%S"),
+        elapsed,
+        input.usage.query_count,
+        format_duration(TimeDelta::milliseconds(input.usage.agent_duration_ms as i64)),
+        input.usage.total_cost_usd,
+    )
+}
+
+fn format_qa_log(qa_log: &[QaRound]) -> String {
+    let mut lines = Vec::new();
+    for (round_index, round) in qa_log.iter().enumerate() {
+        lines.push(format!("### 라운드 {}", round_index + 1));
+        for entry in &round.answers {
+            let answer = match &entry.answer {
+                ClarificationAnswer::Text(text) => text.clone(),
+                ClarificationAnswer::Skipped => "(건너뜀)".to_string(),
+                ClarificationAnswer::DeferredToAgent => "(에이전트에게 맡김)".to_string(),
+            };
+            lines.push(format!("- Q. {}\n  - A. {}", entry.question, answer));
+        }
+    }
+    non_empty_or_placeholder(&lines.join("\n"))
+}
+
+fn format_task_graph(tasks: &[CodingTask]) -> String {
+    if tasks.is_empty() {
+        return non_empty_or_placeholder("");
+    }
+
+    let mut graph = String::from("```mermaid\ngraph TD\n");
+    for task in tasks {
+        graph.push_str(&format!("    {}[\"{}\"]\n", task.task_id, task.task_id));
+        for dependency in &task.dependencies {
+            graph.push_str(&format!("    {} --> {}\n", dependency, task.task_id));
+        }
+    }
+    graph.push_str("```\n");
+
+    for task in tasks {
+        let dependencies = if task.dependencies.is_empty() {
+            "없음".to_string()
+        } else {
+            task.dependencies.join(", ")
+        };
+        graph.push_str(&format!("\n- [{}] {} (선행: {})", task.task_id, task.title, dependencies));
+    }
+    graph
+}
+
+fn format_task_results(input: &SessionReportInput) -> String {
+    let mut table = String::from(
+        "| 작업 | 제목 | 상태 | 리뷰 반복 | 빌드/테스트 | 소요 시간 |\n|---|---|---|---|---|---|",
+    );
+
+    for task in input.tasks {
+        let status = match input.task_reports.iter().rev().find(|r| r.task_id == task.task_id) {
+            Some(report) if report.status == CodingTaskStatus::ImplementationSuccess => "SUCCESS",
+            Some(_) => "BLOCKED",
+            None => "미실행",
+        };
+        let review_count = input
+            .activity
+            .reviews
+            .iter()
+            .filter(|review| review.task_id == task.task_id)
+            .count();
+        let build_test = input
+            .activity
+            .build_tests
+            .iter()
+            .rev()
+            .find(|record| record.task_id == task.task_id)
+            .map_or("-", |record| if record.passed { "통과" } else { "실패" });
+        let duration = input
+            .activity
+            .task_duration(&task.task_id)
+            .map_or_else(|| "-".to_string(), format_duration);
+
+        table.push_str(&format!(
+            "\n| {} | {} | {} | {} | {} | {} |",
+            task.task_id,
+            task.title.replace('|', "\\|"),
+            status,
+            review_count,
+            build_test,
+            duration,
+        ));
+    }
+    table
+}
+
+fn format_reviews(activity: &SessionActivity) -> String {
+    let entries: Vec<String> = activity
+        .reviews
+        .iter()
+        .map(|review| {
+            let verdict = if review.approved { "승인" } else { "변경 요청" };
+            format!(
+                "### [{}] 반복 {}: {}\n\n{}",
+                review.task_id,
+                review.iteration,
+                verdict,
+                review.comment.trim(),
+            )
+        })
+        .collect();
+    non_empty_or_placeholder(&entries.join("\n\n"))
+}
+
+fn format_build_tests(activity: &SessionActivity) -> String {
+    let entries: Vec<String> = activity
+        .build_tests
+        .iter()
+        .map(|record| match &record.failed_stage {
+            None => format!("- [{}] 통과", record.task_id),
+            Some(stage) => format!(
+                "- [{}] {} 실패\n\n```\n{}\n```",
+                record.task_id,
+                stage,
+                last_lines(&record.output, MAX_BUILD_TEST_OUTPUT_LINES),
+            ),
+        })
+        .collect();
+    non_empty_or_placeholder(&entries.join("\n"))
+}
+
+fn last_lines(text: &str, count: usize) -> String {
+    let lines: Vec<&str> = text.trim_end().lines().collect();
+    lines[lines.len().saturating_sub(count)..].join("\n")
+}
+
+fn format_duration(duration: TimeDelta) -> String {
+    let total_seconds = duration.num_seconds().max(0);
+    let (hours, minutes, seconds) = (total_seconds / 3600, total_seconds / 60 % 60, total_seconds % 60);
+    if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else {
+        format!("{}m {:02}s", minutes, seconds)
+    }
+}
+
+fn non_empty_or_placeholder(text: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        "(없음)".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn render_markdown(title: &str, sections: &[ReportSection]) -> String {
+    let mut markdown = format!("# {}\n", title);
+    for section in sections {
+        markdown.push_str(&format!("\n## {}\n\n{}\n", section.title, section.body));
+    }
+    markdown
+}
+
+/// 외부 리소스 없이 열 수 있도록 스타일을 포함한 HTML. 각 섹션의 Markdown 원문을 그대로 보여준다.
+fn render_html(title: &str, sections: &[ReportSection]) -> String {
+    let mut body = String::new();
+    for section in sections {
+        body.push_str(&format!(
+            "<section>\n<h2>{}</h2>\n<pre>{}</pre>\n</section>\n",
+            escape_html(section.title),
+            escape_html(&section.body),
+        ));
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"ko\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>\n\
+         body {{ font-family: sans-serif; max-width: 960px; margin: 2em auto; padding: 0 1em; color: #222; }}\n\
+         h1 {{ border-bottom: 2px solid #444; }}\n\
+         h2 {{ margin-top: 2em; border-bottom: 1px solid #ccc; }}\n\
+         pre {{ white-space: pre-wrap; word-wrap: break-word; background: #f6f8fa; padding: 1em; border-radius: 4px; }}\n\
+         </style>\n</head>\n<body>\n<h1>{title}</h1>\n{body}</body>\n</html>\n",
+        title = escape_html(title),
+        body = body,
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::clarification::QuestionAnswer;
+    use crate::workflow::coding::{CodingTaskKind, TaskTrace};
+    use tempfile::TempDir;
+
+    fn time(hour: u32, minute: u32) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(&format!("2026-03-01T{:02}:{:02}:00+09:00", hour, minute)).unwrap()
+    }
+
+    fn make_task(task_id: &str, dependencies: &[&str]) -> CodingTask {
+        CodingTask {
+            task_id: task_id.to_string(),
+            title: format!("{} 제목", task_id),
+            description: "설명".to_string(),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: Vec::new(),
+        }
+    }
+
+    fn sample_activity() -> SessionActivity {
+        let mut activity = SessionActivity {
+            started_at: Some(time(9, 0)),
+            ..SessionActivity::default()
+        };
+        activity.start_task("TASK-00", time(9, 10));
+        activity.finish_task("TASK-00", time(9, 40));
+        activity.reviews.push(ReviewRecord {
+            task_id: "TASK-00".to_string(),
+            iteration: 1,
+            approved: false,
+            comment: "에러 처리를 추가하세요.".to_string(),
+        });
+        activity.build_tests.push(BuildTestRecord {
+            task_id: "TASK-00".to_string(),
+            passed: false,
+            failed_stage: Some("테스트".to_string()),
+            output: "test a ... FAILED".to_string(),
+        });
+        activity
+    }
+
+    #[test]
+    fn finish_task_closes_latest_unfinished_run() {
+        let mut activity = SessionActivity::default();
+        activity.start_task("TASK-00", time(9, 0));
+        activity.finish_task("TASK-00", time(9, 5));
+        activity.start_task("TASK-00", time(10, 0));
+        activity.finish_task("TASK-00", time(10, 20));
+
+        assert_eq!(activity.task_duration("TASK-00"), Some(TimeDelta::minutes(25)));
+        assert_eq!(activity.task_duration("TASK-01"), None);
+    }
+
+    #[test]
+    fn report_contains_all_sections() {
+        let temp_dir = TempDir::new().unwrap();
+        let activity = sample_activity();
+        let tasks = vec![make_task("TASK-00", &[]), make_task("TASK-01", &["TASK-00"])];
+        let reports = vec![TaskReport {
+            task_id: "TASK-00".to_string(),
+            status: CodingTaskStatus::ImplementationSuccess,
+            report: String::new(),
+            report_file_path: PathBuf::new(),
+            trace: TaskTrace::default(),
+        }];
+        let qa_log = vec![QaRound {
+            answers: vec![QuestionAnswer {
+                question: "범위는?".to_string(),
+                answer: ClarificationAnswer::DeferredToAgent,
+            }],
+        }];
+        let input = SessionReportInput {
+            session_name: "session-1",
+            user_request: "로그인 기능",
+            qa_log: &qa_log,
+            spec: "# Spec",
+            plan: "# Plan",
+            tasks: &tasks,
+            task_reports: &reports,
+            activity: &activity,
+            integration_branch: "bear/integration/session-1-x",
+            commit_log: "abc1234 Add login",
+            usage: UsageTotals {
+                query_count: 3,
+                total_cost_usd: 1.25,
+                agent_duration_ms: 90_000,
+            },
+            generated_at: time(11, 0),
+        };
+
+        let paths = save_session_report(temp_dir.path(), &input).unwrap();
+        let markdown = fs::read_to_string(&paths[0]).unwrap();
+        let html = fs::read_to_string(&paths[1]).unwrap();
+
+        assert!(markdown.contains("- 이번 실행 소요 시간: 2h 00m 00s"));
+        assert!(markdown.contains("- 비용: $1.2500"));
+        assert!(markdown.contains("- Q. 범위는?\n  - A. (에이전트에게 맡김)"));
+        assert!(markdown.contains("    TASK-00 --> TASK-01"));
+        assert!(markdown.contains("| TASK-00 | TASK-00 제목 | SUCCESS | 1 | 실패 | 30m 00s |"));
+        assert!(markdown.contains("| TASK-01 | TASK-01 제목 | 미실행 | 0 | - | - |"));
+        assert!(markdown.contains("### [TASK-00] 반복 1: 변경 요청"));
+        assert!(markdown.contains("abc1234 Add login"));
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("TASK-00 --&gt; TASK-01"));
+    }
+
+    #[test]
+    fn last_lines_keeps_tail_of_output() {
+        assert_eq!(last_lines("a\nb\nc\n", 2), "b\nc");
+        assert_eq!(last_lines("a", 5), "a");
+    }
+
+    #[test]
+    fn session_bundle_archives_journal_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal_dir = temp_dir.path().join("20260301").join("session-1");
+        fs::create_dir_all(&journal_dir).unwrap();
+        fs::write(journal_dir.join(REPORT_MARKDOWN_FILE_NAME), "report").unwrap();
+
+        let bundle = create_session_bundle(&journal_dir).unwrap();
+
+        assert_eq!(bundle, temp_dir.path().join("20260301").join("session-1.tar.gz"));
+        let listing = Command::new("tar").arg("-tzf").arg(&bundle).output().unwrap();
+        assert!(String::from_utf8_lossy(&listing.stdout).contains("session-1/session-report.md"));
+    }
+}
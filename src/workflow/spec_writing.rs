@@ -57,6 +57,11 @@ The spec MUST follow this structure:
 5. Acceptance Criteria - Testable criteria for completion
 6. Open Questions - Any remaining uncertainties
 
+REQUIREMENT IDS:
+- Every item in Functional Requirements, Non-Functional Requirements and Acceptance Criteria MUST start with a unique requirement ID in the form `REQ-001`, `REQ-002`, ... (zero-padded, at least three digits), e.g. `- REQ-001: 사용자는 ...`.
+- Acceptance criteria SHOULD reference the requirement IDs they verify.
+- These IDs are used to trace requirements to tasks, commits and tests, so they MUST stay stable.
+
 IMPORTANT:
 - The spec describes WHAT the system must do, not HOW it is implemented internally.
 - The spec MUST be testable with clear acceptance criteria.
@@ -133,6 +138,7 @@ IMPORTANT:
 - The spec describes WHAT the system must do, not HOW it is implemented internally.
 - The spec MUST be testable with clear acceptance criteria.
 - Write the spec in Korean.
+- REQUIREMENT IDS: Keep every existing requirement ID (`REQ-xxx`) unchanged. Never renumber requirements. A new requirement gets the next unused number, and the ID of a removed requirement MUST NOT be reused.
 - The session conversation history contains all prior requirements, Q&A, and previous spec drafts. Use this context to revise the spec.
 - DECISION ESCALATION: The same decision-escalation rules from the initial spec phase still apply. If the user's feedback introduces or reveals new undecided spec-level topics that require user approval (external interface contract, UI/UX behavior, user-facing auth flow, breaking changes to public contracts, observable behavior trade-offs, platform constraints), you MUST set response_type to "clarifying_questions" and ask the user to decide before revising the spec. When asking, present options with pros/cons and your recommendation. Do NOT silently incorporate your own choices into the revised spec. Remember: do NOT ask about implementation details (library choices, architecture patterns, storage engines, etc.) — those belong to the planning phase.
 - USER RESPONSE CLASSIFICATION: When the previous conversation shows that the most recent model output was a set of clarifying questions (especially decision-escalation questions), you MUST classify the user's current message into one of three categories before taking any other action:
@@ -182,11 +188,7 @@ fn format_qa_log(qa_log: &[QaRound]) -> String {
 
     let mut result = String::new();
     for round in qa_log {
-        result.push_str("Assistant's questions:\n");
-        for (i, question) in round.questions.iter().enumerate() {
-            result.push_str(&format!("{}. {}\n", i + 1, question));
-        }
-        result.push_str(&format!("\nUser's answer:\n{}\n\n", round.answer));
+        result.push_str(&round.format_for_prompt());
     }
     result
 }
@@ -212,6 +214,7 @@ pub fn save_approved_spec(dir: &Path, spec_text: &str) -> io::Result<PathBuf> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::workflow::clarification::{ClarificationAnswer, QuestionAnswer};
     use std::fs;
     use tempfile::TempDir;
 
@@ -284,8 +287,10 @@ mod tests {
     #[test]
     fn build_initial_prompt_contains_all_parts() {
         let qa_log = vec![QaRound {
-            questions: vec!["What scope?".to_string()],
-            answer: "Full scope".to_string(),
+            answers: vec![QuestionAnswer {
+                question: "What scope?".to_string(),
+                answer: ClarificationAnswer::Text("Full scope".to_string()),
+            }],
         }];
 
         let user_request_path = Path::new("/workspace/.bear/20250101/session/user-request.md");
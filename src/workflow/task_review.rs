@@ -0,0 +1,434 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::acceptance;
+use super::coding::{CodingTask, CodingTaskKind};
+
+pub const TASKS_FILE_NAME: &str = "tasks.json";
+
+pub const TASK_REVIEW_HELP: &str = "작업 목록을 수정하려면 아래 명령을 입력하세요:\n\
+     \n\
+     move <ID> <위치>        작업 순서를 바꿉니다. (위치는 1부터)\n\
+     drop <ID>               작업을 삭제합니다.\n\
+     merge <ID> <ID2>        ID2 작업을 ID 작업에 합칩니다.\n\
+     add <제목>              새 작업을 목록 끝에 추가합니다.\n\
+     edit <ID>               $EDITOR에서 제목과 설명을 수정합니다.\n\
+     deps <ID> <ID,...|none> 의존 작업을 지정합니다.\n\
+     \n\
+     Ctrl+A를 누르면 현재 목록으로 코딩을 시작합니다.";
+
+#[derive(Debug, PartialEq)]
+pub enum TaskReviewCommand {
+    Move { task_id: String, position: usize },
+    Drop { task_id: String },
+    Merge { into_task_id: String, merged_task_id: String },
+    Add { title: String },
+    Edit { task_id: String, title: String, description: String },
+    Dependencies { task_id: String, dependencies: Vec<String> },
+}
+
+/// 사용자가 입력한 한 개의 작업 목록 수정 명령을 해석한다.
+/// `edit`은 첫 줄 다음에 제목 한 줄과 설명을 이어서 받는다.
+pub fn parse_task_review_command(input: &str) -> Result<TaskReviewCommand, String> {
+    let (first_line, body) = match input.split_once('\n') {
+        Some((first_line, body)) => (first_line.trim(), body),
+        None => (input.trim(), ""),
+    };
+    let mut words = first_line.split_whitespace();
+    let keyword = words.next().unwrap_or_default();
+    let arguments: Vec<&str> = words.collect();
+
+    match (keyword, arguments.as_slice()) {
+        ("move", [task_id, position]) => {
+            let position = position
+                .parse::<usize>()
+                .ok()
+                .filter(|p| *p >= 1)
+                .ok_or_else(|| format!("위치는 1 이상의 숫자여야 합니다: {}", position))?;
+            Ok(TaskReviewCommand::Move { task_id: task_id.to_string(), position })
+        }
+        ("drop", [task_id]) => Ok(TaskReviewCommand::Drop { task_id: task_id.to_string() }),
+        ("merge", [into_task_id, merged_task_id]) => Ok(TaskReviewCommand::Merge {
+            into_task_id: into_task_id.to_string(),
+            merged_task_id: merged_task_id.to_string(),
+        }),
+        ("add", title) if !title.is_empty() => Ok(TaskReviewCommand::Add { title: title.join(" ") }),
+        ("edit", [task_id]) => parse_edit_body(task_id, body),
+        ("deps", [task_id, dependencies]) => Ok(TaskReviewCommand::Dependencies {
+            task_id: task_id.to_string(),
+            dependencies: parse_dependency_list(dependencies),
+        }),
+        _ => Err(format!("알 수 없는 명령입니다: {}", first_line)),
+    }
+}
+
+fn parse_edit_body(task_id: &str, body: &str) -> Result<TaskReviewCommand, String> {
+    let mut lines = body.lines().skip_while(|line| line.trim().is_empty());
+    let title = lines
+        .next()
+        .map(|line| {
+            // 템플릿의 `# ` 제목 표시만 떼고, 제목 안의 `#`은 그대로 둔다.
+            let line = line.trim();
+            line.strip_prefix("# ").unwrap_or(line).trim().to_string()
+        })
+        .filter(|title| !title.is_empty())
+        .ok_or_else(|| format!("{}의 제목이 비어 있습니다.", task_id))?;
+    let description = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+
+    Ok(TaskReviewCommand::Edit {
+        task_id: task_id.to_string(),
+        title,
+        description,
+    })
+}
+
+fn parse_dependency_list(raw: &str) -> Vec<String> {
+    if raw.eq_ignore_ascii_case("none") {
+        return Vec::new();
+    }
+    raw.split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// `$EDITOR`에 미리 채워 넣을 `edit` 명령 본문을 만든다.
+pub fn build_edit_template(task: &CodingTask) -> String {
+    format!("edit {}\n# {}\n\n{}\n", task.task_id, task.title, task.description)
+}
+
+/// 명령을 작업 목록에 적용한다. 결과 목록이 유효한 DAG가 아니면 원래 목록을 유지하고 오류를 반환한다.
+pub fn apply_task_review_command(
+    tasks: &mut Vec<CodingTask>,
+    command: TaskReviewCommand,
+) -> Result<(), String> {
+    let mut edited = tasks.clone();
+    match command {
+        TaskReviewCommand::Move { task_id, position } => {
+            let index = find_task_index(&edited, &task_id)?;
+            let task = edited.remove(index);
+            let target = (position - 1).min(edited.len());
+            edited.insert(target, task);
+        }
+        TaskReviewCommand::Drop { task_id } => {
+            let index = find_task_index(&edited, &task_id)?;
+            let dependents = dependent_task_ids(&edited, &task_id);
+            if !dependents.is_empty() {
+                return Err(format!(
+                    "{}에 의존하는 작업이 있어 삭제할 수 없습니다: {}",
+                    task_id,
+                    dependents.join(", "),
+                ));
+            }
+            edited.remove(index);
+        }
+        TaskReviewCommand::Merge { into_task_id, merged_task_id } => {
+            merge_tasks(&mut edited, &into_task_id, &merged_task_id)?;
+        }
+        TaskReviewCommand::Add { title } => {
+            edited.push(CodingTask {
+                task_id: acceptance::next_task_id(&edited),
+                title: title.clone(),
+                description: title,
+                dependencies: Vec::new(),
+                kind: CodingTaskKind::Implementation,
+                retry: None,
+                requirement_ids: Vec::new(),
+            });
+        }
+        TaskReviewCommand::Edit { task_id, title, description } => {
+            let index = find_task_index(&edited, &task_id)?;
+            edited[index].title = title;
+            edited[index].description = description;
+        }
+        TaskReviewCommand::Dependencies { task_id, dependencies } => {
+            let index = find_task_index(&edited, &task_id)?;
+            edited[index].dependencies = dependencies;
+        }
+    }
+
+    validate_task_graph(&edited)?;
+    *tasks = edited;
+    Ok(())
+}
+
+fn merge_tasks(
+    tasks: &mut Vec<CodingTask>,
+    into_task_id: &str,
+    merged_task_id: &str,
+) -> Result<(), String> {
+    if into_task_id == merged_task_id {
+        return Err("같은 작업끼리는 합칠 수 없습니다.".to_string());
+    }
+    let into_index = find_task_index(tasks, into_task_id)?;
+    let merged_index = find_task_index(tasks, merged_task_id)?;
+    let merged = tasks.remove(merged_index);
+    let into_index = if merged_index < into_index { into_index - 1 } else { into_index };
+
+    let into = &mut tasks[into_index];
+    into.description = format!(
+        "{}\n\n## {} (merged from {})\n{}",
+        into.description, merged.title, merged.task_id, merged.description,
+    );
+    for dependency in merged.dependencies {
+        if !into.dependencies.contains(&dependency) {
+            into.dependencies.push(dependency);
+        }
+    }
+    into.dependencies
+        .retain(|id| id != into_task_id && id != merged_task_id);
+    for requirement_id in merged.requirement_ids {
+        if !into.requirement_ids.contains(&requirement_id) {
+            into.requirement_ids.push(requirement_id);
+        }
+    }
+
+    // 합쳐진 태스크에 의존하던 태스크는 합친 대상 태스크에 의존한다.
+    for task in tasks.iter_mut() {
+        if !task.dependencies.iter().any(|id| id == merged_task_id) {
+            continue;
+        }
+        task.dependencies.retain(|id| id != merged_task_id);
+        if task.task_id != into_task_id && !task.dependencies.iter().any(|id| id == into_task_id) {
+            task.dependencies.push(into_task_id.to_string());
+        }
+    }
+    Ok(())
+}
+
+fn find_task_index(tasks: &[CodingTask], task_id: &str) -> Result<usize, String> {
+    tasks
+        .iter()
+        .position(|task| task.task_id == task_id)
+        .ok_or_else(|| format!("작업을 찾을 수 없습니다: {}", task_id))
+}
+
+fn dependent_task_ids(tasks: &[CodingTask], task_id: &str) -> Vec<String> {
+    tasks
+        .iter()
+        .filter(|task| task.dependencies.iter().any(|id| id == task_id))
+        .map(|task| task.task_id.clone())
+        .collect()
+}
+
+/// 작업은 목록 순서대로 실행되므로, 모든 의존 작업이 목록에서 앞에 있어야 한다.
+/// 이 조건을 만족하면 의존 관계는 순환 없는 DAG가 된다.
+pub fn validate_task_graph(tasks: &[CodingTask]) -> Result<(), String> {
+    if tasks.is_empty() {
+        return Err("작업 목록이 비어 있습니다.".to_string());
+    }
+
+    for (index, task) in tasks.iter().enumerate() {
+        if tasks[..index].iter().any(|earlier| earlier.task_id == task.task_id) {
+            return Err(format!("작업 ID가 중복되었습니다: {}", task.task_id));
+        }
+        for dependency in &task.dependencies {
+            if dependency == &task.task_id {
+                return Err(format!("{}가 자기 자신에 의존합니다.", task.task_id));
+            }
+            if !tasks.iter().any(|t| &t.task_id == dependency) {
+                return Err(format!(
+                    "{}의 의존 작업이 존재하지 않습니다: {}",
+                    task.task_id, dependency,
+                ));
+            }
+            if !tasks[..index].iter().any(|t| &t.task_id == dependency) {
+                return Err(format!(
+                    "{}의 의존 작업 {}이(가) 목록에서 뒤에 있습니다. 순서를 바꾸거나 의존 관계를 수정하세요.",
+                    task.task_id, dependency,
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn format_task_list(tasks: &[CodingTask]) -> String {
+    let mut message = format!("작업 목록 ({}개):\n", tasks.len());
+    for (i, task) in tasks.iter().enumerate() {
+        message.push_str(&format!("\n{}. [{}] {}", i + 1, task.task_id, task.title));
+        if !task.dependencies.is_empty() {
+            message.push_str(&format!(" (의존: {})", task.dependencies.join(", ")));
+        }
+        if !task.requirement_ids.is_empty() {
+            message.push_str(&format!(" [요구사항: {}]", task.requirement_ids.join(", ")));
+        }
+    }
+    message
+}
+
+pub fn save_tasks(dir: &Path, tasks: &[CodingTask]) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+
+    let file_path = dir.join(TASKS_FILE_NAME);
+    let content = serde_json::to_string_pretty(tasks)?;
+    fs::write(&file_path, content)?;
+
+    Ok(file_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn task(id: &str, dependencies: &[&str]) -> CodingTask {
+        CodingTask {
+            task_id: id.to_string(),
+            title: format!("{} 제목", id),
+            description: format!("{} 설명", id),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: Vec::new(),
+        }
+    }
+
+    fn task_ids(tasks: &[CodingTask]) -> Vec<&str> {
+        tasks.iter().map(|t| t.task_id.as_str()).collect()
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!(
+            parse_task_review_command("move TASK-01 1").unwrap(),
+            TaskReviewCommand::Move { task_id: "TASK-01".to_string(), position: 1 }
+        );
+        assert_eq!(
+            parse_task_review_command("deps TASK-02 TASK-00,TASK-01").unwrap(),
+            TaskReviewCommand::Dependencies {
+                task_id: "TASK-02".to_string(),
+                dependencies: vec!["TASK-00".to_string(), "TASK-01".to_string()],
+            }
+        );
+        assert_eq!(
+            parse_task_review_command("add 로깅 추가").unwrap(),
+            TaskReviewCommand::Add { title: "로깅 추가".to_string() }
+        );
+        assert!(parse_task_review_command("move TASK-01 0").is_err());
+        assert!(parse_task_review_command("rename TASK-01").is_err());
+    }
+
+    #[test]
+    fn parse_edit_command_from_editor_template() {
+        let template = build_edit_template(&task("TASK-00", &[]));
+        let edited = template.replace("TASK-00 설명", "새 설명\n둘째 줄");
+
+        let command = parse_task_review_command(&edited).unwrap();
+
+        assert_eq!(
+            command,
+            TaskReviewCommand::Edit {
+                task_id: "TASK-00".to_string(),
+                title: "TASK-00 제목".to_string(),
+                description: "새 설명\n둘째 줄".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_edit_keeps_hash_characters_inside_title() {
+        let command = parse_task_review_command("edit TASK-00\n# #42 이슈의 C# 바인딩 수정\n설명").unwrap();
+
+        assert_eq!(
+            command,
+            TaskReviewCommand::Edit {
+                task_id: "TASK-00".to_string(),
+                title: "#42 이슈의 C# 바인딩 수정".to_string(),
+                description: "설명".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn move_rejects_order_that_breaks_dependencies() {
+        let mut tasks = vec![task("TASK-00", &[]), task("TASK-01", &["TASK-00"])];
+
+        let result = apply_task_review_command(
+            &mut tasks,
+            TaskReviewCommand::Move { task_id: "TASK-01".to_string(), position: 1 },
+        );
+
+        assert!(result.is_err());
+        assert_eq!(task_ids(&tasks), vec!["TASK-00", "TASK-01"]);
+    }
+
+    #[test]
+    fn drop_rejects_task_with_dependents() {
+        let mut tasks = vec![task("TASK-00", &[]), task("TASK-01", &["TASK-00"])];
+
+        let result = apply_task_review_command(
+            &mut tasks,
+            TaskReviewCommand::Drop { task_id: "TASK-00".to_string() },
+        );
+        assert!(result.unwrap_err().contains("TASK-01"));
+
+        apply_task_review_command(
+            &mut tasks,
+            TaskReviewCommand::Drop { task_id: "TASK-01".to_string() },
+        )
+        .unwrap();
+        assert_eq!(task_ids(&tasks), vec!["TASK-00"]);
+    }
+
+    #[test]
+    fn merge_redirects_dependents_to_surviving_task() {
+        let mut tasks = vec![
+            task("TASK-00", &[]),
+            task("TASK-01", &[]),
+            task("TASK-02", &["TASK-00"]),
+            task("TASK-03", &["TASK-02"]),
+        ];
+
+        apply_task_review_command(
+            &mut tasks,
+            TaskReviewCommand::Merge {
+                into_task_id: "TASK-01".to_string(),
+                merged_task_id: "TASK-02".to_string(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(task_ids(&tasks), vec!["TASK-00", "TASK-01", "TASK-03"]);
+        assert_eq!(tasks[1].dependencies, vec!["TASK-00".to_string()]);
+        assert!(tasks[1].description.contains("TASK-02 설명"));
+        assert_eq!(tasks[2].dependencies, vec!["TASK-01".to_string()]);
+    }
+
+    #[test]
+    fn add_appends_task_with_next_id() {
+        let mut tasks = vec![task("TASK-00", &[]), task("TASK-03", &[])];
+
+        apply_task_review_command(
+            &mut tasks,
+            TaskReviewCommand::Add { title: "문서 정리".to_string() },
+        )
+        .unwrap();
+
+        assert_eq!(tasks[2].task_id, "TASK-04");
+        assert_eq!(tasks[2].title, "문서 정리");
+    }
+
+    #[test]
+    fn validate_rejects_unknown_and_self_dependencies() {
+        assert!(validate_task_graph(&[task("TASK-00", &["TASK-09"])]).is_err());
+        assert!(validate_task_graph(&[task("TASK-00", &["TASK-00"])]).is_err());
+        assert!(validate_task_graph(&[task("TASK-00", &[]), task("TASK-00", &[])]).is_err());
+        assert!(validate_task_graph(&[]).is_err());
+    }
+
+    #[test]
+    fn save_tasks_writes_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let tasks = vec![task("TASK-00", &[])];
+
+        let path = save_tasks(temp_dir.path(), &tasks).unwrap();
+
+        assert_eq!(path, temp_dir.path().join("tasks.json"));
+        let loaded: Vec<CodingTask> =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(loaded[0].task_id, "TASK-00");
+    }
+}
@@ -0,0 +1,329 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::coding::{CodingTask, CodingTaskStatus, TaskReport};
+
+pub const TRACEABILITY_FILE_NAME: &str = "traceability.md";
+const REQUIREMENT_ID_PREFIX: &str = "REQ-";
+const MAX_SUMMARY_CHARS: usize = 60;
+
+/// 스펙에서 찾은 요구사항 ID와 처음 등장한 줄의 요약.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpecRequirement {
+    pub id: String,
+    pub summary: String,
+}
+
+/// 요구사항 하나를 구현한 태스크, 통합된 커밋, 검증하는 테스트.
+#[derive(Debug, PartialEq)]
+pub struct TraceabilityRow {
+    pub requirement: SpecRequirement,
+    pub task_ids: Vec<String>,
+    pub commits: Vec<String>,
+    pub tests: Vec<String>,
+}
+
+impl TraceabilityRow {
+    /// 비어 있는 연결 항목의 이름. 비어 있으면 추적이 완전하다.
+    pub fn missing_links(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        if self.task_ids.is_empty() {
+            missing.push("작업");
+        }
+        if self.commits.is_empty() {
+            missing.push("커밋");
+        }
+        if self.tests.is_empty() {
+            missing.push("테스트");
+        }
+        missing
+    }
+}
+
+/// 스펙에 등장하는 `REQ-xxx` ID를 처음 등장한 순서대로 모은다.
+pub fn extract_requirements(spec: &str) -> Vec<SpecRequirement> {
+    let mut requirements: Vec<SpecRequirement> = Vec::new();
+    for line in spec.lines() {
+        for id in find_requirement_ids(line) {
+            if requirements.iter().any(|r| r.id == id) {
+                continue;
+            }
+            requirements.push(SpecRequirement {
+                summary: summarize_line(line, &id),
+                id,
+            });
+        }
+    }
+    requirements
+}
+
+fn find_requirement_ids(line: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut rest = line;
+    while let Some(position) = rest.find(REQUIREMENT_ID_PREFIX) {
+        let after_prefix = &rest[position + REQUIREMENT_ID_PREFIX.len()..];
+        let digit_count = after_prefix.chars().take_while(char::is_ascii_digit).count();
+        if digit_count > 0 {
+            ids.push(format!("{}{}", REQUIREMENT_ID_PREFIX, &after_prefix[..digit_count]));
+        }
+        rest = &after_prefix[digit_count..];
+    }
+    ids
+}
+
+fn summarize_line(line: &str, id: &str) -> String {
+    let summary = line
+        .replace(id, "")
+        .replace("**", "")
+        .replace('|', " ")
+        .trim_start_matches(|c: char| c == '#' || c == '-' || c == '*' || c.is_whitespace())
+        .trim_start_matches(|c: char| c == ':' || c == ')' || c == ']' || c == '[' || c.is_whitespace())
+        .trim()
+        .to_string();
+
+    if summary.chars().count() > MAX_SUMMARY_CHARS {
+        let truncated: String = summary.chars().take(MAX_SUMMARY_CHARS).collect();
+        format!("{}…", truncated)
+    } else {
+        summary
+    }
+}
+
+/// 요구사항마다 태스크의 `requirement_ids`, 성공한 태스크 보고서의 커밋과 테스트를 연결한다.
+pub fn build_traceability_matrix(
+    requirements: &[SpecRequirement],
+    tasks: &[CodingTask],
+    task_reports: &[TaskReport],
+) -> Vec<TraceabilityRow> {
+    let succeeded_reports: Vec<&TaskReport> = task_reports
+        .iter()
+        .filter(|report| report.status == CodingTaskStatus::ImplementationSuccess)
+        .collect();
+
+    requirements
+        .iter()
+        .map(|requirement| {
+            let task_ids: Vec<String> = tasks
+                .iter()
+                .filter(|task| task.requirement_ids.contains(&requirement.id))
+                .map(|task| task.task_id.clone())
+                .collect();
+
+            let mut commits = Vec::new();
+            let mut tests = Vec::new();
+            for report in &succeeded_reports {
+                if task_ids.contains(&report.task_id) {
+                    push_unique(&mut commits, &report.trace.commits);
+                }
+                for requirement_tests in &report.trace.requirement_tests {
+                    if requirement_tests.requirement_id == requirement.id {
+                        push_unique(&mut tests, &requirement_tests.tests);
+                    }
+                }
+            }
+
+            TraceabilityRow {
+                requirement: requirement.clone(),
+                task_ids,
+                commits,
+                tests,
+            }
+        })
+        .collect()
+}
+
+fn push_unique(target: &mut Vec<String>, items: &[String]) {
+    for item in items {
+        if !target.contains(item) {
+            target.push(item.clone());
+        }
+    }
+}
+
+/// 태스크가 참조하지만 스펙에는 없는 요구사항 ID.
+pub fn unknown_requirement_ids(requirements: &[SpecRequirement], tasks: &[CodingTask]) -> Vec<String> {
+    let mut unknown = Vec::new();
+    for task in tasks {
+        for id in &task.requirement_ids {
+            if !requirements.iter().any(|r| &r.id == id) && !unknown.contains(id) {
+                unknown.push(id.clone());
+            }
+        }
+    }
+    unknown
+}
+
+pub fn render_traceability_matrix(rows: &[TraceabilityRow], unknown_ids: &[String]) -> String {
+    let mut document = String::from(
+        "# Traceability Matrix\n\n\
+         | 요구사항 | 요약 | 작업 | 커밋 | 테스트 | 누락 |\n\
+         |---|---|---|---|---|---|\n",
+    );
+    for row in rows {
+        let missing = row.missing_links();
+        document.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            row.requirement.id,
+            row.requirement.summary,
+            join_or_dash(&row.task_ids, ", "),
+            join_or_dash(&row.commits, "<br>"),
+            join_or_dash(&row.tests, "<br>"),
+            if missing.is_empty() { "-".to_string() } else { format!("⚠ {}", missing.join(", ")) },
+        ));
+    }
+
+    let gaps = format_gaps(rows);
+    document.push_str(&format!("\n## 누락된 연결\n\n{}\n", gaps));
+
+    if !unknown_ids.is_empty() {
+        document.push_str(&format!(
+            "\n## 스펙에 없는 요구사항 ID\n\n작업이 참조하지만 스펙에서 찾을 수 없습니다: {}\n",
+            unknown_ids.join(", "),
+        ));
+    }
+    document
+}
+
+/// 누락된 연결이 있는 요구사항을 한 줄씩 나열한다. 모두 연결되었으면 그렇게 알린다.
+pub fn format_gaps(rows: &[TraceabilityRow]) -> String {
+    let gap_lines: Vec<String> = rows
+        .iter()
+        .filter(|row| !row.missing_links().is_empty())
+        .map(|row| format!("- {}: {} 없음", row.requirement.id, row.missing_links().join(", ")))
+        .collect();
+
+    if gap_lines.is_empty() {
+        "모든 요구사항이 작업, 커밋, 테스트와 연결되었습니다.".to_string()
+    } else {
+        gap_lines.join("\n")
+    }
+}
+
+fn join_or_dash(items: &[String], separator: &str) -> String {
+    if items.is_empty() {
+        "-".to_string()
+    } else {
+        items.join(separator).replace('|', "\\|")
+    }
+}
+
+pub fn save_traceability_matrix(dir: &Path, content: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(TRACEABILITY_FILE_NAME);
+    fs::write(&path, content)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::coding::{CodingTaskKind, RequirementTests, TaskTrace};
+
+    fn make_task(task_id: &str, requirement_ids: &[&str]) -> CodingTask {
+        CodingTask {
+            task_id: task_id.to_string(),
+            title: "작업".to_string(),
+            description: "설명".to_string(),
+            dependencies: vec![],
+            kind: CodingTaskKind::Implementation,
+            retry: None,
+            requirement_ids: requirement_ids.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+
+    fn make_report(task_id: &str, status: CodingTaskStatus, trace: TaskTrace) -> TaskReport {
+        TaskReport {
+            task_id: task_id.to_string(),
+            status,
+            report: String::new(),
+            report_file_path: PathBuf::new(),
+            trace,
+        }
+    }
+
+    #[test]
+    fn extract_requirements_keeps_first_occurrence_in_order() {
+        let spec = "# Spec\n\
+                    ## 기능 요구사항\n\
+                    - **REQ-001**: 사용자는 로그인할 수 있다.\n\
+                    - REQ-002: 비밀번호를 재설정할 수 있다.\n\
+                    ## 인수 조건\n\
+                    - REQ-001, REQ-002를 검증하는 테스트가 통과한다.\n\
+                    - REQ-DRAFT는 ID가 아니다.\n";
+
+        let requirements = extract_requirements(spec);
+
+        assert_eq!(
+            requirements,
+            vec![
+                SpecRequirement {
+                    id: "REQ-001".to_string(),
+                    summary: "사용자는 로그인할 수 있다.".to_string(),
+                },
+                SpecRequirement {
+                    id: "REQ-002".to_string(),
+                    summary: "비밀번호를 재설정할 수 있다.".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn matrix_links_tasks_commits_and_tests_from_successful_reports() {
+        let requirements = extract_requirements("- REQ-001: 로그인\n- REQ-002: 재설정\n- REQ-003: 감사 로그\n");
+        let tasks = vec![
+            make_task("TASK-00", &["REQ-001"]),
+            make_task("TASK-01", &["REQ-002", "REQ-009"]),
+        ];
+        let reports = vec![
+            make_report(
+                "TASK-00",
+                CodingTaskStatus::ImplementationSuccess,
+                TaskTrace {
+                    requirement_tests: vec![RequirementTests {
+                        requirement_id: "REQ-001".to_string(),
+                        tests: vec!["login_succeeds".to_string()],
+                    }],
+                    commits: vec!["abc1234 Add login".to_string()],
+                },
+            ),
+            make_report(
+                "TASK-01",
+                CodingTaskStatus::ImplementationBlocked,
+                TaskTrace {
+                    requirement_tests: vec![RequirementTests {
+                        requirement_id: "REQ-002".to_string(),
+                        tests: vec!["reset_works".to_string()],
+                    }],
+                    commits: vec![],
+                },
+            ),
+        ];
+
+        let rows = build_traceability_matrix(&requirements, &tasks, &reports);
+
+        assert!(rows[0].missing_links().is_empty());
+        assert_eq!(rows[0].commits, vec!["abc1234 Add login"]);
+        assert_eq!(rows[1].task_ids, vec!["TASK-01"]);
+        assert_eq!(rows[1].missing_links(), vec!["커밋", "테스트"]);
+        assert_eq!(rows[2].missing_links(), vec!["작업", "커밋", "테스트"]);
+        assert_eq!(unknown_requirement_ids(&requirements, &tasks), vec!["REQ-009"]);
+
+        let document = render_traceability_matrix(&rows, &["REQ-009".to_string()]);
+        assert!(document.contains("| REQ-001 | 로그인 | TASK-00 | abc1234 Add login | login_succeeds | - |"));
+        assert!(document.contains("- REQ-003: 작업, 커밋, 테스트 없음"));
+        assert!(document.contains("스펙에서 찾을 수 없습니다: REQ-009"));
+    }
+
+    #[test]
+    fn format_gaps_reports_complete_traceability() {
+        let rows = vec![TraceabilityRow {
+            requirement: SpecRequirement { id: "REQ-001".to_string(), summary: String::new() },
+            task_ids: vec!["TASK-00".to_string()],
+            commits: vec!["abc".to_string()],
+            tests: vec!["t".to_string()],
+        }];
+        assert_eq!(format_gaps(&rows), "모든 요구사항이 작업, 커밋, 테스트와 연결되었습니다.");
+    }
+}
@@ -0,0 +1,313 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::acceptance;
+use super::coding::{CodingTask, CodingTaskKind};
+
+pub const VERIFICATION_FILE_NAME: &str = "acceptance-verification.md";
+const FIX_CRITERIA_COMMAND: &str = "/fix-criteria";
+
+#[derive(Debug, Deserialize)]
+pub struct VerificationResult {
+    pub criteria: Vec<CriterionResult>,
+    pub summary: String,
+}
+
+/// 인수 조건 하나에 대한 검증 결과와 근거.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct CriterionResult {
+    pub criterion_id: String,
+    pub criterion: String,
+    pub verdict: CriterionVerdict,
+    pub evidence: String,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+pub enum CriterionVerdict {
+    #[serde(rename = "PASS")]
+    Pass,
+    #[serde(rename = "FAIL")]
+    Fail,
+    #[serde(rename = "UNVERIFIABLE")]
+    Unverifiable,
+}
+
+impl CriterionVerdict {
+    fn label(self) -> &'static str {
+        match self {
+            CriterionVerdict::Pass => "PASS",
+            CriterionVerdict::Fail => "FAIL",
+            CriterionVerdict::Unverifiable => "UNVERIFIABLE",
+        }
+    }
+}
+
+pub fn verification_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "criteria": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "criterion_id": { "type": "string" },
+                        "criterion": { "type": "string" },
+                        "verdict": {
+                            "type": "string",
+                            "enum": ["PASS", "FAIL", "UNVERIFIABLE"]
+                        },
+                        "evidence": { "type": "string" }
+                    },
+                    "required": ["criterion_id", "criterion", "verdict", "evidence"],
+                    "additionalProperties": false
+                }
+            },
+            "summary": { "type": "string" }
+        },
+        "required": ["criteria", "summary"],
+        "additionalProperties": false
+    })
+}
+
+pub fn system_prompt() -> &'static str {
+    r#"# Role
+
+You are the **acceptance verification** assistant. All implementation tasks of this session have been merged into the integration branch. Your job is to check the finished integration branch as a whole against every acceptance criterion of the specification.
+
+**Core rules:**
+- The specification is the canonical source of acceptance criteria.
+- You MUST NOT modify, create, or delete any files, and you MUST NOT commit. Verification only.
+- Judge the code that exists on the integration branch, not what task reports claim.
+- Every verdict MUST be backed by concrete evidence: test names and their results, command output, or file paths and symbols you inspected.
+
+---
+
+# Verification Process
+
+1. Read the specification and enumerate EVERY acceptance criterion in the order it appears. Do not merge or skip criteria.
+2. For each criterion:
+   - Find the tests that exercise it and run them, or run the relevant build/test commands.
+   - If no test covers it, inspect the code paths and, when feasible, exercise the behavior directly (for example by running the program with suitable input).
+3. Decide a verdict:
+   - `PASS`: the evidence shows the criterion is met.
+   - `FAIL`: the evidence shows the criterion is not met, or only partially met.
+   - `UNVERIFIABLE`: the criterion cannot be checked in this environment (for example it needs external services, manual UI interaction, or production data). Explain what is missing.
+
+---
+
+# Output Format
+
+Return JSON following the given JSON Schema:
+- criterion_id: the requirement ID the criterion carries in the specification (for example `REQ-012`). If it has none, use `AC-<n>` numbered by its order in the specification, starting from 1.
+- criterion: the criterion text, quoted or closely paraphrased.
+- verdict: `PASS`, `FAIL`, or `UNVERIFIABLE`.
+- evidence: what you ran or inspected and what you observed. For `FAIL`, state what is missing or wrong precisely enough for a coding agent to fix it.
+- summary: 2-4 sentences on the overall state of the integration branch.
+
+Write criterion, evidence and summary in Korean, preserving code identifiers as-is."#
+}
+
+const VERIFICATION_PROMPT_TEMPLATE: &str = r#"Verify the integration branch against every acceptance criterion in the specification.
+
+Output MUST be valid JSON conforming to the provided JSON Schema.
+
+---
+
+You MUST read the following files before verifying:
+- Specification:
+  - {{SPEC_PATH}}
+- Plan:
+  - {{PLAN_PATH}}
+
+Integration context:
+- Integration Branch: {{INTEGRATION_BRANCH}} (checked out in the current working directory)
+- Changes of this session: `git log --oneline {{BASE_REVISION}}..{{INTEGRATION_BRANCH}}`"#;
+
+pub fn build_verification_prompt(
+    spec_path: &Path,
+    plan_path: &Path,
+    integration_branch: &str,
+    base_revision: &str,
+) -> String {
+    VERIFICATION_PROMPT_TEMPLATE
+        .replace("{{SPEC_PATH}}", &spec_path.display().to_string())
+        .replace("{{PLAN_PATH}}", &plan_path.display().to_string())
+        .replace("{{INTEGRATION_BRANCH}}", integration_branch)
+        .replace("{{BASE_REVISION}}", base_revision)
+}
+
+pub fn failed_criteria(result: &VerificationResult) -> Vec<CriterionResult> {
+    result
+        .criteria
+        .iter()
+        .filter(|criterion| criterion.verdict == CriterionVerdict::Fail)
+        .cloned()
+        .collect()
+}
+
+/// 판정별 개수와 PASS가 아닌 인수 조건을 사용자에게 보여줄 요약으로 만든다.
+pub fn format_verification_summary(result: &VerificationResult) -> String {
+    let count = |verdict: CriterionVerdict| {
+        result.criteria.iter().filter(|c| c.verdict == verdict).count()
+    };
+    let mut summary = format!(
+        "인수 조건 검증: PASS {}, FAIL {}, UNVERIFIABLE {}\n{}",
+        count(CriterionVerdict::Pass),
+        count(CriterionVerdict::Fail),
+        count(CriterionVerdict::Unverifiable),
+        result.summary.trim(),
+    );
+
+    for criterion in result.criteria.iter().filter(|c| c.verdict != CriterionVerdict::Pass) {
+        summary.push_str(&format!(
+            "\n- [{}] {}: {}",
+            criterion.verdict.label(),
+            criterion.criterion_id,
+            criterion.criterion,
+        ));
+    }
+
+    if count(CriterionVerdict::Fail) > 0 {
+        summary.push_str(&format!(
+            "\n\n실패한 인수 조건을 새 작업으로 만들려면 `{} [<ID> ...]`를 입력하세요. ID를 생략하면 모든 실패 조건을 작업으로 만듭니다.",
+            FIX_CRITERIA_COMMAND,
+        ));
+    }
+    summary
+}
+
+pub fn render_verification_markdown(result: &VerificationResult) -> String {
+    let mut markdown = format!("# Acceptance Verification\n\n{}\n", result.summary.trim());
+    for criterion in &result.criteria {
+        markdown.push_str(&format!(
+            "\n## {} [{}]\n\n{}\n\n**근거**\n\n{}\n",
+            criterion.criterion_id,
+            criterion.verdict.label(),
+            criterion.criterion.trim(),
+            criterion.evidence.trim(),
+        ));
+    }
+    markdown
+}
+
+pub fn save_verification_report(dir: &Path, result: &VerificationResult) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(VERIFICATION_FILE_NAME);
+    fs::write(&path, render_verification_markdown(result))?;
+    Ok(path)
+}
+
+/// `/fix-criteria REQ-003 AC-2` 형식의 입력을 해석한다. ID가 없으면 빈 목록(모든 실패 조건)을 반환한다.
+/// 명령이 아니면 None을 반환한다.
+pub fn parse_fix_criteria_request(input: &str) -> Option<Vec<String>> {
+    acceptance::parse_command_ids(input, FIX_CRITERIA_COMMAND)
+}
+
+/// 실패한 인수 조건을 통합 브랜치에서 수정할 코딩 태스크로 변환한다.
+pub fn build_criterion_fix_task(tasks: &[CodingTask], criterion: &CriterionResult) -> CodingTask {
+    let description = format!(
+        "통합 브랜치 인수 조건 검증에서 실패한 조건입니다. \
+         통합 브랜치의 현재 코드를 기준으로 이 조건을 만족하도록 수정하고, 조건을 검증하는 테스트를 추가하세요.\n\n\
+         인수 조건 ({}):\n{}\n\n검증 근거:\n{}",
+        criterion.criterion_id,
+        criterion.criterion.trim(),
+        criterion.evidence.trim(),
+    );
+    let requirement_ids = if criterion.criterion_id.starts_with("REQ-") {
+        vec![criterion.criterion_id.clone()]
+    } else {
+        Vec::new()
+    };
+
+    CodingTask {
+        task_id: acceptance::next_task_id(tasks),
+        title: format!("인수 조건 수정: {}", criterion.criterion_id),
+        description,
+        dependencies: Vec::new(),
+        kind: CodingTaskKind::Implementation,
+        retry: None,
+        requirement_ids,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_criterion(id: &str, verdict: CriterionVerdict) -> CriterionResult {
+        CriterionResult {
+            criterion_id: id.to_string(),
+            criterion: format!("{} 조건", id),
+            verdict,
+            evidence: "cargo test 결과".to_string(),
+        }
+    }
+
+    fn sample_result() -> VerificationResult {
+        VerificationResult {
+            criteria: vec![
+                make_criterion("REQ-001", CriterionVerdict::Pass),
+                make_criterion("REQ-002", CriterionVerdict::Fail),
+                make_criterion("AC-3", CriterionVerdict::Unverifiable),
+            ],
+            summary: "대부분 충족합니다.".to_string(),
+        }
+    }
+
+    #[test]
+    fn verification_result_deserializes_verdicts() {
+        let json = serde_json::json!({
+            "criteria": [
+                { "criterion_id": "REQ-001", "criterion": "로그인", "verdict": "PASS", "evidence": "login_succeeds 통과" },
+                { "criterion_id": "AC-2", "criterion": "알림", "verdict": "UNVERIFIABLE", "evidence": "외부 서비스 필요" }
+            ],
+            "summary": "요약"
+        });
+
+        let result: VerificationResult = serde_json::from_value(json).unwrap();
+
+        assert_eq!(result.criteria[0].verdict, CriterionVerdict::Pass);
+        assert_eq!(result.criteria[1].verdict, CriterionVerdict::Unverifiable);
+    }
+
+    #[test]
+    fn summary_lists_non_passing_criteria_and_fix_hint() {
+        let summary = format_verification_summary(&sample_result());
+
+        assert!(summary.starts_with("인수 조건 검증: PASS 1, FAIL 1, UNVERIFIABLE 1"));
+        assert!(summary.contains("- [FAIL] REQ-002: REQ-002 조건"));
+        assert!(summary.contains("- [UNVERIFIABLE] AC-3"));
+        assert!(!summary.contains("[PASS]"));
+        assert!(summary.contains("/fix-criteria"));
+    }
+
+    #[test]
+    fn parse_fix_criteria_request_reads_ids() {
+        assert_eq!(parse_fix_criteria_request("/fix-criteria"), Some(vec![]));
+        assert_eq!(
+            parse_fix_criteria_request("/fix-criteria REQ-002, AC-3"),
+            Some(vec!["REQ-002".to_string(), "AC-3".to_string()])
+        );
+        assert_eq!(parse_fix_criteria_request("/fix-criteriaX"), None);
+        assert_eq!(parse_fix_criteria_request("버튼 색을 바꿔주세요"), None);
+    }
+
+    #[test]
+    fn fix_task_carries_criterion_and_requirement_id() {
+        let criterion = make_criterion("REQ-002", CriterionVerdict::Fail);
+
+        let task = build_criterion_fix_task(&[], &criterion);
+
+        assert_eq!(task.task_id, "TASK-00");
+        assert_eq!(task.requirement_ids, vec!["REQ-002"]);
+        assert!(task.description.contains("REQ-002 조건"));
+        assert!(task.description.contains("cargo test 결과"));
+        assert!(build_criterion_fix_task(&[], &make_criterion("AC-3", CriterionVerdict::Fail))
+            .requirement_ids
+            .is_empty());
+    }
+}
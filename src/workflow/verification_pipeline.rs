@@ -0,0 +1,389 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_TIMEOUT_SECONDS: u64 = 180;
+const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// 태스크를 통합 브랜치에 머지하기 전에 태스크 워크트리에서 순서대로 실행하는 검증 단계.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VerificationStage {
+    pub name: String,
+    /// `sh -c`로 실행할 명령어.
+    pub command: String,
+    #[serde(default = "default_timeout_seconds")]
+    pub timeout_seconds: u64,
+    /// 태스크 워크트리 기준 상대 경로. 없으면 워크트리 루트에서 실행한다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub environment: BTreeMap<String, String>,
+    /// 실패하면 머지를 막을지 여부. 선택 단계의 실패는 경고로 남고 수리 에이전트에게만 전달된다.
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_timeout_seconds() -> u64 {
+    DEFAULT_TIMEOUT_SECONDS
+}
+
+fn default_required() -> bool {
+    true
+}
+
+impl VerificationStage {
+    /// 기본 시간 제한으로 워크트리 루트에서 실행하는 필수 단계.
+    pub fn required(name: &str, command: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            command: command.to_string(),
+            timeout_seconds: DEFAULT_TIMEOUT_SECONDS,
+            working_directory: None,
+            environment: BTreeMap::new(),
+            required: true,
+        }
+    }
+
+    /// 프롬프트와 메시지에 쓰는 한 줄 설명.
+    pub fn describe(&self) -> String {
+        let requirement = if self.required { "required" } else { "optional" };
+        let mut description = format!("{} ({}): `{}`", self.name, requirement, self.command);
+        if let Some(directory) = &self.working_directory {
+            description.push_str(&format!(" in `{}`", directory.display()));
+        }
+        description
+    }
+}
+
+/// 검증 단계 하나의 실패.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageFailure {
+    pub stage: VerificationStage,
+    pub output: String,
+}
+
+/// 파이프라인 실행 결과. 필수 단계가 실패하면 거기서 멈추므로 실패는 그 앞의 선택 단계 실패를 포함한다.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PipelineOutcome {
+    pub failures: Vec<StageFailure>,
+}
+
+impl PipelineOutcome {
+    /// 머지를 막는 필수 단계의 실패.
+    pub fn blocking_failure(&self) -> Option<&StageFailure> {
+        self.failures.iter().find(|failure| failure.stage.required)
+    }
+
+    pub fn optional_failures(&self) -> impl Iterator<Item = &StageFailure> {
+        self.failures.iter().filter(|failure| !failure.stage.required)
+    }
+}
+
+/// 이름이 겹치지 않고, 명령어와 시간 제한이 있고, 작업 디렉토리가 워크트리 안에 있는지 확인한다.
+pub fn validate_pipeline(stages: &[VerificationStage]) -> Result<(), String> {
+    for (index, stage) in stages.iter().enumerate() {
+        if stage.name.trim().is_empty() {
+            return Err(format!("verification stage {} needs a name", index + 1));
+        }
+        if stages[..index].iter().any(|earlier| earlier.name == stage.name) {
+            return Err(format!("verification stage name '{}' is duplicated", stage.name));
+        }
+        if stage.command.trim().is_empty() {
+            return Err(format!("verification stage '{}' needs a command", stage.name));
+        }
+        if stage.timeout_seconds == 0 {
+            return Err(format!("verification stage '{}' needs a positive timeout", stage.name));
+        }
+        if let Some(directory) = &stage.working_directory
+            && !directory
+                .components()
+                .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(format!(
+                "verification stage '{}' must use a working directory inside the worktree",
+                stage.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 빌드 시스템 파일로 `build`, `test` 두 단계를 추측한다.
+pub fn detect_pipeline(worktree_path: &Path) -> Option<Vec<VerificationStage>> {
+    let makefile_path = worktree_path.join("Makefile");
+    if makefile_path.exists()
+        && let Ok(content) = fs::read_to_string(&makefile_path)
+    {
+        let has_build = content.lines().any(|line| line.starts_with("build:"));
+        let has_test = content.lines().any(|line| line.starts_with("test:"));
+        if has_build && has_test {
+            return Some(build_and_test_stages("make build", "make test"));
+        }
+    }
+
+    if worktree_path.join("Cargo.toml").exists() {
+        return Some(build_and_test_stages("cargo build", "cargo test"));
+    }
+
+    if let Some(stages) = detect_npm_pipeline(worktree_path) {
+        return Some(stages);
+    }
+
+    if worktree_path.join("go.mod").exists() {
+        return Some(build_and_test_stages("go build ./...", "go test ./..."));
+    }
+
+    None
+}
+
+fn detect_npm_pipeline(worktree_path: &Path) -> Option<Vec<VerificationStage>> {
+    let package_json_path = worktree_path.join("package.json");
+    let content = fs::read_to_string(&package_json_path).ok()?;
+    let parsed: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let scripts = parsed.get("scripts")?;
+
+    let has_build = scripts.get("build").is_some();
+    let has_test = scripts.get("test").is_some();
+
+    if has_build && has_test {
+        Some(build_and_test_stages("npm run build", "npm test"))
+    } else {
+        None
+    }
+}
+
+pub fn build_and_test_stages(build_command: &str, test_command: &str) -> Vec<VerificationStage> {
+    vec![
+        VerificationStage::required("build", build_command),
+        VerificationStage::required("test", test_command),
+    ]
+}
+
+/// 단계를 순서대로 실행한다. 선택 단계가 실패해도 계속하고, 필수 단계가 실패하면 멈춘다.
+pub fn run_pipeline(worktree_path: &Path, stages: &[VerificationStage]) -> Result<PipelineOutcome, String> {
+    let mut outcome = PipelineOutcome::default();
+    for stage in stages {
+        let result = run_stage(worktree_path, stage)?;
+        if result.success {
+            continue;
+        }
+        outcome.failures.push(StageFailure {
+            stage: stage.clone(),
+            output: result.combined_output,
+        });
+        if stage.required {
+            break;
+        }
+    }
+    Ok(outcome)
+}
+
+struct ShellCommandResult {
+    success: bool,
+    combined_output: String,
+}
+
+fn run_stage(worktree_path: &Path, stage: &VerificationStage) -> Result<ShellCommandResult, String> {
+    let working_dir = match &stage.working_directory {
+        Some(directory) => worktree_path.join(directory),
+        None => worktree_path.to_path_buf(),
+    };
+    let timeout = format!("{}s", stage.timeout_seconds);
+    let output = Command::new("timeout")
+        .current_dir(&working_dir)
+        .envs(&stage.environment)
+        .args(["--signal=TERM", "--kill-after=15s", &timeout, "sh", "-c", &stage.command])
+        .output()
+        .map_err(|e| format!("failed to execute '{}': {}", stage.command, e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut combined_output = format!("--- stdout ---\n{}\n--- stderr ---\n{}", stdout, stderr);
+    if output.status.code() == Some(TIMEOUT_EXIT_CODE) {
+        combined_output.push_str(&format!("\n--- timed out after {} ---", timeout));
+    }
+
+    Ok(ShellCommandResult {
+        success: output.status.success(),
+        combined_output,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn commands(stages: &[VerificationStage]) -> Vec<&str> {
+        stages.iter().map(|stage| stage.command.as_str()).collect()
+    }
+
+    // -----------------------------------------------------------------------
+    // Build system detection tests
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn detect_pipeline_with_makefile() {
+        let temp_dir = TempDir::new().unwrap();
+        let makefile_content = "build:\n\tcargo build\n\ntest:\n\tcargo test\n";
+        fs::write(temp_dir.path().join("Makefile"), makefile_content).unwrap();
+
+        let stages = detect_pipeline(temp_dir.path()).unwrap();
+        assert_eq!(commands(&stages), ["make build", "make test"]);
+        assert!(stages.iter().all(|stage| stage.required));
+    }
+
+    #[test]
+    fn detect_pipeline_makefile_without_targets() {
+        let temp_dir = TempDir::new().unwrap();
+        let makefile_content = "clean:\n\trm -rf target\n";
+        fs::write(temp_dir.path().join("Makefile"), makefile_content).unwrap();
+
+        // Makefile에 build/test 타겟이 없으면 None
+        let result = detect_pipeline(temp_dir.path());
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn detect_pipeline_with_cargo_toml() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"test\"\n",
+        )
+        .unwrap();
+
+        let stages = detect_pipeline(temp_dir.path()).unwrap();
+        assert_eq!(commands(&stages), ["cargo build", "cargo test"]);
+    }
+
+    #[test]
+    fn detect_pipeline_with_package_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let package_json = serde_json::json!({
+            "scripts": { "build": "tsc", "test": "jest" }
+        });
+        fs::write(
+            temp_dir.path().join("package.json"),
+            serde_json::to_string(&package_json).unwrap(),
+        )
+        .unwrap();
+
+        let stages = detect_pipeline(temp_dir.path()).unwrap();
+        assert_eq!(commands(&stages), ["npm run build", "npm test"]);
+    }
+
+    #[test]
+    fn detect_pipeline_with_go_mod() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("go.mod"),
+            "module example.com/test\n",
+        )
+        .unwrap();
+
+        let stages = detect_pipeline(temp_dir.path()).unwrap();
+        assert_eq!(commands(&stages), ["go build ./...", "go test ./..."]);
+    }
+
+    #[test]
+    fn detect_pipeline_returns_none_for_empty_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = detect_pipeline(temp_dir.path());
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn detect_pipeline_makefile_has_priority_over_cargo() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("Makefile"),
+            "build:\n\tcargo build\n\ntest:\n\tcargo test\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"test\"\n",
+        )
+        .unwrap();
+
+        let stages = detect_pipeline(temp_dir.path()).unwrap();
+        assert_eq!(commands(&stages), ["make build", "make test"]);
+    }
+
+    // -----------------------------------------------------------------------
+    // Pipeline execution tests
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn run_pipeline_success() {
+        let temp_dir = TempDir::new().unwrap();
+        let stages = build_and_test_stages("true", "true");
+
+        let outcome = run_pipeline(temp_dir.path(), &stages).unwrap();
+        assert!(outcome.failures.is_empty());
+    }
+
+    #[test]
+    fn run_pipeline_stops_at_first_required_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let stages = build_and_test_stages("echo build_ok && exit 1", "touch tested");
+
+        let outcome = run_pipeline(temp_dir.path(), &stages).unwrap();
+
+        let failure = outcome.blocking_failure().unwrap();
+        assert_eq!(failure.stage.name, "build");
+        assert!(failure.output.contains("build_ok"));
+        assert!(!temp_dir.path().join("tested").exists());
+    }
+
+    #[test]
+    fn optional_failure_does_not_block_later_stages() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("web")).unwrap();
+        let mut lint = VerificationStage::required("lint", "echo \"$LINT_LEVEL\" && exit 1");
+        lint.required = false;
+        lint.environment.insert("LINT_LEVEL".to_string(), "strict".to_string());
+        let mut test = VerificationStage::required("test", "touch tested");
+        test.working_directory = Some(PathBuf::from("web"));
+
+        let outcome = run_pipeline(temp_dir.path(), &[lint, test]).unwrap();
+
+        assert!(outcome.blocking_failure().is_none());
+        let optional: Vec<&StageFailure> = outcome.optional_failures().collect();
+        assert_eq!(optional.len(), 1);
+        assert!(optional[0].output.contains("strict"));
+        assert!(temp_dir.path().join("web").join("tested").exists());
+    }
+
+    #[test]
+    fn stage_timeout_is_reported() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut stage = VerificationStage::required("slow", "sleep 5");
+        stage.timeout_seconds = 1;
+
+        let outcome = run_pipeline(temp_dir.path(), &[stage]).unwrap();
+
+        assert!(outcome.blocking_failure().unwrap().output.contains("timed out after 1s"));
+    }
+
+    #[test]
+    fn validate_pipeline_rejects_duplicates_and_escaping_directories() {
+        let stages = build_and_test_stages("make", "make test");
+        assert!(validate_pipeline(&stages).is_ok());
+
+        let duplicated = build_and_test_stages("make", "make test")
+            .into_iter()
+            .map(|stage| VerificationStage { name: "check".to_string(), ..stage })
+            .collect::<Vec<_>>();
+        assert!(validate_pipeline(&duplicated).is_err());
+
+        let mut escaping = VerificationStage::required("lint", "eslint .");
+        escaping.working_directory = Some(PathBuf::from("../other"));
+        assert!(validate_pipeline(&[escaping]).is_err());
+    }
+}